@@ -0,0 +1,51 @@
+use std::fmt;
+
+use api_types::{ErrorCode, ErrorResponse};
+
+/// APIクライアントのエラー
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientError {
+    /// バックエンドがエラーレスポンスを返した
+    Api { status: u16, body: ErrorResponse },
+    /// エラーレスポンスの形式でない失敗（プロキシのエラーページなど）
+    Http { status: u16, body: String },
+    /// 通信に失敗した
+    Network(String),
+    /// レスポンスを解釈できなかった
+    Decode(String),
+}
+
+impl ClientError {
+    /// HTTPステータス（通信・解釈の失敗では `None`）
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ClientError::Api { status, .. } | ClientError::Http { status, .. } => Some(*status),
+            ClientError::Network(_) | ClientError::Decode(_) => None,
+        }
+    }
+
+    /// バックエンドのエラーコード（表示する文言はこのコードで選ぶ）
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            ClientError::Api { body, .. } => Some(body.error),
+            _ => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(404)
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Api { body, .. } => write!(f, "{}", body.message),
+            ClientError::Http { status, .. } => write!(f, "サーバーエラー (HTTP {})", status),
+            ClientError::Network(e) => write!(f, "サーバーに接続できませんでした: {}", e),
+            ClientError::Decode(e) => write!(f, "レスポンスを解釈できませんでした: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
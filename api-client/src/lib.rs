@@ -0,0 +1,454 @@
+//! Todo APIの型付きクライアント
+//!
+//! リクエスト・レスポンスは `api-types` の型を使う。ブラウザ（`wasm` フィーチャー）と
+//! ネイティブ（`native` フィーチャー）のどちらでも同じAPIで使える
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), api_client::ClientError> {
+//! use api_client::{ApiClient, CreateTodoDto};
+//!
+//! let client = ApiClient::new("http://localhost:8000");
+//! let todo = client
+//!     .create_todo(&CreateTodoDto {
+//!         title: "牛乳を買う".to_string(),
+//!         completed: false,
+//!         due: None,
+//!     })
+//!     .await?;
+//! client.delete_todo(todo.id).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod error;
+mod transport;
+
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub use api_types::{
+    CalendarFeedDto, CreateTodoDto, ErrorCode, ErrorResponse, ImportReportDto, ImportRowDto,
+    ImportRowStatus, MoveTodoDto, SyncResponseDto, TodoEventDto, TodoResponseDto, TransferFormat,
+    UpdateTodoDto,
+};
+pub use error::ClientError;
+
+use transport::{Body, Method, RawResponse, Transport};
+
+/// APIのバージョン付きパス
+const API_PREFIX: &str = "/api/v1";
+
+/// 冪等なリクエスト（GET/PUT/DELETE）の再試行設定
+/// 通信の失敗と 429/502/503/504 を再試行する。POSTは二重作成を避けるため再試行しない
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 最初のリクエストに加えて再試行する回数
+    pub max_retries: u32,
+    /// 初回の待ち時間（以降は倍にしていく）
+    pub base_delay: Duration,
+    /// 待ち時間の上限。`Retry-After` がこれを超える場合は再試行しない
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 再試行しない
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+}
+
+/// Todo APIクライアント
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    base_url: String,
+    retry: RetryPolicy,
+    /// `Accept-Language` に送る言語（エラーメッセージの言語）
+    language: Option<String>,
+    /// `Authorization: Bearer` に送るトークン
+    token: Option<String>,
+    transport: Transport,
+}
+
+impl ApiClient {
+    /// `base_url` はスキームとホスト（例: `http://localhost:8000`）
+    /// ブラウザで同じオリジンのAPIを呼ぶ場合は空文字列を指定する
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            retry: RetryPolicy::default(),
+            language: None,
+            token: None,
+            transport: Transport::new(),
+        }
+    }
+
+    /// 再試行の設定を変更
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// エラーメッセージの言語（`ja` など）を指定
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.set_language(language);
+        self
+    }
+
+    /// エラーメッセージの言語を変更
+    pub fn set_language(&mut self, language: impl Into<String>) {
+        self.language = Some(language.into());
+    }
+
+    /// `Authorization: Bearer` で送るAPIトークンを指定
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// 全Todoを取得
+    pub async fn list_todos(&self) -> Result<Vec<TodoResponseDto>, ClientError> {
+        self.request(Method::Get, "/todos", None::<&()>).await
+    }
+
+    /// IDでTodoを取得
+    pub async fn get_todo(&self, id: i32) -> Result<TodoResponseDto, ClientError> {
+        self.request(Method::Get, &format!("/todos/{}", id), None::<&()>)
+            .await
+    }
+
+    /// Todoを作成
+    pub async fn create_todo(&self, dto: &CreateTodoDto) -> Result<TodoResponseDto, ClientError> {
+        self.request(Method::Post, "/todos", Some(dto)).await
+    }
+
+    /// Todoを更新
+    pub async fn update_todo(
+        &self,
+        id: i32,
+        dto: &UpdateTodoDto,
+    ) -> Result<TodoResponseDto, ClientError> {
+        self.request(Method::Put, &format!("/todos/{}", id), Some(dto))
+            .await
+    }
+
+    /// Todoを別のTodoの直前・直後に移動
+    pub async fn move_todo(
+        &self,
+        id: i32,
+        dto: &MoveTodoDto,
+    ) -> Result<TodoResponseDto, ClientError> {
+        self.request(Method::Post, &format!("/todos/{}/move", id), Some(dto))
+            .await
+    }
+
+    /// Todoを削除
+    pub async fn delete_todo(&self, id: i32) -> Result<(), ClientError> {
+        self.send(Method::Delete, &format!("/todos/{}", id), None)
+            .await
+            .map(|_| ())
+    }
+
+    /// 前回の同期以降の変更を取得
+    /// `since` には前回のレスポンスの `token` を渡す（`None` なら全件）
+    pub async fn sync(&self, since: Option<&str>) -> Result<SyncResponseDto, ClientError> {
+        let path = match since {
+            Some(token) => format!("/sync?since={}", token),
+            None => "/sync".to_string(),
+        };
+        self.request(Method::Get, &path, None::<&()>).await
+    }
+
+    /// カレンダーフィードの購読URL（初めて取得したときに発行される）
+    pub async fn calendar_feed(&self) -> Result<CalendarFeedDto, ClientError> {
+        self.request(Method::Get, "/calendar", None::<&()>).await
+    }
+
+    /// カレンダーフィードの購読URLを再発行（古いURLは使えなくなる）
+    pub async fn rotate_calendar_feed(&self) -> Result<CalendarFeedDto, ClientError> {
+        self.request(Method::Post, "/calendar/rotate", None::<&()>)
+            .await
+    }
+
+    /// CalDAVのパスワードを再発行（古いパスワードは使えなくなる）
+    pub async fn rotate_caldav_password(&self) -> Result<CalendarFeedDto, ClientError> {
+        self.request(Method::Post, "/calendar/caldav/rotate", None::<&()>)
+            .await
+    }
+
+    /// すべてのTodoを `format` のファイルとして取得
+    pub async fn export(&self, format: TransferFormat) -> Result<String, ClientError> {
+        self.send(
+            Method::Get,
+            &format!("/export?format={}", format.as_str()),
+            None,
+        )
+        .await
+        .map(|res| res.body)
+    }
+
+    /// ファイルからTodoを作成する（`dry_run` なら作成せずに結果だけを返す）
+    /// `format` を省略すると `file_name` の拡張子から推測される
+    pub async fn import(
+        &self,
+        file_name: &str,
+        content: &str,
+        format: Option<TransferFormat>,
+        dry_run: bool,
+    ) -> Result<ImportReportDto, ClientError> {
+        let mut path = format!("/import?dry_run={}", dry_run);
+        if let Some(format) = format {
+            path.push_str(&format!("&format={}", format.as_str()));
+        }
+        let res = self
+            .send(
+                Method::Post,
+                &path,
+                Some(multipart_file(file_name, content)),
+            )
+            .await?;
+        serde_json::from_str(&res.body).map_err(|e| ClientError::Decode(e.to_string()))
+    }
+
+    /// リクエストを送り、成功時のボディをJSONとして解釈する
+    async fn request<T: DeserializeOwned, B: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+    ) -> Result<T, ClientError> {
+        let body = body
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| ClientError::Decode(e.to_string()))?
+            .map(Body::json);
+        let res = self.send(method, path, body).await?;
+        serde_json::from_str(&res.body).map_err(|e| ClientError::Decode(e.to_string()))
+    }
+
+    /// リクエストを送る。冪等なリクエストは `RetryPolicy` に従って再試行する
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Body>,
+    ) -> Result<RawResponse, ClientError> {
+        let url = format!("{}{}{}", self.base_url, API_PREFIX, path);
+        let mut attempt = 0;
+
+        loop {
+            let result = self
+                .transport
+                .send(
+                    method,
+                    &url,
+                    self.language.as_deref(),
+                    self.token.as_deref(),
+                    body.clone(),
+                )
+                .await;
+            let (retryable, retry_after) = match &result {
+                Ok(res) => (
+                    matches!(res.status, 429 | 502 | 503 | 504),
+                    res.retry_after.map(Duration::from_secs),
+                ),
+                Err(_) => (true, None),
+            };
+
+            if retryable && method.is_idempotent() && attempt < self.retry.max_retries {
+                let delay = retry_after.unwrap_or_else(|| {
+                    (self.retry.base_delay * 2u32.saturating_pow(attempt)).min(self.retry.max_delay)
+                });
+                if delay <= self.retry.max_delay {
+                    attempt += 1;
+                    transport::sleep(delay).await;
+                    continue;
+                }
+            }
+
+            return match result {
+                Ok(res) if (200..300).contains(&res.status) => Ok(res),
+                Ok(res) => Err(error_response(res)),
+                Err(e) => Err(ClientError::Network(e)),
+            };
+        }
+    }
+}
+
+/// 成功以外のレスポンスのエラー
+fn error_response(res: RawResponse) -> ClientError {
+    match serde_json::from_str::<ErrorResponse>(&res.body) {
+        Ok(body) => ClientError::Api {
+            status: res.status,
+            body,
+        },
+        Err(_) => ClientError::Http {
+            status: res.status,
+            body: res.body,
+        },
+    }
+}
+
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+impl ApiClient {
+    /// Todoの変更の通知を購読する（Server-Sent Events。ネイティブのみ）
+    /// 通知を受け取ったら `sync` で差分を取得する。通知に対応していないサーバーは404を返す
+    pub async fn watch(&self) -> Result<TodoEvents, ClientError> {
+        let url = format!("{}{}/events", self.base_url, API_PREFIX);
+        match self
+            .transport
+            .open_events(&url, self.language.as_deref(), self.token.as_deref())
+            .await
+        {
+            Ok(Ok(stream)) => Ok(TodoEvents { stream }),
+            Ok(Err(res)) => Err(error_response(res)),
+            Err(e) => Err(ClientError::Network(e)),
+        }
+    }
+}
+
+/// 購読中の変更の通知
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+#[derive(Debug)]
+pub struct TodoEvents {
+    stream: transport::EventStream,
+}
+
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+impl TodoEvents {
+    /// 次の通知。接続が閉じたら `None`（再接続は呼び出し側で行う）
+    pub async fn next(&mut self) -> Option<Result<TodoEventDto, ClientError>> {
+        let data = self.stream.next_data().await?;
+        Some(data.map_err(ClientError::Network).and_then(|data| {
+            serde_json::from_str(&data).map_err(|e| ClientError::Decode(e.to_string()))
+        }))
+    }
+}
+
+/// `file` フィールドに1つのファイルを載せた multipart/form-data
+fn multipart_file(file_name: &str, content: &str) -> Body {
+    // 内容に現れない区切り（ファイルはテキストのため、長さを変えて探せば必ず見つかる）
+    let boundary = (0..)
+        .map(|n| format!("todo-app-boundary-{}", n))
+        .find(|boundary| !content.contains(boundary.as_str()))
+        .unwrap_or_default();
+    let file_name = file_name.replace(['"', '\r', '\n'], "_");
+    Body {
+        content_type: format!("multipart/form-data; boundary={}", boundary),
+        content: format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\r\n{content}\r\n--{boundary}--\r\n"
+        ),
+    }
+}
+
+#[cfg(all(test, feature = "native", not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 用意したレスポンスを順に返すHTTPサーバー
+    async fn serve(responses: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+        let served = count.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await.unwrap();
+                served.fetch_add(1, Ordering::SeqCst);
+                let (status, body) = response.split_once('\n').unwrap();
+                let raw = format!(
+                    "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(raw.as_bytes()).await.unwrap();
+            }
+        });
+        (format!("http://{}", addr), count)
+    }
+
+    fn client(base_url: String) -> ApiClient {
+        ApiClient::new(base_url).with_retry(RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retries_idempotent_requests_and_decodes_error_body() {
+        // GETは503の後に再試行して成功する
+        let (url, count) = serve(vec![
+            "503 Service Unavailable\n{\"error\":\"internal_error\",\"message\":\"停止中\"}",
+            "200 OK\n[{\"id\":1,\"title\":\"牛乳を買う\",\"completed\":false}]",
+        ])
+        .await;
+        let todos = client(url).list_todos().await.unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        // POSTは再試行せず、エラーボディを返す
+        let (url, count) = serve(vec![
+            "503 Service Unavailable\n{\"error\":\"internal_error\",\"message\":\"停止中\"}",
+        ])
+        .await;
+        let err = client(url)
+            .create_todo(&CreateTodoDto {
+                title: "牛乳を買う".to_string(),
+                completed: false,
+                due: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert_eq!(err.status(), Some(503));
+        assert_eq!(err.code(), Some(ErrorCode::InternalError));
+        assert_eq!(err.to_string(), "停止中");
+
+        // エラーレスポンスの形式でないボディ
+        let (url, _) = serve(vec!["404 Not Found\nnot json"]).await;
+        let err = client(url).get_todo(1).await.unwrap_err();
+        assert!(err.is_not_found());
+        assert!(matches!(err, ClientError::Http { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_watch_reads_server_sent_events() {
+        let (url, _) = serve(vec![
+            "200 OK\nretry: 5000\n\n: keep-alive\n\ndata: {\"type\":\"changed\",\"id\":1}\r\n\r\ndata: {\"type\":\"resync\"}\n\n",
+        ])
+        .await;
+        let mut events = client(url).watch().await.unwrap();
+        assert_eq!(
+            events.next().await,
+            Some(Ok(TodoEventDto::Changed { id: 1 }))
+        );
+        assert_eq!(events.next().await, Some(Ok(TodoEventDto::Resync)));
+        assert_eq!(events.next().await, None);
+
+        // 通知に対応していないサーバー
+        let (url, _) = serve(vec!["404 Not Found\nnot found"]).await;
+        assert!(client(url).watch().await.unwrap_err().is_not_found());
+    }
+}
@@ -0,0 +1,63 @@
+//! HTTP通信の実装
+//! ネイティブでは `native` フィーチャー（reqwest）を優先し、wasm32では `wasm` フィーチャー（gloo-net）を使う
+
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+mod native;
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+pub use native::{sleep, EventStream, Transport};
+
+#[cfg(all(
+    feature = "wasm",
+    not(all(feature = "native", not(target_arch = "wasm32")))
+))]
+mod wasm;
+#[cfg(all(
+    feature = "wasm",
+    not(all(feature = "native", not(target_arch = "wasm32")))
+))]
+pub use wasm::{sleep, Transport};
+
+#[cfg(not(any(all(feature = "native", not(target_arch = "wasm32")), feature = "wasm")))]
+compile_error!(
+    "api-client: ネイティブでは `native`、wasm32では `wasm` フィーチャーを有効にしてください"
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    /// 再試行しても結果が変わらないメソッドか
+    pub fn is_idempotent(self) -> bool {
+        !matches!(self, Method::Post)
+    }
+}
+
+/// リクエストボディ
+#[derive(Debug, Clone)]
+pub struct Body {
+    pub content_type: String,
+    pub content: String,
+}
+
+impl Body {
+    pub fn json(content: String) -> Self {
+        Self {
+            content_type: "application/json".to_string(),
+            content,
+        }
+    }
+}
+
+/// 解釈前のレスポンス
+#[derive(Debug)]
+pub struct RawResponse {
+    pub status: u16,
+    /// `Retry-After` ヘッダーの秒数
+    pub retry_after: Option<u64>,
+    pub body: String,
+}
@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use super::{Body, Method, RawResponse};
+
+/// reqwestによる通信
+#[derive(Debug, Clone, Default)]
+pub struct Transport {
+    http: reqwest::Client,
+}
+
+impl Transport {
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        language: Option<&str>,
+        token: Option<&str>,
+        body: Option<Body>,
+    ) -> Result<RawResponse, String> {
+        let mut request = self
+            .request(method, url, language, token)
+            .header("accept", "application/json");
+        if let Some(body) = body {
+            request = request
+                .header("content-type", body.content_type)
+                .body(body.content);
+        }
+
+        let res = request.send().await.map_err(|e| e.to_string())?;
+        raw_response(res).await
+    }
+
+    /// Server-Sent Eventsのストリームを開く。成功以外のステータスはボディを読んで返す
+    pub async fn open_events(
+        &self,
+        url: &str,
+        language: Option<&str>,
+        token: Option<&str>,
+    ) -> Result<Result<EventStream, RawResponse>, String> {
+        let res = self
+            .request(Method::Get, url, language, token)
+            .header("accept", "text/event-stream")
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if res.status().is_success() {
+            Ok(Ok(EventStream {
+                response: res,
+                buffer: Vec::new(),
+            }))
+        } else {
+            raw_response(res).await.map(Err)
+        }
+    }
+
+    fn request(
+        &self,
+        method: Method,
+        url: &str,
+        language: Option<&str>,
+        token: Option<&str>,
+    ) -> reqwest::RequestBuilder {
+        let method = match method {
+            Method::Get => reqwest::Method::GET,
+            Method::Post => reqwest::Method::POST,
+            Method::Put => reqwest::Method::PUT,
+            Method::Delete => reqwest::Method::DELETE,
+        };
+        let mut request = self.http.request(method, url);
+        if let Some(language) = language {
+            request = request.header("accept-language", language);
+        }
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {}", token));
+        }
+        request
+    }
+}
+
+async fn raw_response(res: reqwest::Response) -> Result<RawResponse, String> {
+    let status = res.status().as_u16();
+    let retry_after = res
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+    let body = res.text().await.map_err(|e| e.to_string())?;
+    Ok(RawResponse {
+        status,
+        retry_after,
+        body,
+    })
+}
+
+/// 開いたままのServer-Sent Eventsのレスポンス
+#[derive(Debug)]
+pub struct EventStream {
+    response: reqwest::Response,
+    /// まだ区切り（空行）まで届いていない部分
+    buffer: Vec<u8>,
+}
+
+impl EventStream {
+    /// 次のイベントの `data`（コメントだけのイベントは読み飛ばす）。接続が閉じたら `None`
+    pub async fn next_data(&mut self) -> Option<Result<String, String>> {
+        loop {
+            if let Some(end) = self.buffer.windows(2).position(|pair| pair == b"\n\n") {
+                let event: Vec<u8> = self.buffer.drain(..end + 2).collect();
+                let event = String::from_utf8_lossy(&event);
+                let data: Vec<&str> = event
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("data:"))
+                    .map(|data| data.strip_prefix(' ').unwrap_or(data))
+                    .collect();
+                if !data.is_empty() {
+                    return Some(Ok(data.join("\n")));
+                }
+                continue;
+            }
+            match self.response.chunk().await {
+                // 改行は CRLF でも LF でもよいため、CR を除いて LF だけで区切る
+                Ok(Some(chunk)) => self
+                    .buffer
+                    .extend(chunk.iter().filter(|byte| **byte != b'\r')),
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e.to_string())),
+            }
+        }
+    }
+}
+
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
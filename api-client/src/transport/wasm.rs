@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use gloo_net::http::{Method as HttpMethod, RequestBuilder};
+
+use super::{Body, Method, RawResponse};
+
+/// ブラウザのfetch（gloo-net）による通信
+#[derive(Debug, Clone, Default)]
+pub struct Transport;
+
+impl Transport {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        language: Option<&str>,
+        token: Option<&str>,
+        body: Option<Body>,
+    ) -> Result<RawResponse, String> {
+        let method = match method {
+            Method::Get => HttpMethod::GET,
+            Method::Post => HttpMethod::POST,
+            Method::Put => HttpMethod::PUT,
+            Method::Delete => HttpMethod::DELETE,
+        };
+        let mut request = RequestBuilder::new(url)
+            .method(method)
+            .header("accept", "application/json");
+        if let Some(language) = language {
+            request = request.header("accept-language", language);
+        }
+        if let Some(token) = token {
+            request = request.header("authorization", &format!("Bearer {}", token));
+        }
+        let request = match body {
+            Some(body) => request
+                .header("content-type", &body.content_type)
+                .body(body.content),
+            None => request.build(),
+        }
+        .map_err(|e| e.to_string())?;
+
+        let res = request.send().await.map_err(|e| e.to_string())?;
+        let status = res.status();
+        let retry_after = res
+            .headers()
+            .get("retry-after")
+            .and_then(|v| v.parse().ok());
+        let body = res.text().await.map_err(|e| e.to_string())?;
+        Ok(RawResponse {
+            status,
+            retry_after,
+            body,
+        })
+    }
+}
+
+pub async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// カレンダーフィード（iCalendar）の購読URLとCalDAVの接続先
+/// URLとパスワードを知っていれば誰でも読めるため、共有しないこと
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CalendarFeedDto {
+    /// ホストを除いたパス（`/ical/{token}.ics`）
+    #[cfg_attr(feature = "openapi", schema(example = "/ical/0f3c…9a.ics"))]
+    pub path: String,
+    /// サーバーの公開URL（`server.public_url`）から組み立てたURL
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "https://todo.example.com/ical/0f3c…9a.ics")
+    )]
+    pub url: String,
+    /// CalDAVのサーバーのパス（`/dav/`）
+    #[cfg_attr(feature = "openapi", schema(example = "/dav/"))]
+    pub caldav_path: String,
+    /// CalDAVのパスワード（ユーザー名は任意）。フィードのURLのトークンとは別のもので、別に再発行する
+    pub caldav_password: String,
+}
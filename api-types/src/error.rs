@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+/// 機械判定用のエラーコード
+/// 値は変えない（クライアントはメッセージではなくコードで判定し、表示する文言を選ぶ）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// タイトルが空（空白のみを含む）
+    TitleEmpty,
+    TodoNotFound,
+    /// 並べ替えの移動先のTodoが見つからない
+    MoveTargetNotFound,
+    /// 並べ替えで `before` と `after` の指定が不正
+    InvalidMove,
+    /// 期限を日付・日時として解釈できない
+    InvalidDue,
+    InvalidSyncToken,
+    /// インポート・エクスポートの形式が不明（指定がなく、ファイル名からも推測できない）
+    UnsupportedFormat,
+    /// インポートするファイルを読み込めない
+    InvalidImportFile,
+    /// インポートするファイルの行を読み込めない（インポート結果の行ごとのエラー）
+    InvalidImportRow,
+    /// カレンダーフィードのURLが無効（再発行された古いURLなど）
+    CalendarFeedNotFound,
+    /// リクエストボディのJSONが不正
+    InvalidJson,
+    /// GraphQLでまとめて送った操作の数が上限を超えた
+    BatchTooLarge,
+    PayloadTooLarge,
+    UnsupportedApiVersion,
+    RateLimited,
+    CsrfTokenMismatch,
+    InternalError,
+    /// このクライアントが知らない新しいコード
+    #[serde(other)]
+    Unknown,
+}
+
+impl ErrorCode {
+    /// サーバーが返すコード（`Unknown` 以外）
+    pub const ALL: [ErrorCode; 17] = [
+        ErrorCode::TitleEmpty,
+        ErrorCode::TodoNotFound,
+        ErrorCode::MoveTargetNotFound,
+        ErrorCode::InvalidMove,
+        ErrorCode::InvalidDue,
+        ErrorCode::InvalidSyncToken,
+        ErrorCode::UnsupportedFormat,
+        ErrorCode::InvalidImportFile,
+        ErrorCode::InvalidImportRow,
+        ErrorCode::CalendarFeedNotFound,
+        ErrorCode::InvalidJson,
+        ErrorCode::BatchTooLarge,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::UnsupportedApiVersion,
+        ErrorCode::RateLimited,
+        ErrorCode::CsrfTokenMismatch,
+        ErrorCode::InternalError,
+    ];
+
+    /// JSONでの表記（`title_empty` など）
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::TitleEmpty => "title_empty",
+            ErrorCode::TodoNotFound => "todo_not_found",
+            ErrorCode::MoveTargetNotFound => "move_target_not_found",
+            ErrorCode::InvalidMove => "invalid_move",
+            ErrorCode::InvalidDue => "invalid_due",
+            ErrorCode::InvalidSyncToken => "invalid_sync_token",
+            ErrorCode::UnsupportedFormat => "unsupported_format",
+            ErrorCode::InvalidImportFile => "invalid_import_file",
+            ErrorCode::InvalidImportRow => "invalid_import_row",
+            ErrorCode::CalendarFeedNotFound => "calendar_feed_not_found",
+            ErrorCode::InvalidJson => "invalid_json",
+            ErrorCode::BatchTooLarge => "batch_too_large",
+            ErrorCode::PayloadTooLarge => "payload_too_large",
+            ErrorCode::UnsupportedApiVersion => "unsupported_api_version",
+            ErrorCode::RateLimited => "rate_limited",
+            ErrorCode::CsrfTokenMismatch => "csrf_token_mismatch",
+            ErrorCode::InternalError => "internal_error",
+            ErrorCode::Unknown => "unknown",
+        }
+    }
+
+    /// メッセージカタログ（Fluent）でのID（`error-title-empty` など）
+    pub fn message_id(self) -> String {
+        format!("error-{}", self.as_str().replace('_', "-"))
+    }
+}
+
+/// APIのエラーレスポンス
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    /// 機械判定用のエラーコード
+    #[cfg_attr(feature = "openapi", schema(example = "todo_not_found"))]
+    pub error: ErrorCode,
+    /// 表示用のメッセージ（`Accept-Language` の言語）
+    #[cfg_attr(feature = "openapi", schema(example = "Todo not found"))]
+    pub message: String,
+    /// 再試行できるまでの秒数（レート制限時のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+impl ErrorResponse {
+    pub fn new(error: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            error,
+            message: message.into(),
+            retry_after_secs: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code_round_trips_and_tolerates_unknown_codes() {
+        let json = serde_json::to_string(&ErrorCode::MoveTargetNotFound).unwrap();
+        assert_eq!(
+            json,
+            format!("\"{}\"", ErrorCode::MoveTargetNotFound.as_str())
+        );
+        assert_eq!(
+            ErrorCode::MoveTargetNotFound.message_id(),
+            "error-move-target-not-found"
+        );
+
+        let body: ErrorResponse =
+            serde_json::from_str(r#"{"error":"added_later","message":"..."}"#).unwrap();
+        assert_eq!(body.error, ErrorCode::Unknown);
+    }
+}
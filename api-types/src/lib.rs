@@ -0,0 +1,15 @@
+//! バックエンドとフロントエンドで共有するAPIのリクエスト・レスポンスの型
+//!
+//! どちらもこの型でシリアライズ・デシリアライズするため、形式の食い違いはコンパイルエラーになる
+
+mod calendar;
+mod error;
+mod sync;
+mod todo;
+mod transfer;
+
+pub use calendar::CalendarFeedDto;
+pub use error::{ErrorCode, ErrorResponse};
+pub use sync::{SyncResponseDto, TodoEventDto};
+pub use todo::{CreateTodoDto, MoveTodoDto, TodoResponseDto, UpdateTodoDto};
+pub use transfer::{ImportReportDto, ImportRowDto, ImportRowStatus, TransferFormat};
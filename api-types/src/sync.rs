@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::TodoResponseDto;
+
+/// 差分同期のレスポンスDTO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SyncResponseDto {
+    /// `since` 以降に作成・変更されたTodo（`full` の場合はすべてのTodo）
+    pub todos: Vec<TodoResponseDto>,
+    /// `since` 以降に削除されたTodoのID
+    pub deleted: Vec<i32>,
+    /// 次回の `since` に渡すトークン
+    #[cfg_attr(feature = "openapi", schema(example = "42"))]
+    pub token: String,
+    /// 差分ではなく全件を返した（手元の一覧を `todos` で置き換える）
+    pub full: bool,
+}
+
+/// 変更の通知（`GET /api/v1/events` のServer-Sent Eventsの `data`）
+/// 受け取ったら差分同期（`GET /api/v1/sync`）で変更を取得する
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TodoEventDto {
+    /// 作成・更新された
+    Changed { id: i32 },
+    /// 削除された
+    Deleted { id: i32 },
+    /// 通知を取りこぼした（差分同期で追いつく）
+    Resync,
+}
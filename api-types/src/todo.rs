@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// Todo作成リクエストDTO
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateTodoDto {
+    /// 空白のみは不可
+    #[cfg_attr(feature = "openapi", schema(example = "牛乳を買う"))]
+    pub title: String,
+    #[serde(default)]
+    pub completed: bool,
+    /// 期限（`YYYY-MM-DD` か、オフセット付きの RFC 3339 の日時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = "2025-11-30"))]
+    pub due: Option<String>,
+}
+
+/// Todo更新リクエストDTO
+/// 指定した項目だけを更新する
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateTodoDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<bool>,
+    /// 期限（`YYYY-MM-DD` か、オフセット付きの RFC 3339 の日時）。空文字列で期限なしにする
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+}
+
+/// Todo並べ替えリクエストDTO
+/// `before` か `after` のどちらか一方に、隣に置くTodoのIDを指定する
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MoveTodoDto {
+    /// このTodoの直前に移動する
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<i32>,
+    /// このTodoの直後に移動する
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<i32>,
+}
+
+/// TodoレスポンスDTO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TodoResponseDto {
+    pub id: i32,
+    pub title: String,
+    pub completed: bool,
+    /// 並び順（昇順に並べる）。値そのものに意味はなく、並べ替えのたびに変わりうる
+    #[serde(default)]
+    pub position: f64,
+    /// 作成日時（RFC 3339）。未送信のTodoなどでは `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = "2025-11-25T00:21:55Z"))]
+    pub created_at: Option<String>,
+    /// 期限（`YYYY-MM-DD` か、UTCの RFC 3339 の日時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = "2025-11-30"))]
+    pub due: Option<String>,
+    /// 完了日時（RFC 3339）。完了日時を記録する前に完了にしたTodoでは `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+}
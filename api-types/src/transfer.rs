@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ErrorCode;
+
+/// インポート・エクスポートのファイル形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum TransferFormat {
+    /// `title,completed,created_at` の列を持つCSV
+    #[serde(rename = "csv")]
+    Csv,
+    /// Todoの配列
+    #[serde(rename = "json")]
+    Json,
+    /// `- [ ]` / `- [x]` のチェックリスト
+    #[serde(rename = "md")]
+    Markdown,
+    /// todo.txt 形式（<https://github.com/todotxt/todo.txt>）
+    #[serde(rename = "todotxt")]
+    TodoTxt,
+}
+
+impl TransferFormat {
+    pub const ALL: [TransferFormat; 4] = [
+        TransferFormat::Csv,
+        TransferFormat::Json,
+        TransferFormat::Markdown,
+        TransferFormat::TodoTxt,
+    ];
+
+    /// クエリパラメーターでの表記
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "csv",
+            TransferFormat::Json => "json",
+            TransferFormat::Markdown => "md",
+            TransferFormat::TodoTxt => "todotxt",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(value))
+    }
+
+    /// エクスポートするファイル名（`todos.csv` など）
+    pub fn file_name(self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "todos.csv",
+            TransferFormat::Json => "todos.json",
+            TransferFormat::Markdown => "todos.md",
+            TransferFormat::TodoTxt => "todo.txt",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "text/csv; charset=utf-8",
+            TransferFormat::Json => "application/json",
+            TransferFormat::Markdown => "text/markdown; charset=utf-8",
+            TransferFormat::TodoTxt => "text/plain; charset=utf-8",
+        }
+    }
+
+    /// アップロードされたファイル名の拡張子から形式を推測する
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let (_, extension) = name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Some(TransferFormat::Csv),
+            "json" => Some(TransferFormat::Json),
+            "md" | "markdown" => Some(TransferFormat::Markdown),
+            "txt" => Some(TransferFormat::TodoTxt),
+            _ => None,
+        }
+    }
+}
+
+/// インポートした行の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    /// 作成した（ドライランでは作成する）
+    Imported,
+    /// 既存のTodoかファイル内の前の行と同じタイトルのため読み飛ばした
+    Duplicate,
+    /// 読み込めなかった
+    Invalid,
+    /// 作成に失敗した（同じファイルをもう一度インポートすると、この行だけを作成し直す）
+    Failed,
+}
+
+/// インポートした行ごとの結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportRowDto {
+    /// ファイルの行番号（JSONでは配列の何番目か。どちらも1始まり）
+    pub line: usize,
+    pub title: String,
+    pub completed: bool,
+    pub status: ImportRowStatus,
+    /// `invalid` / `failed` の理由
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorCode>,
+    /// 読み込めなかった理由の詳細（パーサーのメッセージ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// インポートの結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportReportDto {
+    /// 確認のみで、Todoは作成していない
+    pub dry_run: bool,
+    /// 作成した（ドライランでは作成する）件数
+    pub imported: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+    /// 作成に失敗した件数（古いサーバーは返さない）
+    #[serde(default)]
+    pub failed: usize,
+    pub rows: Vec<ImportRowDto>,
+}
@@ -0,0 +1,22 @@
+# APIのエラーメッセージ（IDは `error-` + エラーコード）
+
+error-title-empty = Title can't be empty.
+error-todo-not-found = Todo not found.
+error-move-target-not-found = The todo to move next to was not found.
+error-invalid-move = Specify exactly one of "before" or "after".
+error-invalid-due = Invalid due date. Use YYYY-MM-DD or an RFC 3339 date-time with an offset.
+error-invalid-sync-token = Invalid sync token.
+error-unsupported-format = Unsupported format. Use one of csv, json, md or todotxt.
+error-invalid-import-file = The file could not be imported: { $detail }
+error-invalid-import-row = This row could not be read.
+error-calendar-feed-not-found = Calendar feed not found.
+error-invalid-json = Invalid request body: { $detail }
+error-batch-too-large = Too many operations in one batch (maximum { $max }).
+error-payload-too-large = The request body is too large.
+error-unsupported-api-version = Unsupported API version (supported: { $supported }).
+error-rate-limited = Too many requests. Please retry in { $seconds ->
+    [one] { $seconds } second
+   *[other] { $seconds } seconds
+}.
+error-csrf-token-mismatch = CSRF token mismatch.
+error-internal-error = An internal error occurred. Please try again later.
@@ -0,0 +1,19 @@
+# APIのエラーメッセージ（IDは `error-` + エラーコード）
+
+error-title-empty = タイトルは空にできません
+error-todo-not-found = Todoが見つかりません
+error-move-target-not-found = 移動先のTodoが見つかりません
+error-invalid-move = before か after のどちらか一方を指定してください
+error-invalid-due = 期限が不正です。YYYY-MM-DD かオフセット付きの RFC 3339 の日時で指定してください
+error-invalid-sync-token = 不正な同期トークンです
+error-unsupported-format = 未対応の形式です（csv, json, md, todotxt のいずれかを指定してください）
+error-invalid-import-file = ファイルをインポートできません: { $detail }
+error-invalid-import-row = この行を読み込めません
+error-calendar-feed-not-found = カレンダーフィードが見つかりません
+error-invalid-json = リクエストボディが不正です: { $detail }
+error-batch-too-large = まとめて実行できる操作は{ $max }件までです
+error-payload-too-large = リクエストボディが大きすぎます
+error-unsupported-api-version = 未対応のAPIバージョンです（対応: { $supported }）
+error-rate-limited = リクエストが多すぎます。{ $seconds }秒後に再試行してください
+error-csrf-token-mismatch = CSRFトークンが一致しません
+error-internal-error = サーバーでエラーが発生しました。しばらくしてから再試行してください
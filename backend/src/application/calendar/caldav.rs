@@ -0,0 +1,268 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use super::ical;
+use super::vtodo::{self, VTodo};
+use crate::domain::calendar::{
+    CalDavError, CalendarFeedRepository, CalendarResource, CalendarResourceRepository, TokenKind,
+};
+use crate::domain::todo::{Todo, TodoError, TodoRepository};
+
+/// CalDAVのコレクション内の1件のTodo
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalDavObject {
+    pub name: String,
+    /// 引用符付きのETag（内容が変わると変わる）
+    pub etag: String,
+    /// iCalendar（VCALENDAR に VTODO が1つ）
+    pub body: String,
+}
+
+impl CalDavObject {
+    fn new(resource: &CalendarResource, todo: &Todo) -> Self {
+        let body = ical::render_resource(todo, &resource.uid);
+        Self {
+            name: resource.name.clone(),
+            etag: format!("\"{:016x}\"", fnv1a(body.as_bytes())),
+            body,
+        }
+    }
+}
+
+/// 書き込みの条件（`If-Match` / `If-None-Match: *`）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Preconditions {
+    pub if_match: Option<String>,
+    pub if_none_match: bool,
+}
+
+impl Preconditions {
+    fn check(&self, current: Option<&CalDavObject>) -> Result<(), CalDavError> {
+        let satisfied = match current {
+            Some(current) => {
+                !self.if_none_match
+                    && self
+                        .if_match
+                        .as_deref()
+                        .is_none_or(|expected| etag_matches(expected, &current.etag))
+            }
+            None => self.if_match.is_none(),
+        };
+        if satisfied {
+            Ok(())
+        } else {
+            Err(CalDavError::PreconditionFailed)
+        }
+    }
+}
+
+/// `If-Match` の値（`*` かETagの並び）に一致するか
+fn etag_matches(header: &str, etag: &str) -> bool {
+    header
+        .split(',')
+        .map(|candidate| candidate.trim().trim_start_matches("W/"))
+        .any(|candidate| candidate == "*" || candidate == etag)
+}
+
+/// 64ビットのFNV-1aハッシュ（再起動しても同じETagになるよう、標準ライブラリのハッシュは使わない）
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// CalDAVのサービス
+/// Todoをすべて1つのカレンダーコレクションの VTODO として読み書きする。
+/// パスワードはカレンダーフィードのトークンとは別に発行したもの（再発行すると古いパスワードは使えなくなる）
+pub struct CalDavService {
+    feeds: Arc<dyn CalendarFeedRepository>,
+    resources: Arc<dyn CalendarResourceRepository>,
+    todos: Arc<dyn TodoRepository>,
+    /// 書き込みの条件の確認から書き込みまでを、他のCalDAVの書き込みと重ならないようにする
+    /// （同じ名前への同時の作成で重複したり、`If-Match` を確認した後の更新が失われたりしないように）
+    writes: Mutex<()>,
+}
+
+impl CalDavService {
+    pub fn new(
+        feeds: Arc<dyn CalendarFeedRepository>,
+        resources: Arc<dyn CalendarResourceRepository>,
+        todos: Arc<dyn TodoRepository>,
+    ) -> Self {
+        Self {
+            feeds,
+            resources,
+            todos,
+            writes: Mutex::new(()),
+        }
+    }
+
+    /// パスワードがCalDAVのトークンと一致するか（フィードのURLのトークンでは認証しない）
+    pub async fn authenticate(&self, password: &str) -> Result<(), CalDavError> {
+        let valid = self
+            .feeds
+            .find_token(TokenKind::CalDav)
+            .await?
+            .is_some_and(|token| token.matches(password));
+        if valid {
+            Ok(())
+        } else {
+            Err(CalDavError::Unauthorized)
+        }
+    }
+
+    /// コレクション内のすべてのTodo（並び順）
+    pub async fn list(&self) -> Result<Vec<CalDavObject>, CalDavError> {
+        Ok(self
+            .entries()
+            .await?
+            .iter()
+            .map(|(resource, todo)| CalDavObject::new(resource, todo))
+            .collect())
+    }
+
+    /// コレクションのETag（getctag）。どれかのTodoが作成・更新・削除されると変わる
+    pub fn collection_tag(objects: &[CalDavObject]) -> String {
+        let mut hash = fnv1a(b"todos");
+        for object in objects {
+            hash ^= fnv1a(format!("{}\n{}", object.name, object.etag).as_bytes());
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        format!("\"{:016x}\"", hash)
+    }
+
+    /// 名前 `name` のTodo
+    pub async fn get(&self, name: &str) -> Result<CalDavObject, CalDavError> {
+        self.list()
+            .await?
+            .into_iter()
+            .find(|object| object.name == name)
+            .ok_or(CalDavError::NotFound)
+    }
+
+    /// 名前 `name` にTodoを書き込む。作成した場合は `true`
+    pub async fn put(
+        &self,
+        name: &str,
+        body: &str,
+        preconditions: &Preconditions,
+    ) -> Result<bool, CalDavError> {
+        let received = vtodo::parse(body)?;
+        let _write = self.writes.lock().await;
+        let entries = self.entries().await?;
+        let existing = entries.iter().find(|(resource, _)| resource.name == name);
+        preconditions.check(
+            existing
+                .map(|(resource, todo)| CalDavObject::new(resource, todo))
+                .as_ref(),
+        )?;
+
+        // UIDはリソースごとに一意で、作成後は変えられない
+        let conflict = entries
+            .iter()
+            .find(|(resource, _)| (resource.uid == received.uid) != (resource.name == name));
+        if let Some((resource, _)) = conflict {
+            return Err(CalDavError::UidConflict {
+                name: resource.name.clone(),
+            });
+        }
+
+        match existing {
+            Some((_, todo)) => {
+                let mut todo = todo.clone();
+                apply(&mut todo, &received)?;
+                self.todos.update(&todo).await?;
+                Ok(false)
+            }
+            None => {
+                let mut todo = Todo::new(received.summary.clone()).map_err(invalid)?;
+                apply(&mut todo, &received)?;
+                let saved = self.todos.save(&todo).await?;
+                let id = saved
+                    .id()
+                    .ok_or_else(|| "保存したTodoにIDがありません".to_string())?;
+                let resource = CalendarResource {
+                    todo_id: id,
+                    name: name.to_string(),
+                    uid: received.uid,
+                };
+                if resource != CalendarResource::default_for(id) {
+                    self.resources.save(&resource).await?;
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    /// 名前 `name` のTodoを削除する
+    pub async fn delete(
+        &self,
+        name: &str,
+        preconditions: &Preconditions,
+    ) -> Result<(), CalDavError> {
+        let _write = self.writes.lock().await;
+        let entries = self.entries().await?;
+        let (resource, todo) = entries
+            .iter()
+            .find(|(resource, _)| resource.name == name)
+            .ok_or(CalDavError::NotFound)?;
+        preconditions.check(Some(&CalDavObject::new(resource, todo)))?;
+
+        self.todos.delete(resource.todo_id).await?;
+        if *resource != CalendarResource::default_for(resource.todo_id) {
+            self.resources.delete(resource.todo_id).await?;
+        }
+        Ok(())
+    }
+
+    /// すべてのTodoと、その名前とUID
+    async fn entries(&self) -> Result<Vec<(CalendarResource, Todo)>, CalDavError> {
+        let resources = self.resources.find_all().await?;
+        let mut entries: Vec<_> = self
+            .todos
+            .find_all()
+            .await?
+            .into_iter()
+            .filter_map(|todo| {
+                let id = todo.id()?;
+                let resource = resources
+                    .iter()
+                    .find(|resource| resource.todo_id == id)
+                    .cloned()
+                    .unwrap_or_else(|| CalendarResource::default_for(id));
+                Some((resource, todo))
+            })
+            .collect();
+        // クライアントが既定の名前の形式で作ったリソースは、同じ名前になった既定の名前より優先する
+        // （削除済みのTodoの名前は数えない）
+        let saved: Vec<(String, i32)> = entries
+            .iter()
+            .filter(|(resource, _)| resources.contains(resource))
+            .map(|(resource, _)| (resource.name.clone(), resource.todo_id))
+            .collect();
+        entries.retain(|(resource, _)| {
+            !saved
+                .iter()
+                .any(|(name, id)| *name == resource.name && *id != resource.todo_id)
+        });
+        Ok(entries)
+    }
+}
+
+/// 受け取った内容をTodoに反映する
+fn apply(todo: &mut Todo, received: &VTodo) -> Result<(), CalDavError> {
+    todo.change_title(received.summary.clone())
+        .map_err(invalid)?;
+    todo.set_due(received.due);
+    if received.completed {
+        todo.complete();
+    } else {
+        todo.uncomplete();
+    }
+    Ok(())
+}
+
+fn invalid(e: TodoError) -> CalDavError {
+    CalDavError::InvalidCalendarData(e.to_string())
+}
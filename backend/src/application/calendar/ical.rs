@@ -0,0 +1,232 @@
+//! iCalendar（RFC 5545）への書き出し
+//!
+//! 期限のあるTodoを VTODO として書き出す。VTODO を表示できないカレンダーアプリ向けに、
+//! 未完了のTodoの期限を VEVENT としても書き出せる。
+//! 時刻付きの期限はUTC（`Z` 付き）で書くため、VTIMEZONE を付けずにどのタイムゾーンでも同じ時刻を指す。
+//! 日付だけの期限は `VALUE=DATE` で書き、購読する側のタイムゾーンでのその日になる。
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+
+use crate::domain::todo::{Due, Todo};
+
+/// 1行の最大長（オクテット、改行を除く）
+const MAX_LINE_OCTETS: usize = 75;
+
+/// 購読するアプリに勧める再取得の間隔
+const REFRESH_INTERVAL: &str = "PT15M";
+
+/// 書き出しの設定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeedOptions {
+    /// 未完了のTodoの期限を VEVENT としても書き出す
+    pub events: bool,
+}
+
+/// 期限のあるTodoをカレンダーとして書き出す（`now` は DTSTAMP に使う）
+pub fn render(todos: &[Todo], now: DateTime<Utc>, options: FeedOptions) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//todo-app//Todo calendar feed//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:Todos".to_string(),
+        format!("REFRESH-INTERVAL;VALUE=DURATION:{}", REFRESH_INTERVAL),
+        format!("X-PUBLISHED-TTL:{}", REFRESH_INTERVAL),
+    ];
+
+    for todo in todos {
+        let (Some(id), Some(due)) = (todo.id(), todo.due()) else {
+            continue;
+        };
+        let uid = format!("todo-{}@todo-app", id);
+        push_vtodo(&mut lines, todo, &uid, now);
+
+        // 終わったTodoの期限は予定として表示しない
+        if options.events && !todo.is_completed() {
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:todo-{}-due@todo-app", id));
+            lines.push(format!("DTSTAMP:{}", date_time(now)));
+            lines.push(format!("SUMMARY:{}", escape_text(todo.title())));
+            lines.push(format!("DTSTART{}", due_value(due)));
+            // 終日の予定は翌日で終わる（時刻付きの期限は DTEND を省略して長さ0の予定にする）
+            if let Due::Date(date) = due {
+                if let Some(next) = date.checked_add_days(Days::new(1)) {
+                    lines.push(format!("DTEND;VALUE=DATE:{}", date_value(next)));
+                }
+            }
+            lines.push("TRANSP:TRANSPARENT".to_string());
+            lines.push(format!("RELATED-TO:{}", uid));
+            lines.push("END:VEVENT".to_string());
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+/// CalDAVのリソースとして1件のTodoを書き出す（期限がなくても書き出す）
+/// 内容が変わらなければ同じ文字列になるよう、DTSTAMP には完了日時か作成日時を使う
+pub fn render_resource(todo: &Todo, uid: &str) -> String {
+    let stamp = todo
+        .completed_at()
+        .or(todo.created_at())
+        .unwrap_or(DateTime::UNIX_EPOCH);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//todo-app//Todo CalDAV//EN".to_string(),
+    ];
+    push_vtodo(&mut lines, todo, uid, stamp);
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+fn push_vtodo(lines: &mut Vec<String>, todo: &Todo, uid: &str, stamp: DateTime<Utc>) {
+    lines.push("BEGIN:VTODO".to_string());
+    lines.push(format!("UID:{}", uid));
+    lines.push(format!("DTSTAMP:{}", date_time(stamp)));
+    if let Some(created_at) = todo.created_at() {
+        lines.push(format!("CREATED:{}", date_time(created_at)));
+    }
+    lines.push(format!("SUMMARY:{}", escape_text(todo.title())));
+    if let Some(due) = todo.due() {
+        lines.push(format!("DUE{}", due_value(due)));
+    }
+    if todo.is_completed() {
+        lines.push("STATUS:COMPLETED".to_string());
+        lines.push("PERCENT-COMPLETE:100".to_string());
+        if let Some(completed_at) = todo.completed_at() {
+            lines.push(format!("COMPLETED:{}", date_time(completed_at)));
+        }
+    } else {
+        lines.push("STATUS:NEEDS-ACTION".to_string());
+    }
+    lines.push("END:VTODO".to_string());
+}
+
+/// 期限のパラメーターと値（`;VALUE=DATE:20261019` か `:20261019T000000Z`）
+fn due_value(due: Due) -> String {
+    match due {
+        Due::Date(date) => format!(";VALUE=DATE:{}", date_value(date)),
+        Due::DateTime(at) => format!(":{}", date_time(at)),
+    }
+}
+
+fn date_value(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+/// UTCの日時（`20261019T000000Z`）
+fn date_time(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// TEXT型の値のエスケープ
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 75オクテットごとに折り返し（続きの行は空白で始める）、CRLFで終える
+/// 文字の途中では折り返さない
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_renders_due_todos_with_status_and_time_zones() {
+        let at = |h| Utc.with_ymd_and_hms(2026, 10, 19, h, 0, 0).unwrap();
+        let mut open = Todo::reconstruct(
+            1,
+            "牛乳を買う; 2本, 低脂肪".to_string(),
+            false,
+            1.0,
+            Some(at(1)),
+            Due::parse("2026-10-20"),
+            None,
+        );
+        let done = Todo::reconstruct(
+            2,
+            "報告書".to_string(),
+            true,
+            2.0,
+            None,
+            // 日本時間の9時はUTCの0時
+            Due::parse("2026-10-19T09:00:00+09:00"),
+            Some(at(3)),
+        );
+        let undated = Todo::reconstruct(3, "いつか".to_string(), false, 3.0, None, None, None);
+
+        let ics = render(
+            &[open.clone(), done.clone(), undated],
+            at(12),
+            FeedOptions::default(),
+        );
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VTODO").count(), 2);
+        assert!(!ics.contains("BEGIN:VEVENT"));
+        assert!(!ics.contains("いつか"));
+        for line in [
+            "UID:todo-1@todo-app",
+            "DTSTAMP:20261019T120000Z",
+            "CREATED:20261019T010000Z",
+            "SUMMARY:牛乳を買う\\; 2本\\, 低脂肪",
+            "DUE;VALUE=DATE:20261020",
+            "STATUS:NEEDS-ACTION",
+            "DUE:20261019T000000Z",
+            "STATUS:COMPLETED",
+            "COMPLETED:20261019T030000Z",
+        ] {
+            assert!(ics.contains(&format!("{}\r\n", line)), "{}", line);
+        }
+
+        // 未完了のTodoだけが終日の予定になる
+        let ics = render(&[open.clone(), done], at(12), FeedOptions { events: true });
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261020\r\nDTEND;VALUE=DATE:20261021\r\n"));
+
+        // 長い行は文字の途中で切らずに折り返す
+        open.change_title("あ".repeat(40)).unwrap();
+        let ics = render(&[open], at(12), FeedOptions::default());
+        let summary = ics
+            .split("\r\n")
+            .skip_while(|line| !line.starts_with("SUMMARY:"))
+            .take_while(|line| !line.starts_with("DUE"))
+            .collect::<Vec<_>>();
+        assert!(summary.len() > 1);
+        assert!(summary.iter().all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert_eq!(
+            summary.concat().replace(' ', ""),
+            format!("SUMMARY:{}", "あ".repeat(40))
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! カレンダーフィード（iCalendar）とCalDAV
+//!
+//! 期限のあるTodoを秘密のURLで配信し、カレンダーアプリから購読できるようにする。
+//! CalDAVクライアントからは、すべてのTodoを VTODO として読み書きできる。
+
+pub mod caldav;
+pub mod ical;
+pub mod service;
+pub mod vtodo;
+
+pub use caldav::{CalDavObject, CalDavService, Preconditions};
+pub use ical::FeedOptions;
+pub use service::CalendarService;
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use super::ical::{self, FeedOptions};
+use crate::domain::calendar::{CalendarError, CalendarFeedRepository, FeedToken, TokenKind};
+use crate::domain::todo::TodoRepository;
+
+/// カレンダーフィードのサービス
+/// フィードのURLはトークンを知っていれば誰でも読めるため、トークンの発行・再発行もここで扱う。
+/// CalDAVのパスワードもここで発行するが、フィードのトークンとは別のもので、別に再発行する
+pub struct CalendarService {
+    feeds: Arc<dyn CalendarFeedRepository>,
+    todos: Arc<dyn TodoRepository>,
+}
+
+impl CalendarService {
+    pub fn new(feeds: Arc<dyn CalendarFeedRepository>, todos: Arc<dyn TodoRepository>) -> Self {
+        Self { feeds, todos }
+    }
+
+    /// `kind` のトークン（初めて使うときに発行する）
+    pub async fn token(&self, kind: TokenKind) -> Result<FeedToken, CalendarError> {
+        Ok(self
+            .feeds
+            .find_or_insert_token(kind, &FeedToken::generate())
+            .await?)
+    }
+
+    /// `kind` のトークンを再発行する（古いURL・パスワードは使えなくなる）
+    pub async fn rotate_token(&self, kind: TokenKind) -> Result<FeedToken, CalendarError> {
+        let token = FeedToken::generate();
+        self.feeds.replace_token(kind, &token).await?;
+        Ok(token)
+    }
+
+    /// `token` のフィード（iCalendar）
+    pub async fn feed(&self, token: &str, options: FeedOptions) -> Result<String, CalendarError> {
+        let valid = self
+            .feeds
+            .find_token(TokenKind::Feed)
+            .await?
+            .is_some_and(|stored| stored.matches(token));
+        if !valid {
+            return Err(CalendarError::FeedNotFound);
+        }
+
+        let todos = self.todos.find_all().await?;
+        Ok(ical::render(&todos, Utc::now(), options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::todo::{Due, Todo};
+    use crate::infrastructure::persistence::memory::{MemoryFeedRepository, MemoryTodoRepository};
+
+    #[actix_web::test]
+    async fn test_feed_requires_current_token() {
+        let service = CalendarService::new(
+            Arc::new(MemoryFeedRepository::default()),
+            Arc::new(MemoryTodoRepository::with_todos([Todo::reconstruct(
+                1,
+                "牛乳を買う".to_string(),
+                false,
+                1.0,
+                None,
+                Due::parse("2026-10-20"),
+                None,
+            )])),
+        );
+
+        // 発行前はどのトークンでも読めない
+        assert_eq!(
+            service.feed("", FeedOptions::default()).await,
+            Err(CalendarError::FeedNotFound)
+        );
+
+        // 発行したトークンは次回も同じ
+        let token = service.token(TokenKind::Feed).await.unwrap();
+        assert_eq!(service.token(TokenKind::Feed).await.unwrap(), token);
+        let ics = service
+            .feed(token.as_str(), FeedOptions::default())
+            .await
+            .unwrap();
+        assert!(ics.contains("SUMMARY:牛乳を買う\r\n"));
+
+        // CalDAVのパスワードはフィードのトークンとは別で、フィードは読めない
+        let password = service.token(TokenKind::CalDav).await.unwrap();
+        assert_ne!(password, token);
+        assert_eq!(
+            service
+                .feed(password.as_str(), FeedOptions::default())
+                .await,
+            Err(CalendarError::FeedNotFound)
+        );
+
+        // 再発行すると古いトークンでは読めない（CalDAVのパスワードは変わらない）
+        let rotated = service.rotate_token(TokenKind::Feed).await.unwrap();
+        assert_ne!(rotated, token);
+        assert_eq!(
+            service.feed(token.as_str(), FeedOptions::default()).await,
+            Err(CalendarError::FeedNotFound)
+        );
+        assert!(service
+            .feed(rotated.as_str(), FeedOptions::default())
+            .await
+            .is_ok());
+        assert_eq!(service.token(TokenKind::CalDav).await.unwrap(), password);
+    }
+}
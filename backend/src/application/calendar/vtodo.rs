@@ -0,0 +1,273 @@
+//! CalDAVクライアントから受け取った VTODO の読み取り
+//!
+//! Todoにある項目（SUMMARY・STATUS・DUE）だけを読み、それ以外のプロパティやアラーム（VALARM）は捨てる。
+//! `TZID` 付きの期限はIANAのタイムゾーン名として解釈してUTCに直す。
+//! 解釈できない `TZID` とタイムゾーンのない日時（floating）はUTCとして扱う。
+
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::domain::calendar::CalDavError;
+use crate::domain::todo::Due;
+
+/// 受け取ったTodo
+#[derive(Debug, Clone, PartialEq)]
+pub struct VTodo {
+    pub uid: String,
+    pub summary: String,
+    pub completed: bool,
+    pub due: Option<Due>,
+}
+
+/// 1つのプロパティ（`NAME;PARAM=VALUE:value`）
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, String)>,
+    value: &'a str,
+}
+
+impl Property<'_> {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// iCalendarのオブジェクトから VTODO を1つ読む
+/// 繰り返しの例外（同じUIDの2つ目以降の VTODO）は読み飛ばす
+pub fn parse(body: &str) -> Result<VTodo, CalDavError> {
+    let invalid = |reason: &str| CalDavError::InvalidCalendarData(reason.to_string());
+    let lines = unfold(body);
+
+    let mut stack: Vec<String> = Vec::new();
+    let mut todo: Option<Vec<Property>> = None;
+    let mut current: Option<Vec<Property>> = None;
+    for line in &lines {
+        if line.is_empty() {
+            continue;
+        }
+        let property = split_property(line).ok_or_else(|| invalid("プロパティを読めません"))?;
+        match property.name.as_str() {
+            "BEGIN" => {
+                let name = property.value.to_ascii_uppercase();
+                match (stack.len(), name.as_str()) {
+                    (0, "VCALENDAR") => {}
+                    (0, _) => return Err(invalid("VCALENDAR で始まっていません")),
+                    (1, "VTODO") if todo.is_none() => current = Some(Vec::new()),
+                    (1, "VTODO" | "VTIMEZONE") => {}
+                    (1, _) => return Err(CalDavError::UnsupportedComponent),
+                    _ => {}
+                }
+                stack.push(name);
+            }
+            "END" => {
+                let name = property.value.to_ascii_uppercase();
+                if stack.pop().as_deref() != Some(name.as_str()) {
+                    return Err(invalid("BEGIN と END が対応していません"));
+                }
+                if stack.len() == 1 && name == "VTODO" {
+                    if let Some(properties) = current.take() {
+                        todo = Some(properties);
+                    }
+                }
+            }
+            // VTODO の直下のプロパティだけを読む（VALARM の中身は読まない）
+            _ if stack.len() == 2 => {
+                if let Some(properties) = current.as_mut() {
+                    properties.push(property);
+                }
+            }
+            _ => {}
+        }
+    }
+    if !stack.is_empty() {
+        return Err(invalid("END:VCALENDAR がありません"));
+    }
+    let properties = todo.ok_or(CalDavError::UnsupportedComponent)?;
+    let find = |name: &str| properties.iter().find(|property| property.name == name);
+
+    let uid = find("UID")
+        .map(|property| property.value.trim().to_string())
+        .filter(|uid| !uid.is_empty())
+        .ok_or_else(|| invalid("UID がありません"))?;
+    let summary = find("SUMMARY")
+        .map(|property| unescape_text(property.value))
+        .unwrap_or_default();
+    let completed = find("STATUS")
+        .is_some_and(|property| property.value.trim().eq_ignore_ascii_case("COMPLETED"))
+        || find("COMPLETED").is_some();
+    let due = find("DUE")
+        .map(|property| parse_due(property).ok_or_else(|| invalid("DUE を読めません")))
+        .transpose()?;
+
+    Ok(VTodo {
+        uid,
+        summary,
+        completed,
+        due,
+    })
+}
+
+/// 折り返された行をつなげる（続きの行は空白かタブで始まる）
+fn unfold(body: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in body.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// `NAME;PARAM=VALUE:value` を分ける（引用符の中の `:` と `;` は区切りにしない）
+fn split_property(line: &str) -> Option<Property<'_>> {
+    let mut quoted = false;
+    let mut separators = Vec::new();
+    let mut colon = None;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => separators.push(i),
+            ':' if !quoted => {
+                colon = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let colon = colon?;
+    let head = &line[..colon];
+    let name_end = separators.first().copied().unwrap_or(colon);
+    let name = head[..name_end].trim().to_ascii_uppercase();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut params = Vec::new();
+    let mut bounds = separators;
+    bounds.push(colon);
+    for pair in bounds.windows(2) {
+        let param = &line[pair[0] + 1..pair[1]];
+        let (key, value) = param.split_once('=')?;
+        params.push((
+            key.trim().to_string(),
+            value.trim().trim_matches('"').to_string(),
+        ));
+    }
+
+    Some(Property {
+        name,
+        params,
+        value: &line[colon + 1..],
+    })
+}
+
+/// TEXT型の値のエスケープを戻す
+fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => text.push('\n'),
+            Some(c) => text.push(c),
+            None => {}
+        }
+    }
+    text
+}
+
+/// DUE の値（`20261019`、`20261019T090000Z`、`TZID=Asia/Tokyo:20261019T090000` など）
+fn parse_due(property: &Property) -> Option<Due> {
+    let value = property.value.trim();
+    let date_only = property
+        .param("VALUE")
+        .is_some_and(|kind| kind.eq_ignore_ascii_case("DATE"))
+        || value.len() == 8;
+    if date_only {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(Due::Date);
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let at = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(Due::DateTime(at.and_utc()));
+    }
+    let at = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let zone = property
+        .param("TZID")
+        .and_then(|tzid| tzid.trim_start_matches('/').parse::<Tz>().ok());
+    let at: DateTime<Utc> = match zone {
+        Some(zone) => match zone.from_local_datetime(&at) {
+            LocalResult::Single(at) | LocalResult::Ambiguous(at, _) => at.with_timezone(&Utc),
+            // 夏時間の切り替えで存在しない時刻は、1時間後（切り替え後の同じ壁時計の時刻）にする
+            LocalResult::None => zone
+                .from_local_datetime(&(at + TimeDelta::hours(1)))
+                .earliest()?
+                .with_timezone(&Utc),
+        },
+        None => at.and_utc(),
+    };
+    Some(Due::DateTime(at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reads_todo_fields_and_rejects_other_components() {
+        let body = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VTIMEZONE\r\n\
+            TZID:Asia/Tokyo\r\n\
+            END:VTIMEZONE\r\n\
+            BEGIN:VTODO\r\n\
+            UID:ABC-123\r\n\
+            SUMMARY:牛乳を買う\\, 2本\r\n\
+            \x20（低脂肪）\r\n\
+            DUE;TZID=\"Asia/Tokyo\":20261019T090000\r\n\
+            STATUS:NEEDS-ACTION\r\n\
+            BEGIN:VALARM\r\n\
+            SUMMARY:アラーム\r\n\
+            END:VALARM\r\n\
+            END:VTODO\r\n\
+            END:VCALENDAR\r\n";
+        assert_eq!(
+            parse(body),
+            Ok(VTodo {
+                uid: "ABC-123".to_string(),
+                summary: "牛乳を買う, 2本（低脂肪）".to_string(),
+                completed: false,
+                due: Due::parse("2026-10-19T00:00:00Z"),
+            })
+        );
+
+        // 終日の期限と、STATUS のない完了
+        let body = "BEGIN:VCALENDAR\nBEGIN:VTODO\nUID:x\nSUMMARY:報告書\n\
+            DUE;VALUE=DATE:20261020\nCOMPLETED:20261019T030000Z\nEND:VTODO\nEND:VCALENDAR\n";
+        let todo = parse(body).unwrap();
+        assert!(todo.completed);
+        assert_eq!(todo.due, Due::parse("2026-10-20"));
+
+        let event = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:x\nEND:VEVENT\nEND:VCALENDAR\n";
+        assert_eq!(parse(event), Err(CalDavError::UnsupportedComponent));
+        let no_uid = "BEGIN:VCALENDAR\nBEGIN:VTODO\nSUMMARY:a\nEND:VTODO\nEND:VCALENDAR\n";
+        assert!(matches!(
+            parse(no_uid),
+            Err(CalDavError::InvalidCalendarData(_))
+        ));
+        assert!(matches!(
+            parse("BEGIN:VCALENDAR\nBEGIN:VTODO\nUID:x\n"),
+            Err(CalDavError::InvalidCalendarData(_))
+        ));
+    }
+}
@@ -1,24 +1,14 @@
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
-/// Todo作成リクエストDTO
-#[derive(Debug, Deserialize)]
-pub struct CreateTodoDto {
-    pub title: String,
-    #[serde(default)]
-    pub completed: bool,
-}
-
-/// Todo更新リクエストDTO
-#[derive(Debug, Deserialize)]
-pub struct UpdateTodoDto {
-    pub title: Option<String>,
-    pub completed: Option<bool>,
-}
+// リクエスト・レスポンスの型はフロントエンドと共有する
+pub use api_types::{
+    CreateTodoDto, MoveTodoDto, SyncResponseDto, TodoEventDto, TodoResponseDto, UpdateTodoDto,
+};
 
-/// TodoレスポンスDTO
+/// Todo件数の集計DTO
 #[derive(Debug, Serialize)]
-pub struct TodoResponseDto {
-    pub id: i32,
-    pub title: String,
-    pub completed: bool,
+pub struct TodoStatsDto {
+    pub total: usize,
+    pub open: usize,
+    pub completed: usize,
 }
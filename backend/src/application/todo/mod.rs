@@ -1,5 +1,6 @@
 pub mod dto;
 pub mod service;
+pub mod transfer;
 
-pub use dto::{CreateTodoDto, UpdateTodoDto};
+pub use dto::{CreateTodoDto, MoveTodoDto, UpdateTodoDto};
 pub use service::TodoService;
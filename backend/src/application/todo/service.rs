@@ -1,41 +1,77 @@
-use super::dto::{CreateTodoDto, TodoResponseDto, UpdateTodoDto};
-use crate::domain::todo::{Todo, TodoRepository};
+use super::dto::{
+    CreateTodoDto, MoveTodoDto, SyncResponseDto, TodoResponseDto, TodoStatsDto, UpdateTodoDto,
+};
+use crate::domain::todo::{position, Due, Todo, TodoError, TodoEvent, TodoRepository};
 use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// 変更の通知を溜めておく件数（これを超えて遅れた購読者は通知を取りこぼす）
+const EVENT_CAPACITY: usize = 256;
 
 /// Todoサービス
 /// アプリケーションのユースケースを実装
 pub struct TodoService {
     repository: Arc<dyn TodoRepository>,
+    events: broadcast::Sender<TodoEvent>,
 }
 
 impl TodoService {
     pub fn new(repository: Arc<dyn TodoRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            events: Self::event_channel(),
+        }
+    }
+
+    /// 変更の通知の送り先を作る
+    /// リポジトリのデコレーター（`NotifyingTodoRepository`）と `with_events` の両方に渡す
+    pub fn event_channel() -> broadcast::Sender<TodoEvent> {
+        broadcast::channel(EVENT_CAPACITY).0
+    }
+
+    /// 変更の通知の送り先を指定
+    pub fn with_events(mut self, events: broadcast::Sender<TodoEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Todoの変更の通知を購読する
+    pub fn subscribe(&self) -> broadcast::Receiver<TodoEvent> {
+        self.events.subscribe()
     }
 
     /// すべてのTodoを取得
-    pub async fn get_all_todos(&self) -> Result<Vec<TodoResponseDto>, String> {
+    pub async fn get_all_todos(&self) -> Result<Vec<TodoResponseDto>, TodoError> {
         let todos = self.repository.find_all().await?;
         Ok(todos.into_iter().map(Self::to_response_dto).collect())
     }
 
     /// IDでTodoを取得
-    pub async fn get_todo_by_id(&self, id: i32) -> Result<TodoResponseDto, String> {
+    pub async fn get_todo_by_id(&self, id: i32) -> Result<TodoResponseDto, TodoError> {
         let todo = self
             .repository
             .find_by_id(id)
             .await?
-            .ok_or_else(|| "Todoが見つかりません".to_string())?;
+            .ok_or(TodoError::NotFound)?;
 
         Ok(Self::to_response_dto(todo))
     }
 
+    /// 複数のIDのTodoをまとめて取得（存在しないIDは結果に含めない）
+    pub async fn get_todos_by_ids(&self, ids: &[i32]) -> Result<Vec<TodoResponseDto>, TodoError> {
+        let todos = self.repository.find_by_ids(ids).await?;
+        Ok(todos.into_iter().map(Self::to_response_dto).collect())
+    }
+
     /// Todoを作成
-    pub async fn create_todo(&self, dto: CreateTodoDto) -> Result<TodoResponseDto, String> {
+    pub async fn create_todo(&self, dto: CreateTodoDto) -> Result<TodoResponseDto, TodoError> {
         let mut todo = Todo::new(dto.title)?;
         if dto.completed {
             todo.complete();
         }
+        if let Some(due) = dto.due {
+            todo.set_due(Self::parse_due(&due)?);
+        }
         let saved_todo = self.repository.save(&todo).await?;
         Ok(Self::to_response_dto(saved_todo))
     }
@@ -45,12 +81,12 @@ impl TodoService {
         &self,
         id: i32,
         dto: UpdateTodoDto,
-    ) -> Result<TodoResponseDto, String> {
+    ) -> Result<TodoResponseDto, TodoError> {
         let mut todo = self
             .repository
             .find_by_id(id)
             .await?
-            .ok_or_else(|| "Todoが見つかりません".to_string())?;
+            .ok_or(TodoError::NotFound)?;
 
         // タイトルの更新
         if let Some(title) = dto.title {
@@ -66,22 +102,250 @@ impl TodoService {
             }
         }
 
+        // 期限の更新（空文字列なら期限なし）
+        if let Some(due) = dto.due {
+            todo.set_due(Self::parse_due(&due)?);
+        }
+
         let updated_todo = self.repository.update(&todo).await?;
         Ok(Self::to_response_dto(updated_todo))
     }
 
+    /// Todoを別のTodoの直前（`before`）か直後（`after`）に移動
+    /// 間に位置を取れない場合は、全体の位置を振り直す
+    pub async fn move_todo(&self, id: i32, dto: MoveTodoDto) -> Result<TodoResponseDto, TodoError> {
+        let (anchor_id, after) = match (dto.before, dto.after) {
+            (Some(before), None) => (before, false),
+            (None, Some(after)) => (after, true),
+            _ => return Err(TodoError::InvalidMove),
+        };
+
+        let mut todos = self.repository.find_all().await?;
+        let index = todos
+            .iter()
+            .position(|todo| todo.id() == Some(id))
+            .ok_or(TodoError::NotFound)?;
+        // 自分自身の前後への移動は何もしない
+        if anchor_id == id {
+            return Ok(Self::to_response_dto(todos.swap_remove(index)));
+        }
+        let mut todo = todos.remove(index);
+        let anchor = todos
+            .iter()
+            .position(|todo| todo.id() == Some(anchor_id))
+            .ok_or(TodoError::MoveTargetNotFound)?;
+        let insert_at = if after { anchor + 1 } else { anchor };
+
+        let before = insert_at.checked_sub(1).map(|i| todos[i].position());
+        let next = todos.get(insert_at).map(Todo::position);
+        if let Some(new_position) = position::between(before, next) {
+            todo.move_to(new_position);
+            let moved = self.repository.update(&todo).await?;
+            return Ok(Self::to_response_dto(moved));
+        }
+
+        // 位置が変わるTodoだけをまとめて保存し直す（途中で失敗して並び順が崩れないようにする）
+        todos.insert(insert_at, todo);
+        let positions = position::renumbered(todos.len());
+        let mut moved = Vec::new();
+        for (todo, new_position) in todos.iter_mut().zip(positions) {
+            if todo.position() != new_position {
+                todo.move_to(new_position);
+                moved.push(todo.clone());
+            }
+        }
+        self.repository.update_positions(&moved).await?;
+        Ok(Self::to_response_dto(todos.swap_remove(insert_at)))
+    }
+
     /// Todoを削除
-    pub async fn delete_todo(&self, id: i32) -> Result<(), String> {
-        // 存在確認なしで直接削除（SurrealDBは存在しない場合もエラーを返さない）
+    pub async fn delete_todo(&self, id: i32) -> Result<(), TodoError> {
+        // 存在しない場合はリポジトリが `TodoError::NotFound` を返す
         self.repository.delete(id).await
     }
 
+    /// 状態別のTodo件数を集計
+    pub async fn get_stats(&self) -> Result<TodoStatsDto, TodoError> {
+        let todos = self.repository.find_all().await?;
+        let completed = todos.iter().filter(|todo| todo.is_completed()).count();
+        Ok(TodoStatsDto {
+            total: todos.len(),
+            open: todos.len() - completed,
+            completed,
+        })
+    }
+
+    /// 同期トークン `since` 以降の変更を取得
+    /// トークンがない場合や、DBの作り直しなどでサーバーより新しいトークンが渡された場合は全件を返す
+    pub async fn sync(&self, since: Option<u64>) -> Result<SyncResponseDto, TodoError> {
+        let mut changes = self.repository.find_changes_since(since).await?;
+        let full = match since {
+            Some(since) if since > changes.sequence => {
+                changes = self.repository.find_changes_since(None).await?;
+                true
+            }
+            Some(_) => false,
+            None => true,
+        };
+
+        Ok(SyncResponseDto {
+            todos: changes
+                .changed
+                .into_iter()
+                .map(Self::to_response_dto)
+                .collect(),
+            deleted: changes.deleted,
+            token: changes.sequence.to_string(),
+            full,
+        })
+    }
+
+    /// リクエストの期限を解釈する（空文字列は期限なし）
+    pub(crate) fn parse_due(value: &str) -> Result<Option<Due>, TodoError> {
+        if value.trim().is_empty() {
+            return Ok(None);
+        }
+        Due::parse(value).map(Some).ok_or(TodoError::InvalidDue)
+    }
+
     /// Todoエンティティをレスポンスdtoに変換
     fn to_response_dto(todo: Todo) -> TodoResponseDto {
         TodoResponseDto {
             id: todo.id().expect("保存されたTodoにはIDが必要です"),
             title: todo.title().to_string(),
             completed: todo.is_completed(),
+            position: todo.position(),
+            created_at: todo.created_at().map(|at| at.to_rfc3339()),
+            due: todo.due().map(|due| due.to_string()),
+            completed_at: todo.completed_at().map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::persistence::memory::MemoryTodoRepository;
+
+    fn todo(id: i32, position: f64) -> Todo {
+        Todo::reconstruct(
+            id,
+            format!("todo {}", id),
+            false,
+            position,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[actix_web::test]
+    async fn test_sync_returns_delta_or_full_snapshot() {
+        // ID 1〜3の作成でシーケンスは3になり、ID 2の更新とID 3の削除で5になる
+        let repository = MemoryTodoRepository::with_todos((1..=3).map(|id| todo(id, id as f64)));
+        let service = TodoService::new(Arc::new(repository));
+        service
+            .update_todo(
+                2,
+                UpdateTodoDto {
+                    title: Some("更新".to_string()),
+                    completed: None,
+                    due: None,
+                },
+            )
+            .await
+            .unwrap();
+        service.delete_todo(3).await.unwrap();
+
+        let delta = service.sync(Some(3)).await.unwrap();
+        assert!(!delta.full);
+        assert_eq!(delta.todos.iter().map(|t| t.id).collect::<Vec<_>>(), [2]);
+        assert_eq!(delta.deleted, [3]);
+        assert_eq!(delta.token, "5");
+
+        // トークンがない場合と、サーバーより新しいトークンの場合は全件を返す
+        for since in [None, Some(9)] {
+            let snapshot = service.sync(since).await.unwrap();
+            assert!(snapshot.full);
+            assert_eq!(snapshot.todos.len(), 2);
+            assert!(snapshot.deleted.is_empty());
         }
     }
+
+    #[actix_web::test]
+    async fn test_move_todo_between_neighbours_and_renumber_ties() {
+        // 2と3は同じ位置（並べ替え機能より前からあるTodo）
+        let todos =
+            [(1, 0.0), (2, 1.0), (3, 1.0), (4, 2.0)].map(|(id, position)| todo(id, position));
+        let service = TodoService::new(Arc::new(MemoryTodoRepository::with_todos(todos)));
+        let order = || async {
+            service
+                .get_all_todos()
+                .await
+                .unwrap()
+                .iter()
+                .map(|t| t.id)
+                .collect::<Vec<_>>()
+        };
+
+        let moved = service
+            .move_todo(
+                4,
+                MoveTodoDto {
+                    before: Some(1),
+                    after: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(moved.position, -1.0);
+        assert_eq!(order().await, [4, 1, 2, 3]);
+
+        // 同じ位置のTodoの間には置けないので全体を振り直す
+        service
+            .move_todo(
+                1,
+                MoveTodoDto {
+                    before: None,
+                    after: Some(2),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(order().await, [4, 2, 1, 3]);
+        let positions: Vec<f64> = service
+            .get_all_todos()
+            .await
+            .unwrap()
+            .iter()
+            .map(|t| t.position)
+            .collect();
+        assert_eq!(positions, [1.0, 2.0, 3.0, 4.0]);
+
+        // 自分自身の前後への移動は何もしない
+        let unchanged = service
+            .move_todo(
+                2,
+                MoveTodoDto {
+                    before: None,
+                    after: Some(2),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(unchanged.position, 2.0);
+        assert_eq!(order().await, [4, 2, 1, 3]);
+
+        let err = service
+            .move_todo(
+                4,
+                MoveTodoDto {
+                    before: Some(9),
+                    after: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, TodoError::MoveTargetNotFound);
+    }
 }
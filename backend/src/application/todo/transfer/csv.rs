@@ -0,0 +1,79 @@
+use api_types::TodoResponseDto;
+
+use super::{FormatAdapter, ImportedRow};
+
+/// `title,completed,due,created_at` の列を持つCSV
+/// 読み込みでは列の順番と大文字・小文字を問わず、`title` 以外の列は省略できる
+pub struct Csv;
+
+impl FormatAdapter for Csv {
+    fn write(&self, todos: &[TodoResponseDto]) -> Result<String, String> {
+        let mut writer = ::csv::Writer::from_writer(Vec::new());
+        writer
+            .write_record(["title", "completed", "due", "created_at"])
+            .map_err(|e| e.to_string())?;
+        for todo in todos {
+            writer
+                .write_record([
+                    todo.title.as_str(),
+                    if todo.completed { "true" } else { "false" },
+                    todo.due.as_deref().unwrap_or_default(),
+                    todo.created_at.as_deref().unwrap_or_default(),
+                ])
+                .map_err(|e| e.to_string())?;
+        }
+        let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+
+    fn read(&self, input: &str) -> Result<Vec<ImportedRow>, String> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(::csv::Trim::Headers)
+            .from_reader(input.as_bytes());
+        let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+        let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+        let title = column("title").ok_or("missing \"title\" column")?;
+        let completed = column("completed");
+        let due = column("due");
+
+        let rows = reader
+            .records()
+            .enumerate()
+            .map(|(index, record)| {
+                // ヘッダーが1行目
+                let line = match &record {
+                    Ok(record) => record.position().map(|p| p.line() as usize),
+                    Err(e) => e.position().map(|p| p.line() as usize),
+                }
+                .unwrap_or(index + 2);
+                let todo = record.map_err(|e| e.to_string()).and_then(|record| {
+                    let completed = match completed.and_then(|i| record.get(i)) {
+                        Some(value) => parse_bool(value)?,
+                        None => false,
+                    };
+                    let title = record.get(title).unwrap_or_default();
+                    let due = due
+                        .and_then(|i| record.get(i))
+                        .filter(|due| !due.trim().is_empty());
+                    Ok(api_types::CreateTodoDto {
+                        title: title.to_string(),
+                        completed,
+                        due: due.map(str::to_string),
+                    })
+                });
+                ImportedRow { line, todo }
+            })
+            .collect();
+        Ok(rows)
+    }
+}
+
+/// 完了列の値（他のツールの書き出しに合わせて `1` や `x` も受け付ける）
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "x" | "done" => Ok(true),
+        "false" | "0" | "no" | "" => Ok(false),
+        other => Err(format!("invalid \"completed\" value: {}", other)),
+    }
+}
@@ -0,0 +1,27 @@
+use api_types::{CreateTodoDto, TodoResponseDto};
+
+use super::{FormatAdapter, ImportedRow};
+
+/// Todoの配列（エクスポートはAPIのレスポンスと同じ形式）
+/// 読み込みでは `title` と `completed` 以外の項目を無視する
+pub struct Json;
+
+impl FormatAdapter for Json {
+    fn write(&self, todos: &[TodoResponseDto]) -> Result<String, String> {
+        serde_json::to_string_pretty(todos).map_err(|e| e.to_string())
+    }
+
+    fn read(&self, input: &str) -> Result<Vec<ImportedRow>, String> {
+        let items: Vec<serde_json::Value> =
+            serde_json::from_str(input).map_err(|e| e.to_string())?;
+        let rows = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| ImportedRow {
+                line: index + 1,
+                todo: serde_json::from_value::<CreateTodoDto>(item).map_err(|e| e.to_string()),
+            })
+            .collect();
+        Ok(rows)
+    }
+}
@@ -0,0 +1,36 @@
+use api_types::TodoResponseDto;
+
+use super::{single_line, FormatAdapter, ImportedRow};
+
+/// `- [ ] タイトル` / `- [x] タイトル` のチェックリスト
+/// 読み込みではチェックボックスのない行（見出しや本文）を読み飛ばす
+pub struct Markdown;
+
+impl FormatAdapter for Markdown {
+    fn write(&self, todos: &[TodoResponseDto]) -> Result<String, String> {
+        Ok(todos
+            .iter()
+            .map(|todo| {
+                let mark = if todo.completed { 'x' } else { ' ' };
+                format!("- [{}] {}\n", mark, single_line(&todo.title))
+            })
+            .collect())
+    }
+
+    fn read(&self, input: &str) -> Result<Vec<ImportedRow>, String> {
+        let rows = input
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let item = line.trim().strip_prefix(['-', '*', '+'])?.trim_start();
+                let completed = match item.get(..3)? {
+                    "[ ]" => false,
+                    "[x]" | "[X]" => true,
+                    _ => return None,
+                };
+                Some(ImportedRow::new(index + 1, item[3..].trim(), completed))
+            })
+            .collect();
+        Ok(rows)
+    }
+}
@@ -0,0 +1,315 @@
+//! インポート・エクスポート
+//!
+//! 形式ごとのアダプターがTodoとファイルの変換だけを受け持ち、作成・一覧の取得は `TodoService` を通す。
+
+mod csv;
+mod json;
+mod markdown;
+mod todotxt;
+
+use std::collections::HashSet;
+use std::fmt;
+
+use api_types::{
+    ErrorCode, ImportReportDto, ImportRowDto, ImportRowStatus, TodoResponseDto, TransferFormat,
+};
+
+use super::dto::CreateTodoDto;
+use super::service::TodoService;
+use crate::domain::todo::{Todo, TodoError};
+
+/// ファイルから読み込んだ行
+#[derive(Debug, PartialEq)]
+pub struct ImportedRow {
+    /// 行番号（1始まり）
+    pub line: usize,
+    /// 読み込めなければパーサーのメッセージ（`csv` / `serde_json` に合わせて英語）
+    pub todo: Result<CreateTodoDto, String>,
+}
+
+impl ImportedRow {
+    fn new(line: usize, title: impl Into<String>, completed: bool) -> Self {
+        Self {
+            line,
+            todo: Ok(CreateTodoDto {
+                title: title.into(),
+                completed,
+                due: None,
+            }),
+        }
+    }
+}
+
+/// 形式ごとの読み書き
+trait FormatAdapter {
+    fn write(&self, todos: &[TodoResponseDto]) -> Result<String, String>;
+
+    /// 行ごとに読み込む。ファイル全体を解釈できなければエラー
+    fn read(&self, input: &str) -> Result<Vec<ImportedRow>, String>;
+}
+
+fn adapter(format: TransferFormat) -> &'static dyn FormatAdapter {
+    match format {
+        TransferFormat::Csv => &csv::Csv,
+        TransferFormat::Json => &json::Json,
+        TransferFormat::Markdown => &markdown::Markdown,
+        TransferFormat::TodoTxt => &todotxt::TodoTxt,
+    }
+}
+
+/// 1行に収める形式（Markdown・todo.txt）でタイトルの改行を空白にする
+fn single_line(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 重複の判定に使うタイトル（前後の空白と大文字・小文字を無視する）
+fn duplicate_key(title: &str) -> String {
+    single_line(title).to_lowercase()
+}
+
+/// インポート・エクスポートのエラー
+#[derive(Debug, PartialEq)]
+pub enum TransferError {
+    /// ファイル全体を読み込めない
+    InvalidFile(String),
+    Todo(TodoError),
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferError::InvalidFile(e) => write!(f, "ファイルを読み込めません: {}", e),
+            TransferError::Todo(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<TodoError> for TransferError {
+    fn from(e: TodoError) -> Self {
+        TransferError::Todo(e)
+    }
+}
+
+/// すべてのTodoを `format` で書き出す
+pub async fn export(
+    service: &TodoService,
+    format: TransferFormat,
+) -> Result<String, TransferError> {
+    let todos = service.get_all_todos().await?;
+    adapter(format)
+        .write(&todos)
+        .map_err(|e| TransferError::Todo(TodoError::Repository(e)))
+}
+
+/// `format` のファイルからTodoを作成する
+/// 既存のTodoやファイル内の前の行と同じタイトルは読み飛ばし、`dry_run` なら作成せずに結果だけを返す。
+/// 作成に失敗した行は `failed` として残りの行の処理を続け、それまでの結果を返す
+pub async fn import(
+    service: &TodoService,
+    format: TransferFormat,
+    input: &str,
+    dry_run: bool,
+) -> Result<ImportReportDto, TransferError> {
+    let rows = adapter(format)
+        .read(input.trim_start_matches('\u{feff}'))
+        .map_err(TransferError::InvalidFile)?;
+    let mut seen: HashSet<String> = service
+        .get_all_todos()
+        .await?
+        .iter()
+        .map(|todo| duplicate_key(&todo.title))
+        .collect();
+
+    let mut report = ImportReportDto {
+        dry_run,
+        imported: 0,
+        duplicates: 0,
+        invalid: 0,
+        failed: 0,
+        rows: Vec::with_capacity(rows.len()),
+    };
+    for row in rows {
+        let dto = match row.todo {
+            Ok(dto) => dto,
+            Err(detail) => {
+                report.invalid += 1;
+                report.rows.push(ImportRowDto {
+                    line: row.line,
+                    title: String::new(),
+                    completed: false,
+                    status: ImportRowStatus::Invalid,
+                    error: Some(ErrorCode::InvalidImportRow),
+                    detail: Some(detail),
+                });
+                continue;
+            }
+        };
+
+        let mut error = if Todo::new(dto.title.clone()).is_err() {
+            Some(ErrorCode::TitleEmpty)
+        } else if dto
+            .due
+            .as_deref()
+            .is_some_and(|due| TodoService::parse_due(due).is_err())
+        {
+            Some(ErrorCode::InvalidDue)
+        } else {
+            None
+        };
+        let mut status = if error.is_some() {
+            ImportRowStatus::Invalid
+        } else if !seen.insert(duplicate_key(&dto.title)) {
+            ImportRowStatus::Duplicate
+        } else {
+            ImportRowStatus::Imported
+        };
+        if status == ImportRowStatus::Imported && !dry_run {
+            if let Err(e) = service.create_todo(dto.clone()).await {
+                tracing::error!(line = row.line, error = %e, "インポートした行のTodoを作成できませんでした");
+                // 後の行に同じタイトルがあれば、そちらで作成する
+                seen.remove(&duplicate_key(&dto.title));
+                status = ImportRowStatus::Failed;
+                error = Some(failure_code(e));
+            }
+        }
+        match status {
+            ImportRowStatus::Imported => report.imported += 1,
+            ImportRowStatus::Duplicate => report.duplicates += 1,
+            ImportRowStatus::Invalid => report.invalid += 1,
+            ImportRowStatus::Failed => report.failed += 1,
+        }
+        report.rows.push(ImportRowDto {
+            line: row.line,
+            title: dto.title.trim().to_string(),
+            completed: dto.completed,
+            status,
+            error,
+            detail: None,
+        });
+    }
+    Ok(report)
+}
+
+/// 作成に失敗した行のエラーコード（永続化の失敗は内部エラーとし、詳細はログにだけ出す）
+fn failure_code(err: TodoError) -> ErrorCode {
+    match err {
+        TodoError::EmptyTitle => ErrorCode::TitleEmpty,
+        TodoError::InvalidDue => ErrorCode::InvalidDue,
+        _ => ErrorCode::InternalError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::todo::TodoRepository;
+    use crate::infrastructure::persistence::memory::MemoryTodoRepository;
+    use std::sync::Arc;
+
+    #[actix_web::test]
+    async fn test_round_trips_formats_and_reports_duplicates_and_invalid_rows() {
+        let repository =
+            Arc::new(MemoryTodoRepository::default().failing_saves_titled("保存できない"));
+        let service = TodoService::new(repository.clone());
+        service
+            .create_todo(CreateTodoDto {
+                title: "牛乳を買う".to_string(),
+                completed: false,
+                due: Some("2025-11-30".to_string()),
+            })
+            .await
+            .unwrap();
+        service
+            .create_todo(CreateTodoDto {
+                title: "Write, \"quoted\"\nreport".to_string(),
+                completed: true,
+                due: Some("2025-11-30T09:00:00+09:00".to_string()),
+            })
+            .await
+            .unwrap();
+
+        // 書き出したファイルを読み込むと同じTodoになる（1行の形式では改行は空白になる）
+        let todos = service.get_all_todos().await.unwrap();
+        for format in TransferFormat::ALL {
+            let exported = export(&service, format).await.unwrap();
+            let rows = adapter(format).read(&exported).unwrap();
+            let titles: Vec<_> = rows
+                .into_iter()
+                .map(|row| {
+                    let dto = row.todo.unwrap();
+                    let due = dto.due.map(|due| TodoService::parse_due(&due).unwrap());
+                    (single_line(&dto.title), dto.completed, due)
+                })
+                .collect();
+            // Markdownには期限を書かない
+            let expected: Vec<_> = todos
+                .iter()
+                .map(|todo| {
+                    let due = todo
+                        .due
+                        .as_ref()
+                        .filter(|_| format != TransferFormat::Markdown)
+                        .map(|due| TodoService::parse_due(due).unwrap());
+                    (single_line(&todo.title), todo.completed, due)
+                })
+                .collect();
+            assert_eq!(titles, expected, "{:?}", format);
+        }
+
+        let input = "- [ ] 牛乳を買う\n# 見出し\n- [x] 掃除\n- [ ]   \n- [ ] 掃除\n";
+        let report = import(&service, TransferFormat::Markdown, input, true)
+            .await
+            .unwrap();
+        let statuses: Vec<_> = report
+            .rows
+            .iter()
+            .map(|row| (row.line, row.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, ImportRowStatus::Duplicate),
+                (3, ImportRowStatus::Imported),
+                (4, ImportRowStatus::Invalid),
+                (5, ImportRowStatus::Duplicate),
+            ]
+        );
+        assert_eq!(
+            (report.imported, report.duplicates, report.invalid),
+            (1, 2, 1)
+        );
+        // ドライランでは作成しない
+        assert_eq!(repository.find_all().await.unwrap().len(), 2);
+
+        let report = import(&service, TransferFormat::Markdown, input, false)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 1);
+        let created = repository.find_all().await.unwrap()[2].clone();
+        assert_eq!((created.title(), created.is_completed()), ("掃除", true));
+
+        // 作成に失敗した行は `failed` にし、残りの行の作成を続けて結果を返す
+        let input = "- [ ] 保存できない\n- [ ] 洗濯\n";
+        let report = import(&service, TransferFormat::Markdown, input, false)
+            .await
+            .unwrap();
+        assert_eq!(report.rows[0].status, ImportRowStatus::Failed);
+        assert_eq!(report.rows[0].error, Some(ErrorCode::InternalError));
+        assert_eq!(report.rows[1].status, ImportRowStatus::Imported);
+        assert_eq!((report.imported, report.failed), (1, 1));
+        assert_eq!(repository.find_all().await.unwrap().len(), 4);
+
+        let csv = "Completed,Title,Due\ntrue,ok,2025-12-01\nmaybe,bad,\nfalse,late,tomorrow\n";
+        let report = import(&service, TransferFormat::Csv, csv, true)
+            .await
+            .unwrap();
+        assert_eq!(report.rows[0].status, ImportRowStatus::Imported);
+        assert_eq!(report.rows[1].error, Some(ErrorCode::InvalidImportRow));
+        assert_eq!(report.rows[2].error, Some(ErrorCode::InvalidDue));
+
+        assert!(matches!(
+            import(&service, TransferFormat::Json, "{}", true).await,
+            Err(TransferError::InvalidFile(_))
+        ));
+    }
+}
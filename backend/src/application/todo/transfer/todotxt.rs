@@ -0,0 +1,108 @@
+use api_types::TodoResponseDto;
+use chrono::{DateTime, NaiveDate};
+
+use super::{single_line, CreateTodoDto, FormatAdapter, ImportedRow};
+
+/// todo.txt 形式（<https://github.com/todotxt/todo.txt>）
+/// 完了は先頭の `x `（完了日がわかれば完了日と作成日も）、未完了のTodoには作成日を、期限は `due:` タグで付ける。
+/// 読み込みでは優先度と日付を取り除き、`+project` や `@context` はタイトルに残す
+pub struct TodoTxt;
+
+impl FormatAdapter for TodoTxt {
+    fn write(&self, todos: &[TodoResponseDto]) -> Result<String, String> {
+        Ok(todos
+            .iter()
+            .map(|todo| {
+                let mut title = single_line(&todo.title);
+                if let Some(due) = &todo.due {
+                    title = format!("{} due:{}", title, due);
+                }
+                let date = |at: &Option<String>| {
+                    at.as_deref()
+                        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                        .map(|at| at.format("%Y-%m-%d").to_string())
+                };
+                // 完了日は作成日と組でしか書けない
+                match (
+                    todo.completed,
+                    date(&todo.completed_at),
+                    date(&todo.created_at),
+                ) {
+                    (true, Some(completed), Some(created)) => {
+                        format!("x {} {} {}\n", completed, created, title)
+                    }
+                    (true, _, _) => format!("x {}\n", title),
+                    (false, _, Some(created)) => format!("{} {}\n", created, title),
+                    (false, _, None) => format!("{}\n", title),
+                }
+            })
+            .collect())
+    }
+
+    fn read(&self, input: &str) -> Result<Vec<ImportedRow>, String> {
+        let rows = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let line = line.trim();
+                let (completed, rest) = match line.strip_prefix("x ") {
+                    Some(rest) => (true, rest),
+                    None => (false, strip_priority(line)),
+                };
+                // 完了日と作成日
+                let rest = strip_date(strip_date(rest));
+                let (title, due) = take_due(rest);
+                ImportedRow {
+                    line: index + 1,
+                    todo: Ok(CreateTodoDto {
+                        title,
+                        completed,
+                        due,
+                    }),
+                }
+            })
+            .collect();
+        Ok(rows)
+    }
+}
+
+/// 先頭の `(A) ` を取り除く
+fn strip_priority(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    if bytes.len() >= 4
+        && bytes[0] == b'('
+        && bytes[1].is_ascii_uppercase()
+        && bytes[2] == b')'
+        && bytes[3] == b' '
+    {
+        &line[4..]
+    } else {
+        line
+    }
+}
+
+/// 先頭の `YYYY-MM-DD ` を取り除く
+fn strip_date(line: &str) -> &str {
+    match line.split_once(' ') {
+        Some((date, rest)) if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok() => rest,
+        _ => line,
+    }
+}
+
+/// `due:` タグを取り除いたタイトルと、タグの値
+fn take_due(line: &str) -> (String, Option<String>) {
+    let mut due = None;
+    let title = line
+        .split_whitespace()
+        .filter(|word| match word.strip_prefix("due:") {
+            Some(value) if !value.is_empty() => {
+                due = Some(value.to_string());
+                false
+            }
+            _ => true,
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    (title, due)
+}
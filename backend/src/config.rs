@@ -0,0 +1,798 @@
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 設定ファイルを明示しなかった場合に探すパス
+const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+/// 秘密情報を表示するときの置き換え文字列
+const REDACTED: &str = "********";
+
+/// コマンドライン引数
+/// 各項目は環境変数からも指定でき、CLI > 環境変数 > 設定ファイル > デフォルト の順に優先される
+#[derive(Debug, Default, Parser)]
+#[command(author, version, about)]
+pub struct Cli {
+    /// 設定ファイル(TOML)のパス
+    #[arg(short, long, env = "APP_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// 最終的な設定を（秘密情報を伏せて）表示して終了する
+    #[arg(long)]
+    pub print_config: bool,
+
+    /// バインドするホスト
+    #[arg(long, env = "BIND_HOST")]
+    pub host: Option<String>,
+
+    /// 待ち受けポート
+    #[arg(long, env = "PORT")]
+    pub port: Option<u16>,
+
+    /// 外部から見たサーバーのURL（カレンダーフィードの購読URLに使う）
+    #[arg(long, env = "PUBLIC_URL")]
+    pub public_url: Option<String>,
+
+    /// ワーカースレッド数
+    #[arg(long, env = "WORKERS")]
+    pub workers: Option<usize>,
+
+    /// SurrealDBのURL
+    #[arg(long, env = "DATABASE_URL")]
+    pub database_url: Option<String>,
+
+    /// SurrealDBのユーザー名
+    #[arg(long, env = "DATABASE_USER")]
+    pub database_user: Option<String>,
+
+    /// SurrealDBのパスワード
+    #[arg(long, env = "DATABASE_PASS", hide_env_values = true)]
+    pub database_pass: Option<String>,
+
+    /// SurrealDBのNamespace
+    #[arg(long, env = "DATABASE_NS")]
+    pub database_ns: Option<String>,
+
+    /// SurrealDBのDatabase
+    #[arg(long, env = "DATABASE_NAME")]
+    pub database_name: Option<String>,
+
+    /// CORSで許可するオリジン（カンマ区切り、`*` ですべて許可）
+    #[arg(
+        long = "cors-origin",
+        env = "CORS_ALLOWED_ORIGINS",
+        value_delimiter = ','
+    )]
+    pub cors_origins: Vec<String>,
+
+    /// ログレベル (trace, debug, info, warn, error)。RUST_LOG が設定されている場合はそちらが優先
+    #[arg(long, env = "LOG_LEVEL")]
+    pub log_level: Option<String>,
+
+    /// ログの出力形式 (pretty, json)
+    #[arg(long, env = "LOG_FORMAT")]
+    pub log_format: Option<LogFormat>,
+
+    /// JSONリクエストボディの最大バイト数
+    #[arg(long, env = "JSON_PAYLOAD_LIMIT")]
+    pub json_payload_limit: Option<usize>,
+
+    /// インポートするファイルの最大バイト数
+    #[arg(long, env = "IMPORT_FILE_LIMIT")]
+    pub import_file_limit: Option<usize>,
+
+    /// レート制限を有効にするか (true, false)
+    #[arg(long, env = "RATE_LIMIT_ENABLED")]
+    pub rate_limit_enabled: Option<bool>,
+
+    /// クライアントIPの判定に X-Forwarded-For を信頼するか（リバースプロキシ配下の場合のみ true）
+    #[arg(long, env = "RATE_LIMIT_TRUST_FORWARDED_FOR")]
+    pub rate_limit_trust_forwarded_for: Option<bool>,
+
+    /// GraphiQL（`GET /api/graphql`）を提供するか
+    #[arg(long, env = "GRAPHQL_PLAYGROUND")]
+    pub graphql_playground: Option<bool>,
+}
+
+/// アプリケーション全体の設定
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub cors: CorsConfig,
+    pub security: SecurityConfig,
+    pub log: LogConfig,
+    pub limits: LimitsConfig,
+    pub rate_limit: RateLimitConfig,
+    pub api: ApiConfig,
+    pub graphql: GraphqlConfig,
+}
+
+/// HTTPサーバー設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// 未指定の場合はCPUコア数
+    pub workers: Option<usize>,
+    /// SIGTERM受信後、処理中のリクエストの完了を待つ秒数
+    pub shutdown_timeout_secs: u64,
+    /// 外部から見たサーバーのURL（例: `https://todo.example.com`）
+    /// 購読URLはリクエストの Host ヘッダーではなくこの値から組み立てる
+    pub public_url: String,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 8080,
+            workers: None,
+            shutdown_timeout_secs: 30,
+            public_url: "http://localhost:8080".to_string(),
+        }
+    }
+}
+
+/// データベース接続設定
+/// 認証情報にはデフォルト値を持たせず、未設定の場合は起動時にエラーにする
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// http(s):// または ws(s):// で始まるURL（スキーム省略時は http）
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    pub namespace: String,
+    pub database: String,
+    /// 接続・認証・スキーマ初期化の制限時間
+    pub connect_timeout_secs: u64,
+    /// クエリ1回あたりの制限時間
+    pub query_timeout_secs: u64,
+    /// 再接続の待ち時間の上限（指数バックオフ）
+    pub reconnect_max_backoff_secs: u64,
+    /// 接続中の死活監視の間隔
+    pub health_check_interval_secs: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://127.0.0.1:8000".to_string(),
+            username: String::new(),
+            password: String::new(),
+            namespace: "app".to_string(),
+            database: "todos".to_string(),
+            connect_timeout_secs: 10,
+            query_timeout_secs: 5,
+            reconnect_max_backoff_secs: 30,
+            health_check_interval_secs: 5,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    /// 接続先URL（スキームが省略されている場合は http:// を補う）
+    pub fn endpoint(&self) -> String {
+        if self.url.contains("://") {
+            self.url.clone()
+        } else {
+            format!("http://{}", self.url)
+        }
+    }
+}
+
+/// CORS設定
+/// デフォルトではクロスオリジンのリクエストを許可しない（フロントエンドはnginx/trunk経由の同一オリジン）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    /// 許可するオリジン。`*` を含む場合はすべて許可
+    pub allowed_origins: Vec<String>,
+    /// 許可するHTTPメソッド
+    pub allowed_methods: Vec<String>,
+    /// 許可するリクエストヘッダー
+    pub allowed_headers: Vec<String>,
+    /// ブラウザのスクリプトから参照できるレスポンスヘッダー
+    pub expose_headers: Vec<String>,
+    /// Cookieなどの資格情報付きリクエストを許可するか（`*` とは併用できない）
+    pub allow_credentials: bool,
+    pub max_age_secs: usize,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: strings(&["GET", "POST", "PUT", "DELETE"]),
+            allowed_headers: strings(&[
+                "content-type",
+                "authorization",
+                "x-request-id",
+                "x-csrf-token",
+            ]),
+            expose_headers: strings(&[
+                "x-request-id",
+                "ratelimit-limit",
+                "ratelimit-remaining",
+                "ratelimit-reset",
+                "retry-after",
+                "api-version",
+                "deprecation",
+                "sunset",
+                "link",
+            ]),
+            allow_credentials: false,
+            max_age_secs: 3600,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// すべてのオリジンを許可するかどうか
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|o| o == "*")
+    }
+}
+
+/// セキュリティヘッダーとCSRF対策の設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecurityConfig {
+    /// APIレスポンスに付与する Content-Security-Policy（空文字で付与しない）
+    pub content_security_policy: String,
+    /// Strict-Transport-Security の max-age（0で付与しない）
+    pub hsts_max_age_secs: u64,
+    pub referrer_policy: String,
+    pub csrf: CsrfConfig,
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            content_security_policy: "default-src 'none'; frame-ancestors 'none'".to_string(),
+            hsts_max_age_secs: 31_536_000,
+            referrer_policy: "no-referrer".to_string(),
+            csrf: CsrfConfig::default(),
+        }
+    }
+}
+
+/// CSRF対策（ダブルサブミットCookie）の設定
+/// セッションCookie付きの書き込みリクエストにのみ適用し、Bearerトークンでの利用には影響しない
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CsrfConfig {
+    pub enabled: bool,
+    /// このCookieがあるリクエストをCookieベースのセッションとみなす
+    pub session_cookie: String,
+    /// CSRFトークンを発行するCookie
+    pub cookie_name: String,
+    /// CSRFトークンを送り返すヘッダー
+    pub header_name: String,
+}
+
+impl Default for CsrfConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            session_cookie: "session".to_string(),
+            cookie_name: "csrf_token".to_string(),
+            header_name: "x-csrf-token".to_string(),
+        }
+    }
+}
+
+/// ログ設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            format: LogFormat::Pretty,
+        }
+    }
+}
+
+/// ログの出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 人間向けの整形済み出力
+    Pretty,
+    /// 1行1JSONの構造化出力
+    Json,
+}
+
+/// リクエスト制限
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// JSONリクエストボディの最大バイト数
+    pub json_payload_bytes: usize,
+    /// インポートするファイルの最大バイト数
+    pub import_file_bytes: usize,
+    /// ワーカーあたりの最大同時接続数
+    pub max_connections: usize,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            json_payload_bytes: 64 * 1024,
+            import_file_bytes: 1024 * 1024,
+            max_connections: 25_000,
+        }
+    }
+}
+
+/// レート制限設定
+/// クライアントのIPアドレス、APIトークン、ユーザーのそれぞれに、読み取り・書き込みで別のトークンバケットを持つ。
+/// リクエストは識別できたすべてのバケットで数え、どれか1つでも上限を超えていれば拒否する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// クライアントIPの判定に X-Forwarded-For を信頼するか
+    pub trust_forwarded_for: bool,
+    /// IPアドレスごとの GET/HEAD のバケット
+    pub read: BucketConfig,
+    /// IPアドレスごとの POST/PUT/DELETE などのバケット
+    pub write: BucketConfig,
+    /// APIトークン（`Authorization: Bearer`）ごとのバケット
+    pub token: ScopedBuckets,
+    /// ユーザー（`Authorization: Basic` のユーザー名）ごとのバケット
+    pub user: ScopedBuckets,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        let ScopedBuckets { read, write } = ScopedBuckets::default();
+        Self {
+            enabled: true,
+            trust_forwarded_for: false,
+            read,
+            write,
+            token: ScopedBuckets::default(),
+            user: ScopedBuckets::default(),
+        }
+    }
+}
+
+/// 読み取り・書き込みのバケットの組
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScopedBuckets {
+    /// GET/HEAD のバケット
+    pub read: BucketConfig,
+    /// POST/PUT/DELETE などのバケット
+    pub write: BucketConfig,
+}
+
+impl Default for ScopedBuckets {
+    fn default() -> Self {
+        Self {
+            read: BucketConfig {
+                capacity: 120,
+                refill_per_sec: 2.0,
+            },
+            write: BucketConfig {
+                capacity: 30,
+                refill_per_sec: 0.5,
+            },
+        }
+    }
+}
+
+/// トークンバケットの設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BucketConfig {
+    /// 連続で受け付けられる最大リクエスト数
+    pub capacity: u32,
+    /// 1秒あたりに回復するリクエスト数
+    pub refill_per_sec: f64,
+}
+
+/// APIバージョン設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiConfig {
+    /// `/api/...` でバージョンが指定されなかった場合のバージョン
+    pub default_version: String,
+    /// 非推奨にしたバージョン（`Deprecation` / `Sunset` ヘッダーを付与する）
+    pub deprecations: Vec<VersionDeprecation>,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            default_version: "v1".to_string(),
+            deprecations: Vec::new(),
+        }
+    }
+}
+
+/// GraphQL（`/api/graphql`）の設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GraphqlConfig {
+    /// クエリの入れ子の深さの上限
+    pub max_depth: usize,
+    /// クエリの複雑さ（取得するフィールド数の見積もり）の上限
+    pub max_complexity: usize,
+    /// 配列でまとめて送れる操作の数の上限
+    pub max_batch_size: usize,
+    /// GraphiQL を提供するか（省略時はデバッグビルドでのみ提供）
+    pub playground: bool,
+}
+
+impl Default for GraphqlConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 8,
+            max_complexity: 1000,
+            max_batch_size: 10,
+            playground: cfg!(debug_assertions),
+        }
+    }
+}
+
+/// バージョンの非推奨設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionDeprecation {
+    pub version: String,
+    /// 非推奨になった日時（RFC 3339）
+    pub deprecated_at: DateTime<Utc>,
+    /// 提供を終了する日時（RFC 3339）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<DateTime<Utc>>,
+}
+
+impl Config {
+    /// コマンドライン引数・環境変数・設定ファイルから設定を読み込んで検証する
+    pub fn load(cli: &Cli) -> Result<Self, String> {
+        let mut config = match &cli.config {
+            Some(path) => Self::from_file(path)?,
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                Self::from_file(Path::new(DEFAULT_CONFIG_PATH))?
+            }
+            None => Self::default(),
+        };
+        config.apply_cli(cli);
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// TOMLファイルから読み込む（未指定の項目はデフォルト値）
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("設定ファイル {} を読み込めません: {}", path.display(), e))?;
+        toml::from_str(&content)
+            .map_err(|e| format!("設定ファイル {} の形式が不正です: {}", path.display(), e))
+    }
+
+    /// コマンドライン引数・環境変数で指定された値で上書き
+    fn apply_cli(&mut self, cli: &Cli) {
+        fn set<T: Clone>(target: &mut T, value: &Option<T>) {
+            if let Some(value) = value {
+                *target = value.clone();
+            }
+        }
+
+        set(&mut self.server.host, &cli.host);
+        set(&mut self.server.port, &cli.port);
+        set(&mut self.server.public_url, &cli.public_url);
+        if cli.workers.is_some() {
+            self.server.workers = cli.workers;
+        }
+        set(&mut self.database.url, &cli.database_url);
+        set(&mut self.database.username, &cli.database_user);
+        set(&mut self.database.password, &cli.database_pass);
+        set(&mut self.database.namespace, &cli.database_ns);
+        set(&mut self.database.database, &cli.database_name);
+        if !cli.cors_origins.is_empty() {
+            self.cors.allowed_origins = cli.cors_origins.clone();
+        }
+        set(&mut self.log.level, &cli.log_level);
+        set(&mut self.log.format, &cli.log_format);
+        set(&mut self.limits.json_payload_bytes, &cli.json_payload_limit);
+        set(&mut self.limits.import_file_bytes, &cli.import_file_limit);
+        set(&mut self.rate_limit.enabled, &cli.rate_limit_enabled);
+        set(
+            &mut self.rate_limit.trust_forwarded_for,
+            &cli.rate_limit_trust_forwarded_for,
+        );
+        set(&mut self.graphql.playground, &cli.graphql_playground);
+    }
+
+    /// 設定値を検証し、問題があればすべてまとめて返す
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+
+        if self.server.host.trim().is_empty() {
+            errors.push("server.host が空です".to_string());
+        }
+        if self.server.port == 0 {
+            errors.push("server.port に0は指定できません".to_string());
+        }
+        if self.server.workers == Some(0) {
+            errors.push("server.workers は1以上を指定してください".to_string());
+        }
+        if !["http://", "https://"]
+            .iter()
+            .any(|scheme| self.server.public_url.starts_with(scheme))
+        {
+            errors.push(format!(
+                "server.public_url の {} は http:// または https:// で始まる必要があります",
+                self.server.public_url
+            ));
+        }
+        if self.database.url.trim().is_empty() {
+            errors.push("database.url が空です".to_string());
+        } else if !["http://", "https://", "ws://", "wss://"]
+            .iter()
+            .any(|scheme| self.database.endpoint().starts_with(scheme))
+        {
+            errors.push(format!(
+                "database.url の {} は http(s):// または ws(s):// で始まる必要があります",
+                self.database.url
+            ));
+        }
+        if self.database.username.is_empty() {
+            errors.push(
+                "database.username が設定されていません (DATABASE_USER / --database-user)"
+                    .to_string(),
+            );
+        }
+        if self.database.password.is_empty() {
+            errors.push(
+                "database.password が設定されていません (DATABASE_PASS / --database-pass)"
+                    .to_string(),
+            );
+        }
+        if self.database.namespace.trim().is_empty() {
+            errors.push("database.namespace が空です".to_string());
+        }
+        if self.database.database.trim().is_empty() {
+            errors.push("database.database が空です".to_string());
+        }
+        for (name, value) in [
+            (
+                "database.connect_timeout_secs",
+                self.database.connect_timeout_secs,
+            ),
+            (
+                "database.query_timeout_secs",
+                self.database.query_timeout_secs,
+            ),
+            (
+                "database.reconnect_max_backoff_secs",
+                self.database.reconnect_max_backoff_secs,
+            ),
+            (
+                "database.health_check_interval_secs",
+                self.database.health_check_interval_secs,
+            ),
+        ] {
+            if value == 0 {
+                errors.push(format!("{} は1以上を指定してください", name));
+            }
+        }
+        for origin in &self.cors.allowed_origins {
+            if origin != "*" && !origin.starts_with("http://") && !origin.starts_with("https://") {
+                errors.push(format!(
+                    "cors.allowed_origins の {} は http:// または https:// で始まる必要があります",
+                    origin
+                ));
+            }
+        }
+        if self.cors.allow_credentials && self.cors.allows_any_origin() {
+            errors.push(
+                "cors.allow_credentials を有効にする場合、cors.allowed_origins に * は指定できません"
+                    .to_string(),
+            );
+        }
+        for method in &self.cors.allowed_methods {
+            if actix_web::http::Method::from_bytes(method.as_bytes()).is_err() {
+                errors.push(format!("cors.allowed_methods の {} は不正です", method));
+            }
+        }
+        for (name, headers) in [
+            ("cors.allowed_headers", &self.cors.allowed_headers),
+            ("cors.expose_headers", &self.cors.expose_headers),
+        ] {
+            for value in headers {
+                if actix_web::http::header::HeaderName::from_bytes(value.as_bytes()).is_err() {
+                    errors.push(format!("{} の {} は不正です", name, value));
+                }
+            }
+        }
+        for (name, value) in [
+            (
+                "security.content_security_policy",
+                &self.security.content_security_policy,
+            ),
+            ("security.referrer_policy", &self.security.referrer_policy),
+        ] {
+            if actix_web::http::header::HeaderValue::from_str(value).is_err() {
+                errors.push(format!("{} に使用できない文字が含まれています", name));
+            }
+        }
+        let csrf = &self.security.csrf;
+        if csrf.enabled {
+            if csrf.session_cookie.is_empty() || csrf.cookie_name.is_empty() {
+                errors.push("security.csrf の Cookie 名が空です".to_string());
+            }
+            if actix_web::http::header::HeaderName::from_bytes(csrf.header_name.as_bytes()).is_err()
+            {
+                errors.push(format!(
+                    "security.csrf.header_name の {} は不正です",
+                    csrf.header_name
+                ));
+            }
+        }
+        if !matches!(
+            self.log.level.to_ascii_lowercase().as_str(),
+            "trace" | "debug" | "info" | "warn" | "error" | "off"
+        ) {
+            errors.push(format!(
+                "log.level の {} は不正です (trace, debug, info, warn, error, off)",
+                self.log.level
+            ));
+        }
+        if self.limits.json_payload_bytes == 0 {
+            errors.push("limits.json_payload_bytes は1以上を指定してください".to_string());
+        }
+        if self.limits.import_file_bytes == 0 {
+            errors.push("limits.import_file_bytes は1以上を指定してください".to_string());
+        }
+        if self.limits.max_connections == 0 {
+            errors.push("limits.max_connections は1以上を指定してください".to_string());
+        }
+        for (name, bucket) in [
+            ("rate_limit.read", &self.rate_limit.read),
+            ("rate_limit.write", &self.rate_limit.write),
+            ("rate_limit.token.read", &self.rate_limit.token.read),
+            ("rate_limit.token.write", &self.rate_limit.token.write),
+            ("rate_limit.user.read", &self.rate_limit.user.read),
+            ("rate_limit.user.write", &self.rate_limit.user.write),
+        ] {
+            if bucket.capacity == 0 {
+                errors.push(format!("{}.capacity は1以上を指定してください", name));
+            }
+            if !(bucket.refill_per_sec > 0.0 && bucket.refill_per_sec.is_finite()) {
+                errors.push(format!(
+                    "{}.refill_per_sec は正の数を指定してください",
+                    name
+                ));
+            }
+        }
+
+        for deprecation in &self.api.deprecations {
+            if let Some(sunset) = deprecation.sunset {
+                if sunset <= deprecation.deprecated_at {
+                    errors.push(format!(
+                        "api.deprecations の {} の sunset は deprecated_at より後にしてください",
+                        deprecation.version
+                    ));
+                }
+            }
+        }
+
+        if self.graphql.max_depth == 0 {
+            errors.push("graphql.max_depth は1以上を指定してください".to_string());
+        }
+        if self.graphql.max_complexity == 0 {
+            errors.push("graphql.max_complexity は1以上を指定してください".to_string());
+        }
+        if self.graphql.max_batch_size == 0 {
+            errors.push("graphql.max_batch_size は1以上を指定してください".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "設定が不正です:\n{}",
+                errors
+                    .iter()
+                    .map(|e| format!("  - {}", e))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ))
+        }
+    }
+
+    /// 秘密情報を伏せたコピー
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        if !config.database.password.is_empty() {
+            config.database.password = REDACTED.to_string();
+        }
+        config
+    }
+
+    /// 秘密情報を伏せたTOML表現
+    pub fn to_redacted_toml(&self) -> String {
+        toml::to_string_pretty(&self.redacted()).expect("設定はTOMLに変換できる必要があります")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_config() -> Config {
+        let mut config = Config::default();
+        config.database.username = "root".to_string();
+        config.database.password = "secret".to_string();
+        config
+    }
+
+    #[test]
+    fn test_cli_overrides_file() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [server]
+            port = 3000
+
+            [database]
+            username = "file-user"
+            password = "file-pass"
+            "#,
+        )
+        .unwrap();
+        let cli = Cli {
+            port: Some(9000),
+            database_user: Some("cli-user".to_string()),
+            ..Default::default()
+        };
+
+        config.apply_cli(&cli);
+
+        assert_eq!(config.server.port, 9000);
+        assert_eq!(config.database.username, "cli-user");
+        assert_eq!(config.database.password, "file-pass");
+        assert_eq!(config.database.namespace, "app");
+    }
+
+    #[test]
+    fn test_missing_credentials() {
+        let error = Config::default().validate().unwrap_err();
+        assert!(error.contains("database.username"));
+        assert!(error.contains("database.password"));
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let mut config = valid_config();
+        assert!(config.validate().is_ok());
+
+        config.log.level = "verbose".to_string();
+        config.cors.allowed_origins = vec!["example.com".to_string()];
+        config.server.public_url = "todo.example.com".to_string();
+        let error = config.validate().unwrap_err();
+        assert!(error.contains("log.level"));
+        assert!(error.contains("cors.allowed_origins"));
+        assert!(error.contains("server.public_url"));
+    }
+
+    #[test]
+    fn test_redacted_hides_password() {
+        let output = valid_config().to_redacted_toml();
+        assert!(!output.contains("secret"));
+        assert!(output.contains(REDACTED));
+    }
+}
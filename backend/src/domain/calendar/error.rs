@@ -0,0 +1,88 @@
+use crate::domain::todo::TodoError;
+use std::fmt;
+
+/// カレンダーフィードのエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalendarError {
+    /// トークンが一致しない（再発行された古いURLなど）
+    FeedNotFound,
+    /// 永続化での失敗（内容はログにのみ出す）
+    Repository(String),
+}
+
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalendarError::FeedNotFound => write!(f, "カレンダーフィードが見つかりません"),
+            CalendarError::Repository(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CalendarError {}
+
+/// リポジトリのエラー（文字列）から変換
+impl From<String> for CalendarError {
+    fn from(e: String) -> Self {
+        CalendarError::Repository(e)
+    }
+}
+
+/// Todoリポジトリのエラーから変換
+impl From<TodoError> for CalendarError {
+    fn from(e: TodoError) -> Self {
+        CalendarError::Repository(e.to_string())
+    }
+}
+
+/// CalDAVでのエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalDavError {
+    /// パスワードがCalDAVのトークンと一致しない（まだ発行していない場合も含む）
+    Unauthorized,
+    /// リソースがない
+    NotFound,
+    /// `If-Match` / `If-None-Match` の条件を満たさない
+    PreconditionFailed,
+    /// iCalendarとして読めない、またはTodoにできない
+    InvalidCalendarData(String),
+    /// VTODO 以外のコンポーネント
+    UnsupportedComponent,
+    /// 同じUIDのリソースが別の名前である（UIDを変えようとした場合も含む）
+    UidConflict { name: String },
+    /// 永続化での失敗（内容はログにのみ出す）
+    Repository(String),
+}
+
+impl fmt::Display for CalDavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalDavError::Unauthorized => write!(f, "認証に失敗しました"),
+            CalDavError::NotFound => write!(f, "リソースが見つかりません"),
+            CalDavError::PreconditionFailed => write!(f, "ETagが一致しません"),
+            CalDavError::InvalidCalendarData(e) => write!(f, "iCalendarが不正です: {}", e),
+            CalDavError::UnsupportedComponent => write!(f, "VTODO 以外は保存できません"),
+            CalDavError::UidConflict { name } => write!(f, "UIDが {} と重複しています", name),
+            CalDavError::Repository(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CalDavError {}
+
+/// リポジトリのエラー（文字列）から変換
+impl From<String> for CalDavError {
+    fn from(e: String) -> Self {
+        CalDavError::Repository(e)
+    }
+}
+
+/// Todoリポジトリのエラーから変換（Todoがない場合はリソースがない）
+impl From<TodoError> for CalDavError {
+    fn from(e: TodoError) -> Self {
+        match e {
+            TodoError::NotFound => CalDavError::NotFound,
+            e => CalDavError::Repository(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod error;
+pub mod repository;
+pub mod resource;
+pub mod token;
+
+pub use error::{CalDavError, CalendarError};
+pub use repository::{CalendarFeedRepository, CalendarResourceRepository};
+pub use resource::CalendarResource;
+pub use token::{FeedToken, TokenKind};
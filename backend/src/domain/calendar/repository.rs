@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use super::resource::CalendarResource;
+use super::token::{FeedToken, TokenKind};
+
+/// カレンダーフィードとCalDAVのトークンの保存先（用途ごとに1つ）
+#[async_trait]
+pub trait CalendarFeedRepository: Send + Sync {
+    /// 保存済みの `kind` のトークン（まだ発行していなければ `None`）
+    async fn find_token(&self, kind: TokenKind) -> Result<Option<FeedToken>, String>;
+
+    /// 保存済みの `kind` のトークン。まだなければ `candidate` を保存して返す
+    async fn find_or_insert_token(
+        &self,
+        kind: TokenKind,
+        candidate: &FeedToken,
+    ) -> Result<FeedToken, String>;
+
+    /// `kind` のトークンを置き換える（古いトークンは使えなくなる）
+    async fn replace_token(&self, kind: TokenKind, token: &FeedToken) -> Result<(), String>;
+}
+
+/// CalDAVクライアントが作ったTodoの名前とUIDの保存先
+/// 既定の名前とUIDのTodoは保存しない
+#[async_trait]
+pub trait CalendarResourceRepository: Send + Sync {
+    /// 保存済みのすべての名前とUID
+    async fn find_all(&self) -> Result<Vec<CalendarResource>, String>;
+
+    /// 名前とUIDを保存する（同じTodoのものは置き換える）
+    async fn save(&self, resource: &CalendarResource) -> Result<(), String>;
+
+    /// Todoの名前とUIDを削除する
+    async fn delete(&self, todo_id: i32) -> Result<(), String>;
+}
@@ -0,0 +1,23 @@
+/// CalDAVのコレクション内でのTodoの名前とUID
+///
+/// サーバーで作ったTodoは `todo-{id}.ics` と `todo-{id}@todo-app` で表す（フィードのUIDと同じ）。
+/// CalDAVクライアントが作ったTodoは、クライアントが選んだ名前とUIDを保存して同じものを返す。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarResource {
+    pub todo_id: i32,
+    /// コレクション内のリソース名（`abc.ics` など）
+    pub name: String,
+    /// VTODO の UID
+    pub uid: String,
+}
+
+impl CalendarResource {
+    /// 名前とUIDを保存していないTodoのリソース
+    pub fn default_for(todo_id: i32) -> Self {
+        Self {
+            todo_id,
+            name: format!("todo-{}.ics", todo_id),
+            uid: format!("todo-{}@todo-app", todo_id),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use uuid::Uuid;
+
+/// トークンの用途（それぞれ別に発行・再発行する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// カレンダーフィードのURL（読み取り専用）
+    Feed,
+    /// CalDAVのパスワード（読み書きできる）。フィードのURLから分からないよう別に発行する
+    CalDav,
+}
+
+/// カレンダーフィードのURLやCalDAVのパスワードにする秘密のトークン
+/// 知っていれば誰でも使えるため、漏れたら再発行して古いトークンを無効にする
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedToken(String);
+
+impl FeedToken {
+    /// 推測できないトークンを作る（UUID v4 2つ分、244ビットの乱数）
+    pub fn generate() -> Self {
+        Self(format!(
+            "{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        ))
+    }
+
+    /// 保存済みのトークンを復元
+    pub fn from_stored(value: String) -> Self {
+        Self(value)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// URLのトークンと一致するか（一致した長さから推測されないよう、常に全体を比べる）
+    pub fn matches(&self, candidate: &str) -> bool {
+        let (a, b) = (self.0.as_bytes(), candidate.as_bytes());
+        a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_tokens_are_unique_and_compared_exactly() {
+        let token = FeedToken::generate();
+        assert_eq!(token.as_str().len(), 64);
+        assert_ne!(token, FeedToken::generate());
+
+        assert!(token.matches(token.as_str()));
+        assert!(!token.matches(&token.as_str()[..63]));
+        assert!(!token.matches(&token.as_str().to_uppercase()));
+        assert!(!token.matches(""));
+    }
+}
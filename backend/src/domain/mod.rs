@@ -1 +1,2 @@
+pub mod calendar;
 pub mod todo;
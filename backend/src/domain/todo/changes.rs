@@ -0,0 +1,13 @@
+use super::entity::Todo;
+
+/// 変更シーケンスのある時点以降の変更
+/// Todoの作成・更新・削除のたびにシーケンスが1つずつ進む
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoChanges {
+    /// 作成・更新されたTodo
+    pub changed: Vec<Todo>,
+    /// 削除されたTodoのID
+    pub deleted: Vec<i32>,
+    /// 取得時点のシーケンス（次回はこの値以降の変更を取得する）
+    pub sequence: u64,
+}
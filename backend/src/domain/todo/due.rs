@@ -0,0 +1,61 @@
+//! 期限
+//!
+//! 日付だけの期限（終日）と、時刻まで決まった期限の2種類がある。
+//! 時刻付きの期限はオフセット付きで受け取り、UTCに直して保存する。
+//! 日付だけの期限はどのタイムゾーンでも同じ日を指す。
+
+use std::fmt;
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Due {
+    /// その日のうち（`2026-10-19`）
+    Date(NaiveDate),
+    /// その時刻まで（`2026-10-19T09:00:00+09:00` など）
+    DateTime(DateTime<Utc>),
+}
+
+impl Due {
+    /// `YYYY-MM-DD` か RFC 3339 の日時（オフセット必須）を解釈する
+    pub fn parse(value: &str) -> Option<Due> {
+        let value = value.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Some(Due::Date(date));
+        }
+        DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|at| Due::DateTime(at.with_timezone(&Utc)))
+    }
+}
+
+/// 保存・APIでの表記（`2026-10-19` か `2026-10-19T00:00:00Z`）
+impl fmt::Display for Due {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Due::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            Due::DateTime(at) => write!(f, "{}", at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dates_and_normalize_offsets_to_utc() {
+        assert_eq!(
+            Due::parse("2026-10-19").map(|due| due.to_string()),
+            Some("2026-10-19".to_string())
+        );
+        assert_eq!(
+            Due::parse("2026-10-19T09:00:00+09:00").map(|due| due.to_string()),
+            Some("2026-10-19T00:00:00Z".to_string())
+        );
+        // オフセットのない日時はタイムゾーンが決まらないため受け付けない
+        assert_eq!(Due::parse("2026-10-19T09:00:00"), None);
+        assert_eq!(Due::parse("2026-02-30"), None);
+    }
+}
@@ -1,34 +1,62 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::due::Due;
+use super::error::TodoError;
+
 /// Todoドメインエンティティ
 /// ビジネスロジックとドメイン知識をカプセル化
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Todo {
     id: Option<i32>,
     title: String,
     completed: bool,
+    /// 並び順（昇順）。新しいTodoは保存時に末尾に置かれる
+    position: f64,
+    /// 作成日時（永続化前は `None`）
+    created_at: Option<DateTime<Utc>>,
+    /// 期限
+    due: Option<Due>,
+    /// 完了日時（未完了なら `None`。完了にしたときに保存先で記録される）
+    completed_at: Option<DateTime<Utc>>,
 }
 
 impl Todo {
     /// 新しいTodoを作成（永続化前、IDなし）
-    pub fn new(title: String) -> Result<Self, String> {
+    pub fn new(title: String) -> Result<Self, TodoError> {
         if title.trim().is_empty() {
-            return Err("タイトルは空にできません".to_string());
+            return Err(TodoError::EmptyTitle);
         }
         
         Ok(Self {
             id: None,
             title: title.trim().to_string(),
             completed: false,
+            position: 0.0,
+            created_at: None,
+            due: None,
+            completed_at: None,
         })
     }
 
     /// 既存のTodoを再構築（永続化済み、ID付き）
-    pub fn reconstruct(id: i32, title: String, completed: bool) -> Self {
+    pub fn reconstruct(
+        id: i32,
+        title: String,
+        completed: bool,
+        position: f64,
+        created_at: Option<DateTime<Utc>>,
+        due: Option<Due>,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> Self {
         Self {
             id: Some(id),
             title,
             completed,
+            position,
+            created_at,
+            due,
+            completed_at,
         }
     }
 
@@ -47,10 +75,35 @@ impl Todo {
         self.completed
     }
 
+    /// 並び順を取得
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+
+    /// 作成日時を取得
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    /// 期限を取得
+    pub fn due(&self) -> Option<Due> {
+        self.due
+    }
+
+    /// 完了日時を取得
+    pub fn completed_at(&self) -> Option<DateTime<Utc>> {
+        self.completed_at
+    }
+
+    /// 期限を変更（`None` で期限なし）
+    pub fn set_due(&mut self, due: Option<Due>) {
+        self.due = due;
+    }
+
     /// タイトルを変更
-    pub fn change_title(&mut self, new_title: String) -> Result<(), String> {
+    pub fn change_title(&mut self, new_title: String) -> Result<(), TodoError> {
         if new_title.trim().is_empty() {
-            return Err("タイトルは空にできません".to_string());
+            return Err(TodoError::EmptyTitle);
         }
         self.title = new_title.trim().to_string();
         Ok(())
@@ -64,9 +117,16 @@ impl Todo {
     /// Todoを未完了にする
     pub fn uncomplete(&mut self) {
         self.completed = false;
+        self.completed_at = None;
+    }
+
+    /// 並び順を変更
+    pub fn move_to(&mut self, position: f64) {
+        self.position = position;
     }
 
     /// 完了状態をトグル
+    #[allow(dead_code)]
     pub fn toggle_completion(&mut self) {
         self.completed = !self.completed;
     }
@@ -87,7 +147,7 @@ mod tests {
     #[test]
     fn test_empty_title() {
         let result = Todo::new("  ".to_string());
-        assert!(result.is_err());
+        assert_eq!(result, Err(TodoError::EmptyTitle));
     }
 
     #[test]
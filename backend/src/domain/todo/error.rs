@@ -0,0 +1,47 @@
+use std::fmt;
+
+/// Todoの操作で起こるエラー
+/// 表示する文言はプレゼンテーション層がリクエストの言語に合わせて選ぶ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TodoError {
+    /// タイトルが空（空白のみを含む）
+    EmptyTitle,
+    NotFound,
+    /// 並べ替えの移動先のTodoが見つからない
+    MoveTargetNotFound,
+    /// 並べ替えで移動先が1つに決まらない
+    InvalidMove,
+    /// 期限を日付・日時として解釈できない
+    InvalidDue,
+    /// 永続化での失敗（内容はログにのみ出す）
+    Repository(String),
+}
+
+impl fmt::Display for TodoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TodoError::EmptyTitle => write!(f, "タイトルは空にできません"),
+            TodoError::NotFound => write!(f, "Todoが見つかりません"),
+            TodoError::MoveTargetNotFound => write!(f, "移動先のTodoが見つかりません"),
+            TodoError::InvalidMove => {
+                write!(f, "before か after のどちらか一方を指定してください")
+            }
+            TodoError::InvalidDue => {
+                write!(
+                    f,
+                    "期限は YYYY-MM-DD かオフセット付きの RFC 3339 で指定してください"
+                )
+            }
+            TodoError::Repository(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TodoError {}
+
+/// 永続化のエラー（文字列）から変換
+impl From<String> for TodoError {
+    fn from(e: String) -> Self {
+        TodoError::Repository(e)
+    }
+}
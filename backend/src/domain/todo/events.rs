@@ -0,0 +1,11 @@
+use super::entity::Todo;
+
+/// Todoの変更の通知
+/// 変更を保存した後に送る。購読者は差分同期などで最新の状態を取り直す
+#[derive(Debug, Clone, PartialEq)]
+pub enum TodoEvent {
+    /// 作成・更新された
+    Saved(Todo),
+    /// 削除された
+    Deleted(i32),
+}
@@ -1,5 +1,14 @@
+pub mod changes;
+pub mod due;
 pub mod entity;
+pub mod error;
+pub mod events;
+pub mod position;
 pub mod repository;
 
+pub use changes::TodoChanges;
+pub use due::Due;
 pub use entity::Todo;
+pub use error::TodoError;
+pub use events::TodoEvent;
 pub use repository::TodoRepository;
@@ -0,0 +1,50 @@
+//! 手動の並び順
+//!
+//! 並び順は小数で表し、2つのTodoの間に移動するときは両隣の中間の値を振る。
+//! 他のTodoの位置を変えずに済むが、同じ場所への移動を繰り返すと精度が尽きるため、
+//! その場合は全体を振り直す。
+
+/// 両隣の間に置く位置（`None` は先頭・末尾）
+/// 間に値を取れない場合は `None`（全体の振り直しが必要）
+pub fn between(before: Option<f64>, after: Option<f64>) -> Option<f64> {
+    let position = match (before, after) {
+        (None, None) => 1.0,
+        (Some(before), None) => before.floor() + 1.0,
+        (None, Some(after)) => after.ceil() - 1.0,
+        (Some(before), Some(after)) => before + (after - before) / 2.0,
+    };
+
+    let above = before.is_none_or(|before| position > before);
+    let below = after.is_none_or(|after| position < after);
+    (above && below && position.is_finite()).then_some(position)
+}
+
+/// 並び順どおりに `1.0, 2.0, ...` と振り直した位置
+pub fn renumbered(count: usize) -> impl Iterator<Item = f64> {
+    (1..=count).map(|n| n as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_between_until_precision_runs_out() {
+        assert_eq!(between(None, None), Some(1.0));
+        assert_eq!(between(Some(2.5), None), Some(3.0));
+        assert_eq!(between(None, Some(1.0)), Some(0.0));
+        assert_eq!(between(Some(1.0), Some(2.0)), Some(1.5));
+
+        // 同じ位置に並んだTodoの間には置けない
+        assert_eq!(between(Some(0.0), Some(0.0)), None);
+
+        // 同じ隙間への移動を繰り返すと、いずれ振り直しが必要になる
+        let mut after = 2.0;
+        let mut moves = 0;
+        while let Some(position) = between(Some(1.0), Some(after)) {
+            after = position;
+            moves += 1;
+        }
+        assert!(moves > 40);
+    }
+}
@@ -1,22 +1,47 @@
 use async_trait::async_trait;
+use super::changes::TodoChanges;
 use super::entity::Todo;
+use super::error::TodoError;
 
 /// Todoリポジトリトレイト
 /// インフラストラクチャ層で実装される永続化の抽象インターフェース
 #[async_trait]
 pub trait TodoRepository: Send + Sync {
-    /// すべてのTodoを取得
-    async fn find_all(&self) -> Result<Vec<Todo>, String>;
+    /// すべてのTodoを並び順に取得
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError>;
     
     /// IDでTodoを取得
-    async fn find_by_id(&self, id: i32) -> Result<Option<Todo>, String>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<Todo>, TodoError>;
+
+    /// 複数のIDのTodoをまとめて取得（存在しないIDは結果に含めない）
+    /// 既定では1件ずつ取得する。1回のクエリで取得できる実装は上書きする
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Todo>, TodoError> {
+        let mut todos = Vec::with_capacity(ids.len());
+        for id in ids {
+            todos.extend(self.find_by_id(*id).await?);
+        }
+        Ok(todos)
+    }
     
-    /// Todoを保存（作成）
-    async fn save(&self, todo: &Todo) -> Result<Todo, String>;
+    /// Todoを保存（作成）。並び順は末尾になる
+    async fn save(&self, todo: &Todo) -> Result<Todo, TodoError>;
     
-    /// Todoを更新
-    async fn update(&self, todo: &Todo) -> Result<Todo, String>;
+    /// Todoを更新（存在しない場合は `TodoError::NotFound`）
+    async fn update(&self, todo: &Todo) -> Result<Todo, TodoError>;
     
-    /// Todoを削除
-    async fn delete(&self, id: i32) -> Result<(), String>;
+    /// 複数のTodoの並び順をまとめて更新（並べ替えで全体の位置を振り直す場合に使う）
+    /// 既定では1件ずつ更新する。すべてを1つのトランザクションで更新できる実装は上書きする
+    async fn update_positions(&self, todos: &[Todo]) -> Result<Vec<Todo>, TodoError> {
+        let mut updated = Vec::with_capacity(todos.len());
+        for todo in todos {
+            updated.push(self.update(todo).await?);
+        }
+        Ok(updated)
+    }
+
+    /// Todoを削除（存在しない場合は `TodoError::NotFound`）
+    async fn delete(&self, id: i32) -> Result<(), TodoError>;
+
+    /// シーケンス `since` より後の変更を並び順に取得（`None` の場合はすべてのTodo）
+    async fn find_changes_since(&self, since: Option<u64>) -> Result<TodoChanges, TodoError>;
 }
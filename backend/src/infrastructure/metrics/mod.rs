@@ -0,0 +1,87 @@
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+/// Prometheusメトリクスの登録先
+/// HTTP層・永続化層・ドメインの各メトリクスをまとめて保持する
+pub struct Metrics {
+    registry: Registry,
+    /// HTTPリクエスト数（method, route, status）
+    pub http_requests_total: IntCounterVec,
+    /// HTTPリクエストの処理時間（method, route, status）
+    pub http_request_duration_seconds: HistogramVec,
+    /// SurrealDBへのクエリ時間（operation）
+    pub db_query_duration_seconds: HistogramVec,
+    /// SurrealDBへのクエリ失敗数（operation）
+    pub db_query_errors_total: IntCounterVec,
+    /// Todo件数（state = total, open, completed）
+    pub todos: IntGaugeVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTPリクエスト数"),
+            &["method", "route", "status"],
+        )
+        .expect("メトリクス定義が不正です");
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTPリクエストの処理時間（秒）"),
+            &["method", "route", "status"],
+        )
+        .expect("メトリクス定義が不正です");
+        let db_query_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("db_query_duration_seconds", "SurrealDBクエリの処理時間（秒）")
+                .buckets(vec![
+                    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+                ]),
+            &["operation"],
+        )
+        .expect("メトリクス定義が不正です");
+        let db_query_errors_total = IntCounterVec::new(
+            Opts::new("db_query_errors_total", "SurrealDBクエリの失敗数"),
+            &["operation"],
+        )
+        .expect("メトリクス定義が不正です");
+        let todos = IntGaugeVec::new(Opts::new("todos", "状態別のTodo件数"), &["state"])
+            .expect("メトリクス定義が不正です");
+
+        for collector in [
+            Box::new(http_requests_total.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration_seconds.clone()),
+            Box::new(db_query_duration_seconds.clone()),
+            Box::new(db_query_errors_total.clone()),
+            Box::new(todos.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("メトリクスの登録に失敗しました");
+        }
+
+        Self {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            db_query_duration_seconds,
+            db_query_errors_total,
+            todos,
+        }
+    }
+
+    /// Prometheusのテキスト形式で出力
+    pub fn render(&self) -> Result<String, String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| format!("メトリクスの出力に失敗しました: {}", e))?;
+        String::from_utf8(buffer).map_err(|e| format!("メトリクスの出力に失敗しました: {}", e))
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,10 @@
+pub mod metrics;
 pub mod persistence;
+pub mod rate_limit;
 
-pub use persistence::{init_db, TodoRepositoryImpl};
+pub use metrics::Metrics;
+pub use persistence::{
+    check_database, CalendarFeedRepositoryImpl, CalendarResourceRepositoryImpl, Database,
+    MeteredTodoRepository, NotifyingTodoRepository, TodoRepositoryImpl,
+};
+pub use rate_limit::{MemoryRateLimitStore, RateLimiter};
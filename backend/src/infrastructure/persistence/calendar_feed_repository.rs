@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::domain::calendar::{CalendarFeedRepository, FeedToken, TokenKind};
+use crate::infrastructure::persistence::database::Database;
+
+/// SurrealDB実装のトークンの保存先
+/// フィードは `calendar_feed:current`、CalDAVのパスワードは `calendar_feed:caldav` の1件
+pub struct CalendarFeedRepositoryImpl {
+    db: Arc<Database>,
+}
+
+impl CalendarFeedRepositoryImpl {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// `kind` のトークンを保存するレコードのID
+    fn record_id(kind: TokenKind) -> &'static str {
+        match kind {
+            TokenKind::Feed => "current",
+            TokenKind::CalDav => "caldav",
+        }
+    }
+}
+
+#[async_trait]
+impl CalendarFeedRepository for CalendarFeedRepositoryImpl {
+    #[instrument(skip(self), err)]
+    async fn find_token(&self, kind: TokenKind) -> Result<Option<FeedToken>, String> {
+        let db = self.db.client().await?;
+        let token: Option<String> = self
+            .db
+            .with_timeout(
+                db.query("SELECT VALUE token FROM ONLY type::thing('calendar_feed', $id)")
+                    .bind(("id", Self::record_id(kind))),
+            )
+            .await?
+            .take(0)
+            .map_err(|e| format!("データベースエラー: {}", e))?;
+        Ok(token.map(FeedToken::from_stored))
+    }
+
+    #[instrument(skip(self, candidate), err)]
+    async fn find_or_insert_token(
+        &self,
+        kind: TokenKind,
+        candidate: &FeedToken,
+    ) -> Result<FeedToken, String> {
+        // 同時に発行しても同じトークンになるよう、1つの文で読み書きする
+        let db = self.db.client().await?;
+        let token: Option<String> = self
+            .db
+            .with_timeout(
+                db.query(
+                    "UPSERT ONLY type::thing('calendar_feed', $id) \
+                     SET token = token ?? $token RETURN VALUE token",
+                )
+                .bind(("id", Self::record_id(kind)))
+                .bind(("token", candidate.as_str().to_string())),
+            )
+            .await?
+            .take(0)
+            .map_err(|e| format!("データベースエラー: {}", e))?;
+        token
+            .map(FeedToken::from_stored)
+            .ok_or_else(|| "トークンの保存に失敗しました".to_string())
+    }
+
+    #[instrument(skip(self, token), err)]
+    async fn replace_token(&self, kind: TokenKind, token: &FeedToken) -> Result<(), String> {
+        let db = self.db.client().await?;
+        self.db
+            .with_timeout(
+                db.query("UPSERT type::thing('calendar_feed', $id) SET token = $token")
+                    .bind(("id", Self::record_id(kind)))
+                    .bind(("token", token.as_str().to_string())),
+            )
+            .await?
+            .check()
+            .map_err(|e| format!("データベースエラー: {}", e))?;
+        Ok(())
+    }
+}
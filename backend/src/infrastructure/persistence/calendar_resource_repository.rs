@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::domain::calendar::{CalendarResource, CalendarResourceRepository};
+use crate::infrastructure::persistence::database::Database;
+
+/// SurrealDB用のCalDAVリソースのレコード（`calendar_resources:<TodoのID>`）
+#[derive(Debug, Serialize, Deserialize)]
+struct CalendarResourceRecord {
+    todo_id: i32,
+    name: String,
+    uid: String,
+}
+
+/// SurrealDB実装のCalDAVリソースの名前とUIDの保存先
+pub struct CalendarResourceRepositoryImpl {
+    db: Arc<Database>,
+}
+
+impl CalendarResourceRepositoryImpl {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CalendarResourceRepository for CalendarResourceRepositoryImpl {
+    #[instrument(skip(self), err)]
+    async fn find_all(&self) -> Result<Vec<CalendarResource>, String> {
+        let db = self.db.client().await?;
+        let records: Vec<CalendarResourceRecord> = self
+            .db
+            .with_timeout(db.query("SELECT todo_id, name, uid FROM calendar_resources"))
+            .await?
+            .take(0)
+            .map_err(|e| format!("データベースエラー: {}", e))?;
+        Ok(records
+            .into_iter()
+            .map(|record| CalendarResource {
+                todo_id: record.todo_id,
+                name: record.name,
+                uid: record.uid,
+            })
+            .collect())
+    }
+
+    /// 削除済みのTodoに同じ名前が残っていれば、その名前を外してから保存する
+    #[instrument(skip(self), err)]
+    async fn save(&self, resource: &CalendarResource) -> Result<(), String> {
+        let db = self.db.client().await?;
+        self.db
+            .with_timeout(
+                db.query(
+                    "DELETE calendar_resources WHERE name = $name AND todo_id != $todo_id; \
+                     UPSERT type::thing('calendar_resources', $todo_id) \
+                     SET todo_id = $todo_id, name = $name, uid = $uid",
+                )
+                .bind(("todo_id", resource.todo_id))
+                .bind(("name", resource.name.clone()))
+                .bind(("uid", resource.uid.clone())),
+            )
+            .await?
+            .check()
+            .map_err(|e| format!("データベースエラー: {}", e))?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn delete(&self, todo_id: i32) -> Result<(), String> {
+        let db = self.db.client().await?;
+        self.db
+            .with_timeout(
+                db.query("DELETE type::thing('calendar_resources', $todo_id)")
+                    .bind(("todo_id", todo_id)),
+            )
+            .await?
+            .check()
+            .map_err(|e| format!("データベースエラー: {}", e))?;
+        Ok(())
+    }
+}
@@ -1,99 +1,237 @@
-use std::env;
+use std::fmt::Display;
+use std::future::IntoFuture;
+use std::sync::Arc;
 use std::time::Duration;
-use surrealdb::engine::remote::http::{Client, Http};
+use surrealdb::engine::any::{self, Any};
 use surrealdb::opt::auth::Root;
 use surrealdb::Surreal;
-use tokio::time::sleep;
-
-pub type DbClient = Surreal<Client>;
-
-/// データベース接続を初期化  
-pub async fn init_db() -> DbClient {
-    let db_url = env::var("DATABASE_URL").unwrap_or_else(|_| "http://127.0.0.1:8000".to_string());
-
-    // http:// または https:// スキームを削除
-    let db_host = db_url
-        .strip_prefix("http://")
-        .or_else(|| db_url.strip_prefix("https://"))
-        .unwrap_or(&db_url);
-
-    let db_user = env::var("DATABASE_USER").unwrap_or_else(|_| "root".to_string());
-
-    let db_pass = env::var("DATABASE_PASS").unwrap_or_else(|_| "root".to_string());
-
-    let db_ns = env::var("DATABASE_NS").unwrap_or_else(|_| "app".to_string());
-
-    let db_name = env::var("DATABASE_NAME").unwrap_or_else(|_| "todos".to_string());
-
-    println!("📡 SurrealDB接続中: {}", db_host);
-
-    // リトライロジック
-    let mut retries = 0;
-    let max_retries = 10;
+use tokio::sync::{watch, RwLock};
+use tokio::time::{sleep, timeout};
+use tracing::{info, warn};
+
+use crate::config::DatabaseConfig;
+
+/// SurrealDBクライアント
+/// URLのスキームに応じて HTTP (http/https) または WebSocket (ws/wss) で接続する
+pub type DbClient = Surreal<Any>;
+
+/// 現在のスキーマバージョン
+/// `init_schema` の定義を変更したら上げる。DBには定義と同じトランザクションで書き込み、
+/// 新しいサーバーが移行したDBを古いサーバーが古いバージョンで上書きすることはない
+pub const SCHEMA_VERSION: i64 = 5;
+
+/// 再接続の初回待ち時間
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// SurrealDBへの接続を管理する
+/// 起動時に接続できなくてもサーバーは起動し、バックグラウンドで再接続を続ける
+pub struct Database {
+    config: DatabaseConfig,
+    client: RwLock<Option<DbClient>>,
+    shutdown: watch::Sender<bool>,
+}
 
-    let db = loop {
-        match Surreal::new::<Http>(db_host).await {
-            Ok(db) => {
-                println!("✅ SurrealDB接続成功");
-                break db;
-            }
-            Err(e) => {
-                retries += 1;
-                if retries >= max_retries {
-                    panic!(
-                        "Failed to connect to SurrealDB after {} retries: {}",
-                        max_retries, e
-                    );
+impl Database {
+    /// 未接続の状態で作成（接続は `spawn_connector` で開始する）
+    pub fn new(config: DatabaseConfig) -> Arc<Self> {
+        let (shutdown, _) = watch::channel(false);
+        Arc::new(Self {
+            config,
+            client: RwLock::new(None),
+            shutdown,
+        })
+    }
+
+    /// メモリ上のSurrealDBに接続済みの状態で作成（テスト用）
+    #[cfg(test)]
+    pub async fn in_memory() -> Arc<Self> {
+        let db = any::connect("mem://").await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        init_schema(&db).await.unwrap();
+        let database = Self::new(DatabaseConfig::default());
+        *database.client.write().await = Some(db);
+        database
+    }
+
+    /// 接続済みのクライアントを取得
+    pub async fn client(&self) -> Result<DbClient, String> {
+        self.client
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| "データベースに接続されていません".to_string())
+    }
+
+    /// 接続済みかどうか
+    pub async fn is_connected(&self) -> bool {
+        self.client.read().await.is_some()
+    }
+
+    /// クエリにタイムアウトを設定して実行
+    pub async fn with_timeout<T, E: Display>(
+        &self,
+        query: impl IntoFuture<Output = Result<T, E>>,
+    ) -> Result<T, String> {
+        let limit = Duration::from_secs(self.config.query_timeout_secs);
+        match timeout(limit, query).await {
+            Ok(result) => result.map_err(|e| format!("データベースエラー: {}", e)),
+            Err(_) => Err(format!(
+                "データベースが{}秒以内に応答しませんでした",
+                limit.as_secs()
+            )),
+        }
+    }
+
+    /// 接続・死活監視のバックグラウンドタスクを開始
+    /// 未接続の間は指数バックオフで再接続し、接続後は定期的に死活監視して切断を検知する
+    pub fn spawn_connector(self: &Arc<Self>) {
+        let database = self.clone();
+        let mut shutdown = self.shutdown.subscribe();
+
+        tokio::spawn(async move {
+            let max_backoff = Duration::from_secs(database.config.reconnect_max_backoff_secs);
+            let health_interval = Duration::from_secs(database.config.health_check_interval_secs);
+            let mut backoff = INITIAL_BACKOFF;
+            let mut attempts: u32 = 0;
+
+            loop {
+                let wait = if database.is_connected().await {
+                    if !database.ping().await {
+                        warn!("SurrealDBとの接続が切れました。再接続します");
+                        *database.client.write().await = None;
+                        continue;
+                    }
+                    health_interval
+                } else {
+                    attempts += 1;
+                    match database.connect().await {
+                        Ok(client) => {
+                            info!(attempts, "SurrealDB接続完了");
+                            *database.client.write().await = Some(client);
+                            backoff = INITIAL_BACKOFF;
+                            attempts = 0;
+                            health_interval
+                        }
+                        Err(e) => {
+                            warn!(attempts, retry_in_secs = backoff.as_secs(), error = %e, "SurrealDB接続失敗。再試行します");
+                            let wait = backoff;
+                            backoff = (backoff * 2).min(max_backoff);
+                            wait
+                        }
+                    }
+                };
+
+                tokio::select! {
+                    _ = sleep(wait) => {}
+                    _ = shutdown.changed() => break,
                 }
-                println!(
-                    "⚠️  接続失敗 ({}/{}): {}. 再試行します...",
-                    retries, max_retries, e
-                );
-                sleep(Duration::from_secs(2)).await;
             }
-        }
-    };
-
-    println!("🔐 認証中...");
-
-    // 認証
-    db.signin(Root {
-        username: &db_user,
-        password: &db_pass,
-    })
-    .await
-    .expect("Failed to sign in");
 
-    println!("🗂️  Namespace/Database選択中: {}/{}", db_ns, db_name);
-
-    // Namespace と Database を使用
-    db.use_ns(&db_ns)
-        .use_db(&db_name)
-        .await
-        .expect("Failed to use namespace and database");
-
-    // スキーマ初期化
-    init_schema(&db).await;
-
-    db
+            *database.client.write().await = None;
+            info!("SurrealDB接続を終了しました");
+        });
+    }
+
+    /// バックグラウンドタスクを停止して接続を破棄
+    pub fn shutdown(&self) {
+        self.shutdown.send_replace(true);
+    }
+
+    /// 接続・認証・Namespace選択・スキーマ初期化を行う
+    async fn connect(&self) -> Result<DbClient, String> {
+        let endpoint = self.config.endpoint();
+        let connect_timeout = Duration::from_secs(self.config.connect_timeout_secs);
+        info!(endpoint = %endpoint, "SurrealDB接続中");
+
+        let steps = async {
+            let db = any::connect(endpoint.as_str())
+                .await
+                .map_err(|e| format!("接続に失敗しました: {}", e))?;
+
+            db.signin(Root {
+                username: &self.config.username,
+                password: &self.config.password,
+            })
+            .await
+            .map_err(|e| format!("認証に失敗しました: {}", e))?;
+
+            db.use_ns(&self.config.namespace)
+                .use_db(&self.config.database)
+                .await
+                .map_err(|e| format!("Namespace/Databaseの選択に失敗しました: {}", e))?;
+
+            init_schema(&db).await?;
+
+            Ok(db)
+        };
+
+        timeout(connect_timeout, steps).await.unwrap_or_else(|_| {
+            Err(format!(
+                "{}秒以内に接続できませんでした",
+                connect_timeout.as_secs()
+            ))
+        })
+    }
+
+    /// 接続が生きているかを確認
+    async fn ping(&self) -> bool {
+        let Ok(db) = self.client().await else {
+            return false;
+        };
+        self.with_timeout(db.health()).await.is_ok()
+    }
 }
 
 /// スキーマとテーブルを初期化
-async fn init_schema(db: &DbClient) {
-    println!("📋 スキーマ初期化中...");
+async fn init_schema(db: &DbClient) -> Result<(), String> {
+    info!("スキーマ初期化中");
 
     // Todoテーブルの定義
-    let _result = db
-        .query(
-            "
-            DEFINE TABLE IF NOT EXISTS todos SCHEMAFULL;
-            DEFINE FIELD IF NOT EXISTS title ON TABLE todos TYPE string;
-            DEFINE FIELD IF NOT EXISTS completed ON TABLE todos TYPE bool DEFAULT false;
-            DEFINE FIELD IF NOT EXISTS created_at ON TABLE todos TYPE datetime DEFAULT time::now();
-            ",
-        )
-        .await
-        .expect("Failed to initialize schema");
-
-    println!("✅ スキーマ初期化完了");
+    // seq は変更シーケンス（change_seq:current）の値で、作成・更新のたびに進める
+    // 削除したTodoは差分同期のために todo_tombstones に残す
+    // position は手動の並び順。既存のTodoは0にそろえ、作成順で並べる（並べ替えたときに振り直される）
+    // due は期限（`YYYY-MM-DD` かUTCの RFC 3339）、completed_at は完了にした日時
+    // calendar_feed:current はカレンダーフィードのURLのトークン
+    // calendar_resources はCalDAVクライアントが作ったTodoの名前とUID（レコードIDはTodoのID）
+    // 定義がすべて成功した場合にだけバージョンを書き込むよう、1つのトランザクションで実行する
+    db.query(
+        "
+        BEGIN TRANSACTION;
+        DEFINE TABLE IF NOT EXISTS todos SCHEMAFULL;
+        DEFINE FIELD IF NOT EXISTS title ON TABLE todos TYPE string;
+        DEFINE FIELD IF NOT EXISTS completed ON TABLE todos TYPE bool DEFAULT false;
+        DEFINE FIELD IF NOT EXISTS created_at ON TABLE todos TYPE datetime DEFAULT time::now();
+        DEFINE FIELD IF NOT EXISTS seq ON TABLE todos TYPE int DEFAULT 0;
+        DEFINE INDEX IF NOT EXISTS todos_seq ON TABLE todos FIELDS seq;
+        UPDATE todos SET seq = 0 WHERE seq = NONE;
+        DEFINE FIELD IF NOT EXISTS position ON TABLE todos TYPE float DEFAULT 0.0;
+        DEFINE INDEX IF NOT EXISTS todos_position ON TABLE todos FIELDS position;
+        UPDATE todos SET position = 0.0 WHERE position = NONE;
+        DEFINE FIELD IF NOT EXISTS due ON TABLE todos TYPE option<string>;
+        DEFINE FIELD IF NOT EXISTS completed_at ON TABLE todos TYPE option<datetime>;
+        DEFINE TABLE IF NOT EXISTS todo_tombstones SCHEMAFULL;
+        DEFINE FIELD IF NOT EXISTS todo_id ON TABLE todo_tombstones TYPE string;
+        DEFINE FIELD IF NOT EXISTS seq ON TABLE todo_tombstones TYPE int;
+        DEFINE FIELD IF NOT EXISTS deleted_at ON TABLE todo_tombstones TYPE datetime DEFAULT time::now();
+        DEFINE INDEX IF NOT EXISTS todo_tombstones_seq ON TABLE todo_tombstones FIELDS seq;
+        DEFINE TABLE IF NOT EXISTS change_seq SCHEMALESS;
+        DEFINE TABLE IF NOT EXISTS calendar_feed SCHEMALESS;
+        DEFINE TABLE IF NOT EXISTS calendar_resources SCHEMAFULL;
+        DEFINE FIELD IF NOT EXISTS todo_id ON TABLE calendar_resources TYPE int;
+        DEFINE FIELD IF NOT EXISTS name ON TABLE calendar_resources TYPE string;
+        DEFINE FIELD IF NOT EXISTS uid ON TABLE calendar_resources TYPE string;
+        DEFINE INDEX IF NOT EXISTS calendar_resources_name ON TABLE calendar_resources FIELDS name UNIQUE;
+        DEFINE TABLE IF NOT EXISTS schema_meta SCHEMALESS;
+        UPSERT schema_meta:current SET version = math::max([version ?? 0, $version]);
+        COMMIT TRANSACTION;
+        ",
+    )
+    .bind(("version", SCHEMA_VERSION))
+    .await
+    .map_err(|e| format!("スキーマ初期化に失敗しました: {}", e))?
+    .check()
+    .map_err(|e| format!("スキーマ初期化に失敗しました: {}", e))?;
+
+    info!("スキーマ初期化完了");
+    Ok(())
 }
@@ -0,0 +1,146 @@
+use serde::Serialize;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use super::database::{Database, SCHEMA_VERSION};
+
+/// 各チェックのタイムアウト
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 依存先のチェック結果
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl DependencyCheck {
+    fn from_result(name: &'static str, result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Self {
+                name,
+                ok: true,
+                detail: None,
+            },
+            Err(detail) => Self {
+                name,
+                ok: false,
+                detail: Some(detail),
+            },
+        }
+    }
+}
+
+/// SurrealDBが利用可能かを確認する
+/// 接続済み → 到達可能 → 認証済み → スキーマが最新 の順に確認し、失敗した時点で以降はスキップする
+pub async fn check_database(database: &Database) -> Vec<DependencyCheck> {
+    let mut checks = Vec::new();
+
+    let db = match database.client().await {
+        Ok(db) => {
+            checks.push(DependencyCheck::from_result("surrealdb_connected", Ok(())));
+            db
+        }
+        Err(e) => {
+            checks.push(DependencyCheck::from_result("surrealdb_connected", Err(e)));
+            return checks;
+        }
+    };
+
+    let reachable = with_timeout(async {
+        db.health()
+            .await
+            .map_err(|e| format!("SurrealDBに接続できません: {}", e))
+    })
+    .await;
+    let ok = reachable.is_ok();
+    checks.push(DependencyCheck::from_result(
+        "surrealdb_reachable",
+        reachable,
+    ));
+    if !ok {
+        return checks;
+    }
+
+    // 認証されていなければ INFO FOR DB は権限エラーになる
+    let signed_in = with_timeout(async {
+        let response = db
+            .query("INFO FOR DB")
+            .await
+            .map_err(|e| format!("SurrealDBの認証に失敗しています: {}", e))?;
+        response
+            .check()
+            .map(|_| ())
+            .map_err(|e| format!("SurrealDBの認証に失敗しています: {}", e))
+    })
+    .await;
+    let ok = signed_in.is_ok();
+    checks.push(DependencyCheck::from_result(
+        "surrealdb_signed_in",
+        signed_in,
+    ));
+    if !ok {
+        return checks;
+    }
+
+    let schema = with_timeout(async {
+        let mut response = db
+            .query("SELECT VALUE version FROM ONLY schema_meta:current")
+            .await
+            .map_err(|e| format!("スキーマバージョンを取得できません: {}", e))?;
+        let version: Option<i64> = response
+            .take(0)
+            .map_err(|e| format!("スキーマバージョンを取得できません: {}", e))?;
+        check_schema_version(version)
+    })
+    .await;
+    checks.push(DependencyCheck::from_result("schema_version", schema));
+
+    checks
+}
+
+/// DBに記録されたスキーマのバージョンがこのサーバーのものと一致するかを確認する
+/// 新しいサーバーが移行済みのDBでは、古いサーバーを準備完了にしない
+fn check_schema_version(version: Option<i64>) -> Result<(), String> {
+    match version {
+        Some(SCHEMA_VERSION) => Ok(()),
+        Some(version) if version > SCHEMA_VERSION => Err(format!(
+            "DBはこのサーバーより新しいスキーマに移行済みです (DB: {}, サーバー: {})",
+            version, SCHEMA_VERSION
+        )),
+        Some(version) => Err(format!(
+            "スキーマの移行が完了していません (DB: {}, サーバー: {})",
+            version, SCHEMA_VERSION
+        )),
+        None => Err("スキーマが初期化されていません".to_string()),
+    }
+}
+
+async fn with_timeout(check: impl Future<Output = Result<(), String>>) -> Result<(), String> {
+    timeout(CHECK_TIMEOUT, check).await.unwrap_or_else(|_| {
+        Err(format!(
+            "{}秒以内に応答がありません",
+            CHECK_TIMEOUT.as_secs()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_schema_version() {
+        assert!(check_schema_version(Some(SCHEMA_VERSION)).is_ok());
+        assert!(check_schema_version(Some(SCHEMA_VERSION + 1))
+            .unwrap_err()
+            .contains("新しいスキーマ"));
+        assert!(check_schema_version(Some(SCHEMA_VERSION - 1))
+            .unwrap_err()
+            .contains("移行が完了していません"));
+        assert!(check_schema_version(None).is_err());
+    }
+}
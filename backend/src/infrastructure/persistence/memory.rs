@@ -0,0 +1,228 @@
+//! テスト用のメモリ上のリポジトリ
+//!
+//! SurrealDBの実装と同じく、Todoを並び順に返し、作成・更新・削除のたびに変更シーケンスを進める。
+
+use crate::domain::calendar::{CalendarFeedRepository, FeedToken, TokenKind};
+use crate::domain::todo::{Todo, TodoChanges, TodoError, TodoRepository};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// メモリ上のTodoリポジトリ
+#[derive(Default)]
+pub struct MemoryTodoRepository {
+    state: Mutex<TodoState>,
+    /// 保存に失敗させるタイトル
+    failing_title: Option<String>,
+    find_by_id_calls: AtomicUsize,
+    find_by_ids_calls: AtomicUsize,
+}
+
+#[derive(Default)]
+struct TodoState {
+    /// Todoと、最後に作成・更新したときのシーケンス
+    todos: Vec<(Todo, u64)>,
+    /// 削除したTodoのIDと、削除したときのシーケンス
+    tombstones: Vec<(i32, u64)>,
+    sequence: u64,
+}
+
+impl TodoState {
+    fn next_sequence(&mut self) -> u64 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    fn sorted(todos: impl Iterator<Item = Todo>) -> Vec<Todo> {
+        let mut todos: Vec<Todo> = todos.collect();
+        todos.sort_by(|a, b| a.position().total_cmp(&b.position()));
+        todos
+    }
+}
+
+impl MemoryTodoRepository {
+    /// IDと並び順を持つTodoで始める（1件ごとにシーケンスが進む）
+    pub fn with_todos(todos: impl IntoIterator<Item = Todo>) -> Self {
+        let repository = Self::default();
+        {
+            let mut state = repository.state.lock().unwrap();
+            for todo in todos {
+                let sequence = state.next_sequence();
+                state.todos.push((todo, sequence));
+            }
+        }
+        repository
+    }
+
+    /// タイトルが `title` のTodoの保存を失敗させる
+    pub fn failing_saves_titled(mut self, title: &str) -> Self {
+        self.failing_title = Some(title.to_string());
+        self
+    }
+
+    /// `find_by_id` が呼ばれた回数
+    pub fn find_by_id_calls(&self) -> usize {
+        self.find_by_id_calls.load(Ordering::SeqCst)
+    }
+
+    /// `find_by_ids` が呼ばれた回数
+    pub fn find_by_ids_calls(&self) -> usize {
+        self.find_by_ids_calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl TodoRepository for MemoryTodoRepository {
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        // 読み取りと書き込みの間に他のリクエストが入れるよう、DBと同じく一度中断する
+        tokio::task::yield_now().await;
+        let state = self.state.lock().unwrap();
+        Ok(TodoState::sorted(
+            state.todos.iter().map(|(todo, _)| todo.clone()),
+        ))
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Todo>, TodoError> {
+        self.find_by_id_calls.fetch_add(1, Ordering::SeqCst);
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .todos
+            .iter()
+            .find(|(todo, _)| todo.id() == Some(id))
+            .map(|(todo, _)| todo.clone()))
+    }
+
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Todo>, TodoError> {
+        self.find_by_ids_calls.fetch_add(1, Ordering::SeqCst);
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .todos
+            .iter()
+            .filter(|(todo, _)| todo.id().is_some_and(|id| ids.contains(&id)))
+            .map(|(todo, _)| todo.clone())
+            .collect())
+    }
+
+    async fn save(&self, todo: &Todo) -> Result<Todo, TodoError> {
+        if self.failing_title.as_deref() == Some(todo.title()) {
+            return Err(TodoError::Repository("保存に失敗しました".to_string()));
+        }
+        let mut state = self.state.lock().unwrap();
+        let id = state
+            .todos
+            .iter()
+            .filter_map(|(todo, _)| todo.id())
+            .chain(state.tombstones.iter().map(|(id, _)| *id))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let position = state
+            .todos
+            .iter()
+            .map(|(todo, _)| todo.position())
+            .fold(0.0, f64::max)
+            + 1.0;
+        let saved = Todo::reconstruct(
+            id,
+            todo.title().to_string(),
+            todo.is_completed(),
+            position,
+            todo.created_at(),
+            todo.due(),
+            todo.completed_at(),
+        );
+        let sequence = state.next_sequence();
+        state.todos.push((saved.clone(), sequence));
+        Ok(saved)
+    }
+
+    async fn update(&self, todo: &Todo) -> Result<Todo, TodoError> {
+        let mut state = self.state.lock().unwrap();
+        let sequence = state.next_sequence();
+        let stored = state
+            .todos
+            .iter_mut()
+            .find(|(stored, _)| stored.id() == todo.id())
+            .ok_or(TodoError::NotFound)?;
+        *stored = (todo.clone(), sequence);
+        Ok(todo.clone())
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), TodoError> {
+        let mut state = self.state.lock().unwrap();
+        let index = state
+            .todos
+            .iter()
+            .position(|(todo, _)| todo.id() == Some(id))
+            .ok_or(TodoError::NotFound)?;
+        state.todos.remove(index);
+        let sequence = state.next_sequence();
+        state.tombstones.push((id, sequence));
+        Ok(())
+    }
+
+    async fn find_changes_since(&self, since: Option<u64>) -> Result<TodoChanges, TodoError> {
+        let state = self.state.lock().unwrap();
+        let since = since.map_or(0, |since| since + 1);
+        let changed = state
+            .todos
+            .iter()
+            .filter(|(_, sequence)| *sequence >= since)
+            .map(|(todo, _)| todo.clone());
+        let deleted = state
+            .tombstones
+            .iter()
+            .filter(|(_, sequence)| since > 0 && *sequence >= since)
+            .map(|(id, _)| *id)
+            .collect();
+        Ok(TodoChanges {
+            changed: TodoState::sorted(changed),
+            deleted,
+            sequence: state.sequence,
+        })
+    }
+}
+
+/// メモリ上のフィードのトークンの保存先
+#[derive(Default)]
+pub struct MemoryFeedRepository {
+    tokens: Mutex<HashMap<TokenKind, FeedToken>>,
+}
+
+impl MemoryFeedRepository {
+    /// `kind` のトークンを発行済みにする
+    pub fn with_token(self, kind: TokenKind, token: &str) -> Self {
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(kind, FeedToken::from_stored(token.to_string()));
+        self
+    }
+}
+
+#[async_trait]
+impl CalendarFeedRepository for MemoryFeedRepository {
+    async fn find_token(&self, kind: TokenKind) -> Result<Option<FeedToken>, String> {
+        Ok(self.tokens.lock().unwrap().get(&kind).cloned())
+    }
+
+    async fn find_or_insert_token(
+        &self,
+        kind: TokenKind,
+        candidate: &FeedToken,
+    ) -> Result<FeedToken, String> {
+        Ok(self
+            .tokens
+            .lock()
+            .unwrap()
+            .entry(kind)
+            .or_insert_with(|| candidate.clone())
+            .clone())
+    }
+
+    async fn replace_token(&self, kind: TokenKind, token: &FeedToken) -> Result<(), String> {
+        self.tokens.lock().unwrap().insert(kind, token.clone());
+        Ok(())
+    }
+}
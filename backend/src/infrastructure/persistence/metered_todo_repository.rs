@@ -0,0 +1,78 @@
+use crate::domain::todo::{Todo, TodoChanges, TodoError, TodoRepository};
+use crate::infrastructure::metrics::Metrics;
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// クエリ時間と失敗数を記録するTodoリポジトリのデコレーター
+pub struct MeteredTodoRepository {
+    inner: Arc<dyn TodoRepository>,
+    metrics: Arc<Metrics>,
+}
+
+impl MeteredTodoRepository {
+    pub fn new(inner: Arc<dyn TodoRepository>, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// 処理時間を計測し、失敗した場合はエラー数を加算
+    async fn observe<T>(
+        &self,
+        operation: &str,
+        query: impl Future<Output = Result<T, TodoError>>,
+    ) -> Result<T, TodoError> {
+        let started = Instant::now();
+        let result = query.await;
+        self.metrics
+            .db_query_duration_seconds
+            .with_label_values(&[operation])
+            .observe(started.elapsed().as_secs_f64());
+        // 見つからないなどの結果はクエリの失敗として数えない
+        if let Err(TodoError::Repository(_)) = result {
+            self.metrics
+                .db_query_errors_total
+                .with_label_values(&[operation])
+                .inc();
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl TodoRepository for MeteredTodoRepository {
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        self.observe("find_all", self.inner.find_all()).await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Todo>, TodoError> {
+        self.observe("find_by_id", self.inner.find_by_id(id)).await
+    }
+
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Todo>, TodoError> {
+        self.observe("find_by_ids", self.inner.find_by_ids(ids))
+            .await
+    }
+
+    async fn save(&self, todo: &Todo) -> Result<Todo, TodoError> {
+        self.observe("save", self.inner.save(todo)).await
+    }
+
+    async fn update(&self, todo: &Todo) -> Result<Todo, TodoError> {
+        self.observe("update", self.inner.update(todo)).await
+    }
+
+    async fn update_positions(&self, todos: &[Todo]) -> Result<Vec<Todo>, TodoError> {
+        self.observe("update_positions", self.inner.update_positions(todos))
+            .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), TodoError> {
+        self.observe("delete", self.inner.delete(id)).await
+    }
+
+    async fn find_changes_since(&self, since: Option<u64>) -> Result<TodoChanges, TodoError> {
+        self.observe("find_changes_since", self.inner.find_changes_since(since))
+            .await
+    }
+}
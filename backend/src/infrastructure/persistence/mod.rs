@@ -1,5 +1,17 @@
+pub mod calendar_feed_repository;
+pub mod calendar_resource_repository;
 pub mod database;
+pub mod health;
+#[cfg(test)]
+pub mod memory;
+pub mod metered_todo_repository;
+pub mod notifying_todo_repository;
 pub mod todo_repository;
 
-pub use database::init_db;
+pub use calendar_feed_repository::CalendarFeedRepositoryImpl;
+pub use calendar_resource_repository::CalendarResourceRepositoryImpl;
+pub use database::Database;
+pub use health::check_database;
+pub use metered_todo_repository::MeteredTodoRepository;
+pub use notifying_todo_repository::NotifyingTodoRepository;
 pub use todo_repository::TodoRepositoryImpl;
@@ -0,0 +1,67 @@
+use crate::domain::todo::{Todo, TodoChanges, TodoError, TodoEvent, TodoRepository};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// 変更を保存した後に `TodoEvent` を送るTodoリポジトリのデコレーター
+/// REST・CalDAV・インポートのどの経路の変更も通知する（同じプロセス内の購読者にだけ届く）
+pub struct NotifyingTodoRepository {
+    inner: Arc<dyn TodoRepository>,
+    events: broadcast::Sender<TodoEvent>,
+}
+
+impl NotifyingTodoRepository {
+    pub fn new(inner: Arc<dyn TodoRepository>, events: broadcast::Sender<TodoEvent>) -> Self {
+        Self { inner, events }
+    }
+
+    /// 購読者がいない場合の送信エラーは無視する
+    fn notify(&self, event: TodoEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+#[async_trait]
+impl TodoRepository for NotifyingTodoRepository {
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        self.inner.find_all().await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Todo>, TodoError> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Todo>, TodoError> {
+        self.inner.find_by_ids(ids).await
+    }
+
+    async fn save(&self, todo: &Todo) -> Result<Todo, TodoError> {
+        let saved = self.inner.save(todo).await?;
+        self.notify(TodoEvent::Saved(saved.clone()));
+        Ok(saved)
+    }
+
+    async fn update(&self, todo: &Todo) -> Result<Todo, TodoError> {
+        let updated = self.inner.update(todo).await?;
+        self.notify(TodoEvent::Saved(updated.clone()));
+        Ok(updated)
+    }
+
+    async fn update_positions(&self, todos: &[Todo]) -> Result<Vec<Todo>, TodoError> {
+        let updated = self.inner.update_positions(todos).await?;
+        for todo in &updated {
+            self.notify(TodoEvent::Saved(todo.clone()));
+        }
+        Ok(updated)
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), TodoError> {
+        self.inner.delete(id).await?;
+        self.notify(TodoEvent::Deleted(id));
+        Ok(())
+    }
+
+    async fn find_changes_since(&self, since: Option<u64>) -> Result<TodoChanges, TodoError> {
+        self.inner.find_changes_since(since).await
+    }
+}
@@ -1,8 +1,32 @@
-use crate::domain::todo::{Todo, TodoRepository};
-use crate::infrastructure::persistence::database::DbClient;
+use crate::domain::todo::{Due, Todo, TodoChanges, TodoError, TodoRepository};
+use crate::infrastructure::persistence::database::Database;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
+use std::time::Duration;
 use surrealdb::sql::Thing;
+use tracing::{debug, instrument, trace};
+
+/// 変更シーケンスを1つ進めて新しい値を返すサブクエリ
+/// 書き込みと同じトランザクションの中で使い、書き込みと一緒にコミットする。
+/// 同時に書き込むトランザクションは `change_seq:current` の更新が衝突するため、
+/// シーケンスは採番した順にコミットされる（コミット済みの値より小さい値が後からコミットされることはない）。
+/// 衝突して失敗したトランザクションは `TodoRepositoryImpl::write` でやり直す
+const NEXT_SEQUENCE: &str =
+    "(UPSERT ONLY change_seq:current SET seq = (seq ?? 0) + 1 RETURN VALUE seq)";
+
+/// コミット済みの変更シーケンスを返すサブクエリ
+const CURRENT_SEQUENCE: &str = "((SELECT VALUE seq FROM change_seq:current)[0] ?? 0)";
+
+/// トランザクションが競合した書き込みをやり直す回数の上限
+const WRITE_RETRIES: u32 = 10;
+
+/// SurrealDBが競合したトランザクションのエラーに付ける文言
+const RETRYABLE_ERROR: &str = "This transaction can be retried";
+
+/// 末尾の位置を返すサブクエリ（`position::between(Some(最後), None)` と同じ規則）
+const LAST_POSITION: &str = "math::floor((SELECT VALUE position FROM todos ORDER BY position DESC LIMIT 1)[0] ?? 0.0) + 1.0";
 
 /// SurrealDB用のTodoレコード
 #[derive(Debug, Serialize, Deserialize)]
@@ -10,6 +34,14 @@ struct TodoRecord {
     id: Option<Thing>,
     title: String,
     completed: bool,
+    #[serde(default)]
+    position: f64,
+    #[serde(default)]
+    created_at: Option<surrealdb::sql::Datetime>,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(default)]
+    completed_at: Option<surrealdb::sql::Datetime>,
 }
 
 impl From<TodoRecord> for Todo {
@@ -23,7 +55,7 @@ impl From<TodoRecord> for Todo {
             .map(|thing| {
                 match &thing.id {
                     Id::String(s) => {
-                        println!("🔧 ID変換: SurrealDB ID (String) = '{}'", s);
+                        trace!(surreal_id = %s, "ID変換: SurrealDB ID (String)");
                         // 文字列を整数にパース
                         s.parse::<i32>().unwrap_or_else(|_| {
                             // パースできない場合はハッシュ値を使用（ULID/UUID対応）
@@ -31,16 +63,16 @@ impl From<TodoRecord> for Todo {
                             s.hash(&mut hasher);
                             let hash = hasher.finish();
                             let final_id = ((hash % (i32::MAX as u64 - 1)) + 1) as i32;
-                            println!("🔧 ID変換: ハッシュ後のID = {}", final_id);
+                            trace!(id = final_id, "ID変換: ハッシュ後のID");
                             final_id
                         })
                     }
                     Id::Number(n) => {
-                        println!("🔧 ID変換: SurrealDB ID (Number) = {}", n);
+                        trace!(surreal_id = n, "ID変換: SurrealDB ID (Number)");
                         *n as i32
                     }
                     Id::Array(a) => {
-                        println!("🔧 ID変換: SurrealDB ID (Array) = {:?}", a);
+                        trace!(surreal_id = ?a, "ID変換: SurrealDB ID (Array)");
                         // 配列の場合はハッシュ化
                         let mut hasher = DefaultHasher::new();
                         format!("{:?}", a).hash(&mut hasher);
@@ -48,7 +80,7 @@ impl From<TodoRecord> for Todo {
                         ((hash % (i32::MAX as u64 - 1)) + 1) as i32
                     }
                     Id::Object(o) => {
-                        println!("🔧 ID変換: SurrealDB ID (Object) = {:?}", o);
+                        trace!(surreal_id = ?o, "ID変換: SurrealDB ID (Object)");
                         // オブジェクトの場合はハッシュ化
                         let mut hasher = DefaultHasher::new();
                         format!("{:?}", o).hash(&mut hasher);
@@ -56,30 +88,39 @@ impl From<TodoRecord> for Todo {
                         ((hash % (i32::MAX as u64 - 1)) + 1) as i32
                     }
                     _ => {
-                        println!("🔧 ID変換: SurrealDB ID (Unknown)");
+                        trace!("ID変換: SurrealDB ID (Unknown)");
                         1
                     }
                 }
             })
             .unwrap_or(1); // IDが無い場合のデフォルト値
 
-        println!("🔧 ID変換: 最終的なID = {}", id);
-        Todo::reconstruct(id, record.title, record.completed)
+        trace!(id, "ID変換: 最終的なID");
+        Todo::reconstruct(
+            id,
+            record.title,
+            record.completed,
+            record.position,
+            record.created_at.map(|at| at.0),
+            record.due.as_deref().and_then(Due::parse),
+            record.completed_at.map(|at| at.0),
+        )
     }
 }
 
 use std::collections::HashMap;
+use std::sync::Arc;
 /// SurrealDB実装のTodoリポジトリ
 use tokio::sync::RwLock;
 
 pub struct TodoRepositoryImpl {
-    db: DbClient,
+    db: Arc<Database>,
     // i32 ID -> SurrealDB String ID のマッピング
     id_mapping: RwLock<HashMap<i32, String>>,
 }
 
 impl TodoRepositoryImpl {
-    pub fn new(db: DbClient) -> Self {
+    pub fn new(db: Arc<Database>) -> Self {
         Self {
             db,
             id_mapping: RwLock::new(HashMap::new()),
@@ -98,6 +139,66 @@ impl TodoRepositoryImpl {
         }
     }
 
+    /// SurrealDBの文字列IDからAPIで使うIDを求める（`From<TodoRecord>` と同じ規則）
+    fn domain_id(surreal_id: &str) -> i32 {
+        surreal_id
+            .parse::<i32>()
+            .unwrap_or_else(|_| Self::hash_to_i32(surreal_id))
+    }
+
+    /// APIのIDに対応するSurrealDBのIDを取得
+    /// 再起動直後などでマッピングにない場合は、IDを読み込み直してから探す
+    async fn surreal_id(&self, id: i32) -> Result<Option<String>, TodoError> {
+        if let Some(surreal_id) = self.id_mapping.read().await.get(&id) {
+            return Ok(Some(surreal_id.clone()));
+        }
+        self.load_ids().await?;
+        Ok(self.id_mapping.read().await.get(&id).cloned())
+    }
+
+    /// 書き込みのトランザクションを実行する
+    /// 同時の書き込みと競合して失敗した場合は、少し待ってから最初からやり直す
+    async fn write<T, F, Fut>(&self, transaction: F) -> Result<T, TodoError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, TodoError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match transaction().await {
+                Err(TodoError::Repository(e))
+                    if attempt < WRITE_RETRIES && e.contains(RETRYABLE_ERROR) =>
+                {
+                    attempt += 1;
+                    debug!(attempt, "トランザクションが競合したため再試行");
+                    // 同時に失敗した書き込み同士が再び衝突しないよう、待ち時間をばらつかせる
+                    let jitter = RandomState::new().hash_one(attempt) % 10;
+                    tokio::time::sleep(Duration::from_millis(u64::from(attempt) * 5 + jitter))
+                        .await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// すべてのTodoのIDをマッピングに読み込む
+    async fn load_ids(&self) -> Result<(), TodoError> {
+        let db = self.db.client().await?;
+        let ids: Vec<Thing> = self
+            .db
+            .with_timeout(db.query("SELECT VALUE id FROM todos"))
+            .await?
+            .take(0)
+            .map_err(|e| format!("データベースエラー: {}", e))?;
+        let mut mapping = self.id_mapping.write().await;
+        for thing in &ids {
+            let surreal_id = Self::extract_id_string(thing);
+            mapping.insert(Self::hash_to_i32(&surreal_id), surreal_id);
+        }
+        trace!(count = ids.len(), "マッピング読み込み");
+        Ok(())
+    }
+
     /// 文字列IDをi32にハッシュ化
     fn hash_to_i32(s: &str) -> i32 {
         use std::collections::hash_map::DefaultHasher;
@@ -112,11 +213,14 @@ impl TodoRepositoryImpl {
 
 #[async_trait]
 impl TodoRepository for TodoRepositoryImpl {
-    async fn find_all(&self) -> Result<Vec<Todo>, String> {
+    #[instrument(skip(self), err)]
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        let db = self.db.client().await?;
         let records: Vec<TodoRecord> = self
             .db
-            .select("todos")
-            .await
+            .with_timeout(db.query("SELECT * FROM todos ORDER BY position, created_at"))
+            .await?
+            .take(0)
             .map_err(|e| format!("データベースエラー: {}", e))?;
 
         // マッピングを更新
@@ -130,7 +234,7 @@ impl TodoRepository for TodoRepositoryImpl {
                 if let Some(ref thing) = record.id {
                     let surreal_id = Self::extract_id_string(thing);
                     let hashed_id = Self::hash_to_i32(&surreal_id);
-                    println!("📝 マッピング追加: {} -> {}", hashed_id, surreal_id);
+                    trace!(id = hashed_id, surreal_id = %surreal_id, "マッピング追加");
                     mapping.insert(hashed_id, surreal_id);
                 }
                 record.into()
@@ -140,122 +244,361 @@ impl TodoRepository for TodoRepositoryImpl {
         Ok(todos)
     }
 
-    async fn find_by_id(&self, id: i32) -> Result<Option<Todo>, String> {
-        println!("🔍 find_by_id: IDで検索しています: {}", id);
-
+    #[instrument(skip(self), err)]
+    async fn find_by_id(&self, id: i32) -> Result<Option<Todo>, TodoError> {
         // マッピングから実際のSurrealDB IDを取得
-        let mapping = self.id_mapping.read().await;
-        let surreal_id = mapping.get(&id);
-
-        if let Some(surreal_id) = surreal_id {
-            println!("🔍 find_by_id: マッピング発見: {} -> {}", id, surreal_id);
+        if let Some(surreal_id) = self.surreal_id(id).await? {
+            debug!(surreal_id = %surreal_id, "マッピング発見");
+            let db = self.db.client().await?;
             let record: Option<TodoRecord> = self
                 .db
-                .select(("todos", surreal_id.as_str()))
-                .await
-                .map_err(|e| format!("データベースエラー: {}", e))?;
+                .with_timeout(db.select(("todos", surreal_id.as_str())))
+                .await?;
 
-            println!("🔍 find_by_id: 検索結果: {:?}", record.is_some());
+            debug!(found = record.is_some(), "検索結果");
             Ok(record.map(Into::into))
         } else {
-            println!("🔍 find_by_id: マッピングが見つかりません: {}", id);
+            debug!("マッピングが見つかりません");
             Ok(None)
         }
     }
 
-    async fn save(&self, todo: &Todo) -> Result<Todo, String> {
-        #[derive(Serialize)]
-        struct CreateTodo {
-            title: String,
-            completed: bool,
+    #[instrument(skip(self), err)]
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Todo>, TodoError> {
+        // マッピングにないIDは、読み込み直してもなければ `find_by_id` と同様に存在しないものとして扱う
+        let mapped = {
+            let mapping = self.id_mapping.read().await;
+            ids.iter().all(|id| mapping.contains_key(id))
+        };
+        if !mapped {
+            self.load_ids().await?;
         }
-
-        let new_todo = CreateTodo {
-            title: todo.title().to_string(),
-            completed: todo.is_completed(),
+        let things: Vec<Thing> = {
+            let mapping = self.id_mapping.read().await;
+            ids.iter()
+                .filter_map(|id| mapping.get(id))
+                .map(|surreal_id| Thing::from(("todos", surreal_id.as_str())))
+                .collect()
         };
+        if things.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        // ID自動生成でレコードを作成
-        let created: Option<TodoRecord> = self
+        let db = self.db.client().await?;
+        let records: Vec<TodoRecord> = self
             .db
-            .create("todos")
-            .content(new_todo)
-            .await
+            .with_timeout(
+                db.query("SELECT * FROM todos WHERE id IN $ids ORDER BY position, created_at")
+                    .bind(("ids", things)),
+            )
+            .await?
+            .take(0)
             .map_err(|e| format!("データベースエラー: {}", e))?;
 
+        debug!(requested = ids.len(), found = records.len(), "検索結果");
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    #[instrument(skip(self, todo), err)]
+    async fn save(&self, todo: &Todo) -> Result<Todo, TodoError> {
+        // ID自動生成でレコードを作成
+        let created: Option<TodoRecord> = self
+            .write(|| async {
+                let db = self.db.client().await?;
+                let mut response = self
+                    .db
+                    .with_timeout(
+                        db.query(format!(
+                            "BEGIN TRANSACTION; \
+                             CREATE ONLY todos CONTENT {{ \
+                                 title: $title, completed: $completed, due: $due, \
+                                 completed_at: IF $completed THEN time::now() ELSE NONE END, \
+                                 position: {}, seq: {} \
+                             }}; \
+                             COMMIT TRANSACTION;",
+                            LAST_POSITION, NEXT_SEQUENCE
+                        ))
+                        .bind(("title", todo.title().to_string()))
+                        .bind(("completed", todo.is_completed()))
+                        .bind(("due", todo.due().map(|due| due.to_string()))),
+                    )
+                    .await?;
+                Ok(response
+                    .take(0)
+                    .map_err(|e| format!("データベースエラー: {}", e))?)
+            })
+            .await?;
+
         let created = created.ok_or_else(|| "作成に失敗しました".to_string())?;
-        println!("✅ save: 作成されたレコード: {:?}", created);
+        debug!(record = ?created, "レコード作成");
 
         // マッピングに追加
         if let Some(ref thing) = created.id {
             let surreal_id = Self::extract_id_string(thing);
             let hashed_id = Self::hash_to_i32(&surreal_id);
-            println!("📝 save: マッピング追加: {} -> {}", hashed_id, surreal_id);
+            trace!(id = hashed_id, surreal_id = %surreal_id, "マッピング追加");
             self.id_mapping.write().await.insert(hashed_id, surreal_id);
         }
 
-        let todo_entity = created.into();
-        println!("✅ save: Todoエンティティ: {:?}", todo_entity);
-        Ok(todo_entity)
+        Ok(created.into())
     }
 
-    async fn update(&self, todo: &Todo) -> Result<Todo, String> {
-        let id = todo.id().ok_or("更新対象のTodoにIDが必要です")?;
+    #[instrument(skip(self, todo), fields(id = ?todo.id()), err)]
+    async fn update(&self, todo: &Todo) -> Result<Todo, TodoError> {
+        let id = todo
+            .id()
+            .ok_or_else(|| "更新対象のTodoにIDが必要です".to_string())?;
 
-        #[derive(Serialize)]
-        struct UpdateTodo {
-            title: String,
-            completed: bool,
-        }
+        // マッピングから実際のSurrealDB IDを取得
+        let surreal_id = self.surreal_id(id).await?.ok_or(TodoError::NotFound)?;
+        debug!(surreal_id = %surreal_id, "マッピング使用");
 
-        let update_data = UpdateTodo {
-            title: todo.title().to_string(),
-            completed: todo.is_completed(),
-        };
+        let updated: Option<TodoRecord> = self
+            .write(|| async {
+                let db = self.db.client().await?;
+                let mut response = self
+                    .db
+                    .with_timeout(
+                        db.query(format!(
+                            "BEGIN TRANSACTION; \
+                             UPDATE type::thing('todos', $id) \
+                             SET title = $title, completed = $completed, due = $due, position = $position, \
+                                 completed_at = IF $completed THEN completed_at ?? time::now() ELSE NONE END, \
+                                 seq = {}; \
+                             COMMIT TRANSACTION;",
+                            NEXT_SEQUENCE
+                        ))
+                        .bind(("id", surreal_id.clone()))
+                        .bind(("title", todo.title().to_string()))
+                        .bind(("completed", todo.is_completed()))
+                        .bind(("due", todo.due().map(|due| due.to_string())))
+                        .bind(("position", todo.position())),
+                    )
+                    .await?;
+                Ok(response
+                    .take(0)
+                    .map_err(|e| format!("データベースエラー: {}", e))?)
+            })
+            .await?;
 
-        // マッピングから実際のSurrealDB IDを取得
-        let mapping = self.id_mapping.read().await;
-        let surreal_id = mapping
-            .get(&id)
-            .ok_or_else(|| format!("ID {} のマッピングが見つかりません", id))?
-            .clone();
-        drop(mapping); // read lockを早めに解放
+        // 他のリクエストで削除されていた場合は何も更新されない
+        updated.map(Into::into).ok_or(TodoError::NotFound)
+    }
 
-        println!("🔄 update: マッピング使用: {} -> {}", id, surreal_id);
+    #[instrument(skip(self, todos), fields(count = todos.len()), err)]
+    async fn update_positions(&self, todos: &[Todo]) -> Result<Vec<Todo>, TodoError> {
+        if todos.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        let updated: Option<TodoRecord> = self
-            .db
-            .update(("todos", surreal_id.as_str()))
-            .content(update_data)
-            .await
-            .map_err(|e| format!("データベースエラー: {}", e))?;
+        // マッピングから実際のSurrealDB IDを取得
+        let mut surreal_ids = Vec::with_capacity(todos.len());
+        for todo in todos {
+            let id = todo
+                .id()
+                .ok_or_else(|| "更新対象のTodoにIDが必要です".to_string())?;
+            surreal_ids.push(self.surreal_id(id).await?.ok_or(TodoError::NotFound)?);
+        }
 
-        updated
-            .map(Into::into)
-            .ok_or_else(|| "更新に失敗しました".to_string())
+        // 途中で失敗した場合にどれも更新しないよう、1つのトランザクションで更新する
+        let statements: String = (0..todos.len())
+            .map(|i| {
+                format!(
+                    "UPDATE type::thing('todos', $id{i}) SET position = $position{i}, seq = {};",
+                    NEXT_SEQUENCE
+                )
+            })
+            .collect();
+        self.write(|| async {
+            let db = self.db.client().await?;
+            let mut query = db.query(format!(
+                "BEGIN TRANSACTION; {} COMMIT TRANSACTION;",
+                statements
+            ));
+            for (i, (todo, surreal_id)) in todos.iter().zip(&surreal_ids).enumerate() {
+                query = query
+                    .bind((format!("id{}", i), surreal_id.clone()))
+                    .bind((format!("position{}", i), todo.position()));
+            }
+            let mut response = self.db.with_timeout(query).await?;
+
+            (0..todos.len())
+                .map(|i| {
+                    let updated: Option<TodoRecord> = response
+                        .take(i)
+                        .map_err(|e| format!("データベースエラー: {}", e))?;
+                    updated.map(Into::into).ok_or(TodoError::NotFound)
+                })
+                .collect()
+        })
+        .await
     }
 
-    async fn delete(&self, id: i32) -> Result<(), String> {
+    #[instrument(skip(self), err)]
+    async fn delete(&self, id: i32) -> Result<(), TodoError> {
         // マッピングから実際のSurrealDB IDを取得
-        let mapping = self.id_mapping.read().await;
-        let surreal_id = mapping
-            .get(&id)
-            .ok_or_else(|| format!("ID {} のマッピングが見つかりません", id))?
-            .clone();
-        drop(mapping); // read lockを解放
+        let surreal_id = self.surreal_id(id).await?.ok_or(TodoError::NotFound)?;
+        debug!(surreal_id = %surreal_id, "マッピング使用");
+
+        // 削除と墓標の作成を1つのトランザクションで行う（実際に削除した場合のみ墓標を残す）
+        let deleted: Option<TodoRecord> = self
+            .write(|| async {
+                let db = self.db.client().await?;
+                let mut response = self
+                    .db
+                    .with_timeout(
+                        db.query(format!(
+                            "BEGIN TRANSACTION; \
+                             LET $deleted = DELETE type::thing('todos', $id) RETURN BEFORE; \
+                             IF $deleted {{ \
+                                 CREATE todo_tombstones CONTENT {{ todo_id: $id, seq: {} }} \
+                             }}; \
+                             $deleted; \
+                             COMMIT TRANSACTION;",
+                            NEXT_SEQUENCE
+                        ))
+                        .bind(("id", surreal_id.clone())),
+                    )
+                    .await?;
+                Ok(response
+                    .take(2)
+                    .map_err(|e| format!("データベースエラー: {}", e))?)
+            })
+            .await?;
+
+        // マッピングから削除
+        self.id_mapping.write().await.remove(&id);
+        trace!("マッピング削除");
+
+        // 他のリクエストで削除されていた場合は見つからない
+        deleted.map(|_| ()).ok_or(TodoError::NotFound)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn find_changes_since(&self, since: Option<u64>) -> Result<TodoChanges, TodoError> {
+        let db = self.db.client().await?;
 
-        println!("🗑️ delete: マッピング使用: {} -> {}", id, surreal_id);
+        // 再起動直後はマッピングが空なので、差分に含まれないTodoも更新・削除できるようIDだけ読み込む
+        if since.is_some() && self.id_mapping.read().await.is_empty() {
+            self.load_ids().await?;
+        }
 
-        let _: Option<TodoRecord> = self
+        // シーケンスと変更を1つのトランザクション（同じスナップショット）で読み、
+        // そのシーケンス以前の変更だけを返す。コミット前の書き込みのシーケンスは
+        // 読んだ値より大きくなるため、次回の同期で取得される
+        let mut response = self
             .db
-            .delete(("todos", surreal_id.as_str()))
-            .await
+            .with_timeout(
+                db.query(format!(
+                    "BEGIN TRANSACTION;
+                     SELECT VALUE seq FROM change_seq:current;
+                     SELECT * FROM todos WHERE seq > $since AND seq <= {sequence} ORDER BY position, created_at;
+                     SELECT VALUE todo_id FROM todo_tombstones WHERE seq > $since AND seq <= {sequence};
+                     COMMIT TRANSACTION;",
+                    sequence = CURRENT_SEQUENCE
+                ))
+                .bind(("since", since.map_or(-1, |since| since as i64))),
+            )
+            .await?;
+        let sequence: Option<i64> = response
+            .take(0)
+            .map_err(|e| format!("データベースエラー: {}", e))?;
+        let sequence = sequence.unwrap_or(0);
+        let records: Vec<TodoRecord> = response
+            .take(1)
             .map_err(|e| format!("データベースエラー: {}", e))?;
+        let deleted: Vec<String> = if since.is_some() {
+            response
+                .take(2)
+                .map_err(|e| format!("データベースエラー: {}", e))?
+        } else {
+            Vec::new()
+        };
 
-        // マッピングから削除
-        self.id_mapping.write().await.remove(&id);
-        println!("🗑️ delete: マッピング削除: {}", id);
+        // 変更されたTodoを後から更新・削除できるようマッピングに追加
+        let mut mapping = self.id_mapping.write().await;
+        let changed = records
+            .into_iter()
+            .map(|record| {
+                if let Some(ref thing) = record.id {
+                    let surreal_id = Self::extract_id_string(thing);
+                    mapping.insert(Self::hash_to_i32(&surreal_id), surreal_id);
+                }
+                record.into()
+            })
+            .collect();
+        let deleted = deleted
+            .iter()
+            .map(|surreal_id| {
+                mapping.remove(&Self::hash_to_i32(surreal_id));
+                Self::domain_id(surreal_id)
+            })
+            .collect();
 
-        Ok(())
+        debug!(sequence, "変更を取得");
+        Ok(TodoChanges {
+            changed,
+            deleted,
+            sequence: sequence as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 存在しないTodoの更新・削除は、マッピングがない場合もレコードがない場合も `NotFound` になる
+    #[actix_web::test]
+    async fn test_missing_todo_is_not_found() {
+        let db = Database::in_memory().await;
+        let repository = TodoRepositoryImpl::new(db.clone());
+        let saved = repository
+            .save(&Todo::new("牛乳を買う".to_string()).unwrap())
+            .await
+            .unwrap();
+        let id = saved.id().unwrap();
+
+        // 再起動直後（マッピングが空）でも既存のTodoは取得・更新できる
+        let restarted = TodoRepositoryImpl::new(db.clone());
+        assert_eq!(restarted.find_by_id(id).await.unwrap(), Some(saved.clone()));
+        assert!(restarted.update(&saved).await.is_ok());
+
+        repository.delete(id).await.unwrap();
+        assert_eq!(repository.delete(id).await, Err(TodoError::NotFound));
+        assert_eq!(repository.delete(id + 1).await, Err(TodoError::NotFound));
+        assert_eq!(repository.update(&saved).await, Err(TodoError::NotFound));
+
+        // 他のインスタンスで削除済みのレコードもマッピングに残っていても見つからない
+        assert_eq!(restarted.delete(id).await, Err(TodoError::NotFound));
+        assert_eq!(restarted.update(&saved).await, Err(TodoError::NotFound));
+        assert_eq!(
+            restarted.update_positions(&[saved]).await,
+            Err(TodoError::NotFound)
+        );
+
+        let changes = repository.find_changes_since(Some(0)).await.unwrap();
+        assert_eq!(changes.deleted, [id]);
+    }
+
+    /// 同時の書き込みは変更シーケンスの行で競合しても、やり直してすべて成功する
+    #[actix_web::test]
+    async fn test_concurrent_writes_are_serialised() {
+        let repository = Arc::new(TodoRepositoryImpl::new(Database::in_memory().await));
+        let saves = (0..50).map(|i| {
+            let repository = repository.clone();
+            tokio::spawn(async move {
+                repository
+                    .save(&Todo::new(format!("todo {}", i)).unwrap())
+                    .await
+            })
+        });
+        for saved in futures_util::future::join_all(saves).await {
+            saved.unwrap().unwrap();
+        }
+
+        let changes = repository.find_changes_since(None).await.unwrap();
+        assert_eq!(changes.changed.len(), 50);
+        assert_eq!(changes.sequence, 50);
     }
 }
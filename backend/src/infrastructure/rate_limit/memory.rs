@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{RateLimitDecision, RateLimitStore};
+use crate::config::BucketConfig;
+
+/// 保持するバケットの上限
+/// 超えた場合は最も長く使われていないバケットを捨てる（そのクライアントは満タンのバケットからやり直す）
+const MAX_BUCKETS: usize = 100_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// プロセス内のメモリにバケットを保持する実装
+pub struct MemoryRateLimitStore {
+    buckets: Mutex<LruCache<String, Bucket>>,
+}
+
+impl MemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self::with_max_buckets(MAX_BUCKETS)
+    }
+
+    /// 保持するバケットの上限を指定して作成
+    pub fn with_max_buckets(max_buckets: usize) -> Self {
+        let max_buckets = NonZeroUsize::new(max_buckets).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            buckets: Mutex::new(LruCache::new(max_buckets)),
+        }
+    }
+}
+
+impl Default for MemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn acquire(&self, key: &str, config: &BucketConfig, now: Instant) -> RateLimitDecision {
+        let capacity = f64::from(config.capacity);
+        let mut buckets = self
+            .buckets
+            .lock()
+            .expect("レート制限のロックが壊れています");
+
+        let bucket = buckets.get_or_insert_mut(key.to_string(), || Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        // 経過時間分のトークンを補充
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * config.refill_per_sec).min(capacity);
+        bucket.updated_at = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        let refill_secs =
+            |tokens: f64| Duration::from_secs_f64(tokens.max(0.0) / config.refill_per_sec);
+        RateLimitDecision {
+            allowed,
+            limit: config.capacity,
+            remaining: bucket.tokens.floor() as u32,
+            reset_after: refill_secs(capacity - bucket.tokens),
+            retry_after: (!allowed).then(|| refill_secs(1.0 - bucket.tokens)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket() -> BucketConfig {
+        BucketConfig {
+            capacity: 2,
+            refill_per_sec: 1.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_when_empty() {
+        let store = MemoryRateLimitStore::new();
+        let now = Instant::now();
+
+        assert_eq!(store.acquire("a", &bucket(), now).await.remaining, 1);
+        assert_eq!(store.acquire("a", &bucket(), now).await.remaining, 0);
+
+        let decision = store.acquire("a", &bucket(), now).await;
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(1)));
+
+        // 別のキーは影響を受けない
+        assert!(store.acquire("b", &bucket(), now).await.allowed);
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used_bucket() {
+        let store = MemoryRateLimitStore::with_max_buckets(2);
+        let now = Instant::now();
+        store.acquire("a", &bucket(), now).await;
+        store.acquire("b", &bucket(), now).await;
+        store.acquire("a", &bucket(), now).await;
+
+        // 上限を超えると最も長く使われていない b を捨て、a は残る
+        store.acquire("c", &bucket(), now).await;
+        assert_eq!(store.buckets.lock().unwrap().len(), 2);
+        assert_eq!(store.acquire("a", &bucket(), now).await.remaining, 0);
+        assert!(!store.buckets.lock().unwrap().contains(&"b".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_refills_over_time() {
+        let store = MemoryRateLimitStore::new();
+        let now = Instant::now();
+        for _ in 0..2 {
+            store.acquire("a", &bucket(), now).await;
+        }
+
+        let later = now + Duration::from_millis(1500);
+        let decision = store.acquire("a", &bucket(), later).await;
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+    }
+}
@@ -0,0 +1,130 @@
+pub mod memory;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::config::{BucketConfig, RateLimitConfig};
+
+pub use memory::MemoryRateLimitStore;
+
+/// レート制限の判定結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    /// バケットの容量
+    pub limit: u32,
+    /// 残りのリクエスト数
+    pub remaining: u32,
+    /// バケットが満タンに戻るまでの時間
+    pub reset_after: Duration,
+    /// 拒否された場合、次のリクエストが可能になるまでの時間
+    pub retry_after: Option<Duration>,
+}
+
+/// トークンバケットの保存先
+/// 複数インスタンスで共有する場合は Redis などの実装に差し替える
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// `key` のバケットからトークンを1つ取り出す
+    async fn acquire(&self, key: &str, bucket: &BucketConfig, now: Instant) -> RateLimitDecision;
+}
+
+/// リクエストの種類（読み取りと書き込みで別のバケットを使う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    Read,
+    Write,
+}
+
+impl RequestKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            RequestKind::Read => "read",
+            RequestKind::Write => "write",
+        }
+    }
+}
+
+/// クライアントを識別する単位（単位ごとに別の上限を持つ）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientScope {
+    Ip,
+    Token,
+    User,
+}
+
+impl ClientScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            ClientScope::Ip => "ip",
+            ClientScope::Token => "token",
+            ClientScope::User => "user",
+        }
+    }
+}
+
+/// 設定と保存先をまとめたレート制限
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    store: Arc<dyn RateLimitStore>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig, store: Arc<dyn RateLimitStore>) -> Self {
+        Self { config, store }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// リクエストを1件、識別できたクライアント `clients` のすべてのバケットから消費する
+    /// 上限を超えたバケットがあればそこで拒否し、最も厳しい判定を返す
+    pub async fn check(
+        &self,
+        clients: &[(ClientScope, String)],
+        kind: RequestKind,
+    ) -> RateLimitDecision {
+        let now = Instant::now();
+        let mut strictest: Option<RateLimitDecision> = None;
+        for (scope, client) in clients {
+            let key = format!("{}:{}:{}", kind.as_str(), scope.as_str(), client);
+            let decision = self
+                .store
+                .acquire(&key, self.bucket(*scope, kind), now)
+                .await;
+            let denied = !decision.allowed;
+            strictest = Some(match strictest {
+                Some(current) if stricter(&current, &decision) => current,
+                _ => decision,
+            });
+            if denied {
+                break;
+            }
+        }
+        strictest.expect("レート制限の対象のクライアントがありません")
+    }
+
+    fn bucket(&self, scope: ClientScope, kind: RequestKind) -> &BucketConfig {
+        let (read, write) = match scope {
+            ClientScope::Ip => (&self.config.read, &self.config.write),
+            ClientScope::Token => (&self.config.token.read, &self.config.token.write),
+            ClientScope::User => (&self.config.user.read, &self.config.user.write),
+        };
+        match kind {
+            RequestKind::Read => read,
+            RequestKind::Write => write,
+        }
+    }
+}
+
+/// `a` が `b` より厳しい（拒否されている、または残りが少ない）か
+fn stricter(a: &RateLimitDecision, b: &RateLimitDecision) -> bool {
+    match (a.allowed, b.allowed) {
+        (false, true) => true,
+        (true, false) => false,
+        (false, false) => a.retry_after >= b.retry_after,
+        (true, true) => (a.remaining, b.reset_after) <= (b.remaining, a.reset_after),
+    }
+}
@@ -1,55 +1,143 @@
 mod application;
+mod config;
 mod domain;
 mod infrastructure;
+mod logging;
 mod presentation;
 
-use actix_cors::Cors;
+use actix_web::middleware::from_fn;
 use actix_web::{web, App, HttpServer};
+use clap::Parser;
 use std::sync::Arc;
 
+use application::calendar::{CalDavService, CalendarService};
 use application::todo::TodoService;
-use infrastructure::{init_db, TodoRepositoryImpl};
+use config::{Cli, Config, ServerConfig};
+use infrastructure::{
+    CalendarFeedRepositoryImpl, CalendarResourceRepositoryImpl, Database, MemoryRateLimitStore,
+    MeteredTodoRepository, Metrics, NotifyingTodoRepository, RateLimiter, TodoRepositoryImpl,
+};
+use presentation::api::Versioning;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // 環境変数の読み込み
     dotenvy::dotenv().ok();
 
-    println!("🚀 サーバーを起動中...");
+    // 設定の読み込み（CLI > 環境変数 > 設定ファイル > デフォルト）
+    let cli = Cli::parse();
+    let config = match Config::load(&cli) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(2);
+        }
+    };
 
-    // データベース初期化
-    let pool = init_db().await;
-    println!("✅ データベース接続完了");
+    // APIバージョンの設定（未知のバージョンを指定していないか確認する）
+    let versioning = match Versioning::from_config(&config.api) {
+        Ok(versioning) => versioning,
+        Err(e) => {
+            eprintln!("❌ 設定が不正です: {}", e);
+            std::process::exit(2);
+        }
+    };
 
-    // リポジトリ層の初期化
-    let todo_repository = Arc::new(TodoRepositoryImpl::new(pool));
+    if cli.print_config {
+        print!("{}", config.to_redacted_toml());
+        return Ok(());
+    }
+
+    logging::init(&config.log);
+    tracing::info!("サーバーを起動中");
+
+    // データベース接続（接続できるまでバックグラウンドで再試行し、その間は /readyz が503を返す）
+    let database = Database::new(config.database.clone());
+    database.spawn_connector();
+
+    // メトリクスの初期化
+    let metrics = Arc::new(Metrics::new());
+
+    // リポジトリ層の初期化（クエリ時間をメトリクスに記録し、変更を通知する）
+    let todo_events = TodoService::event_channel();
+    let todo_repository = Arc::new(NotifyingTodoRepository::new(
+        Arc::new(MeteredTodoRepository::new(
+            Arc::new(TodoRepositoryImpl::new(database.clone())),
+            metrics.clone(),
+        )),
+        todo_events.clone(),
+    ));
 
     // アプリケーション層（サービス）の初期化
-    let todo_service = Arc::new(TodoService::new(todo_repository));
+    let todo_service = Arc::new(TodoService::new(todo_repository.clone()).with_events(todo_events));
+    let calendar_feeds = Arc::new(CalendarFeedRepositoryImpl::new(database.clone()));
+    let calendar_service = Arc::new(CalendarService::new(
+        calendar_feeds.clone(),
+        todo_repository.clone(),
+    ));
+    let caldav_service = Arc::new(CalDavService::new(
+        calendar_feeds,
+        Arc::new(CalendarResourceRepositoryImpl::new(database.clone())),
+        todo_repository,
+    ));
+    let graphql_schema = presentation::graphql::schema(todo_service.clone(), &config.graphql);
 
-    // ポート番号を環境変数から取得、デフォルトは8080
-    let port = std::env::var("PORT")
-        .ok()
-        .and_then(|p| p.parse::<u16>().ok())
-        .unwrap_or(8080);
+    // レート制限（全ワーカーで同じバケットを共有する）
+    let rate_limiter = web::Data::new(RateLimiter::new(
+        config.rate_limit.clone(),
+        Arc::new(MemoryRateLimitStore::new()),
+    ));
+    let limits = web::Data::new(config.limits.clone());
 
-    println!("🌐 サーバーを http://0.0.0.0:{} で起動します", port);
+    let ServerConfig {
+        host,
+        port,
+        workers,
+        shutdown_timeout_secs,
+        ..
+    } = config.server.clone();
 
-    // HTTPサーバーの起動
-    HttpServer::new(move || {
-        // CORS設定
-        let cors = Cors::default()
-            .allow_any_origin()
-            .allow_any_method()
-            .allow_any_header()
-            .max_age(3600);
+    tracing::info!("サーバーを http://{}:{} で起動します", host, port);
 
+    // HTTPサーバーの起動
+    let app_database = database.clone();
+    let mut server = HttpServer::new(move || {
         App::new()
-            .wrap(cors)
+            .wrap(from_fn(presentation::middleware::csrf))
+            .wrap(from_fn(presentation::middleware::rate_limit))
+            .wrap(presentation::middleware::security_headers(&config.security))
+            .wrap(presentation::middleware::cors(&config.cors))
+            .wrap(from_fn(presentation::middleware::http_metrics))
+            .wrap(from_fn(presentation::middleware::request_tracing))
+            .app_data(
+                web::JsonConfig::default()
+                    .limit(config.limits.json_payload_bytes)
+                    .error_handler(presentation::api::error::json_error_handler),
+            )
             .app_data(web::Data::new(todo_service.clone()))
-            .configure(presentation::config)
+            .app_data(web::Data::new(calendar_service.clone()))
+            .app_data(web::Data::new(caldav_service.clone()))
+            .app_data(limits.clone())
+            .app_data(web::Data::new(metrics.clone()))
+            .app_data(rate_limiter.clone())
+            .app_data(web::Data::new(config.security.csrf.clone()))
+            .app_data(web::Data::new(app_database.clone()))
+            .app_data(web::Data::new(graphql_schema.clone()))
+            .app_data(web::Data::new(config.graphql.clone()))
+            .app_data(web::Data::new(config.server.clone()))
+            .configure(|cfg| presentation::config(cfg, &versioning))
     })
-    .bind(("0.0.0.0", port))?
-    .run()
-    .await
+    .max_connections(config.limits.max_connections)
+    // SIGTERM/SIGINT受信時は新規接続の受け付けを止め、処理中のリクエストの完了を待つ
+    .shutdown_timeout(shutdown_timeout_secs);
+
+    if let Some(workers) = workers {
+        server = server.workers(workers);
+    }
+
+    let result = server.bind((host.as_str(), port))?.run().await;
+
+    database.shutdown();
+    tracing::info!("サーバーを停止しました");
+    result
 }
@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use actix_web::http::header::{self, CacheControl, CacheDirective};
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+
+use super::error::{self, ErrorResponse};
+use crate::application::calendar::{CalendarService, FeedOptions};
+use crate::config::ServerConfig;
+use crate::domain::calendar::{CalendarError, FeedToken, TokenKind};
+use crate::presentation::caldav;
+use crate::presentation::i18n::Locale;
+use api_types::CalendarFeedDto;
+
+/// フィードのクエリ
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    /// trueなら未完了のTodoの期限を予定（VEVENT）としても含める
+    #[serde(default)]
+    pub events: bool,
+}
+
+/// 購読URL（設定した公開URLから組み立てる）とCalDAVの接続先
+/// Host ヘッダーはクライアントが自由に指定できるため、秘密のURLの組み立てには使わない
+fn feed_dto(
+    server: &ServerConfig,
+    token: &FeedToken,
+    caldav_password: &FeedToken,
+) -> CalendarFeedDto {
+    let path = format!("/ical/{}.ics", token.as_str());
+    CalendarFeedDto {
+        url: format!("{}{}", server.public_url.trim_end_matches('/'), path),
+        path,
+        caldav_path: caldav::ROOT_PATH.to_string(),
+        caldav_password: caldav_password.as_str().to_string(),
+    }
+}
+
+/// 購読URLとCalDAVのパスワード（どちらも初めて取得したときに発行する）
+async fn feed(
+    service: &CalendarService,
+    server: &ServerConfig,
+) -> std::result::Result<CalendarFeedDto, CalendarError> {
+    let token = service.token(TokenKind::Feed).await?;
+    let caldav_password = service.token(TokenKind::CalDav).await?;
+    Ok(feed_dto(server, &token, &caldav_password))
+}
+
+/// `kind` のトークンを再発行し、新しい購読URLとCalDAVのパスワードを返す
+async fn rotate(
+    service: &CalendarService,
+    server: &ServerConfig,
+    kind: TokenKind,
+) -> std::result::Result<CalendarFeedDto, CalendarError> {
+    service.rotate_token(kind).await?;
+    feed(service, server).await
+}
+
+/// カレンダーフィードの購読URLを取得（初めて取得したときに発行する）
+/// フィード（`GET /ical/{token}.ics`）は期限のあるTodoを VTODO として返し、`?events=true` で未完了の期限を VEVENT としても返す。
+/// CalDAV（`/dav/`）にはBasic認証で、フィードとは別に発行した `caldav_password` で接続する
+#[utoipa::path(
+    get,
+    path = "/api/v1/calendar",
+    tag = "calendar",
+    responses(
+        (status = 200, description = "購読URL", body = CalendarFeedDto),
+        (status = 500, description = "データベースエラー", body = ErrorResponse),
+    )
+)]
+pub async fn get_feed_url(
+    service: web::Data<Arc<CalendarService>>,
+    server: web::Data<ServerConfig>,
+    locale: Locale,
+) -> Result<HttpResponse> {
+    match feed(&service, &server).await {
+        Ok(dto) => Ok(HttpResponse::Ok().json(dto)),
+        Err(e) => Ok(error::calendar_error(locale, e)),
+    }
+}
+
+/// 購読URLを再発行（古いURLは使えなくなる。CalDAVのパスワードは変わらない）
+#[utoipa::path(
+    post,
+    path = "/api/v1/calendar/rotate",
+    tag = "calendar",
+    responses(
+        (status = 200, description = "新しい購読URL", body = CalendarFeedDto),
+        (status = 500, description = "データベースエラー", body = ErrorResponse),
+    )
+)]
+pub async fn rotate_feed_url(
+    service: web::Data<Arc<CalendarService>>,
+    server: web::Data<ServerConfig>,
+    locale: Locale,
+) -> Result<HttpResponse> {
+    match rotate(&service, &server, TokenKind::Feed).await {
+        Ok(dto) => {
+            tracing::info!("カレンダーフィードのURLを再発行しました");
+            Ok(HttpResponse::Ok().json(dto))
+        }
+        Err(e) => Ok(error::calendar_error(locale, e)),
+    }
+}
+
+/// CalDAVのパスワードを再発行（古いパスワードは使えなくなる。購読URLは変わらない）
+#[utoipa::path(
+    post,
+    path = "/api/v1/calendar/caldav/rotate",
+    tag = "calendar",
+    responses(
+        (status = 200, description = "新しいCalDAVのパスワード", body = CalendarFeedDto),
+        (status = 500, description = "データベースエラー", body = ErrorResponse),
+    )
+)]
+pub async fn rotate_caldav_password(
+    service: web::Data<Arc<CalendarService>>,
+    server: web::Data<ServerConfig>,
+    locale: Locale,
+) -> Result<HttpResponse> {
+    match rotate(&service, &server, TokenKind::CalDav).await {
+        Ok(dto) => {
+            tracing::info!("CalDAVのパスワードを再発行しました");
+            Ok(HttpResponse::Ok().json(dto))
+        }
+        Err(e) => Ok(error::calendar_error(locale, e)),
+    }
+}
+
+/// カレンダーフィード（iCalendar）
+pub async fn get_feed(
+    service: web::Data<Arc<CalendarService>>,
+    token: web::Path<String>,
+    query: web::Query<FeedQuery>,
+    locale: Locale,
+) -> Result<HttpResponse> {
+    let options = FeedOptions {
+        events: query.events,
+    };
+    match service.feed(&token, options).await {
+        Ok(body) => Ok(HttpResponse::Ok()
+            .content_type("text/calendar; charset=utf-8")
+            // URLが秘密のため、共有キャッシュに残さない
+            .insert_header(CacheControl(vec![
+                CacheDirective::Private,
+                CacheDirective::NoStore,
+            ]))
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                "inline; filename=\"todos.ics\"",
+            ))
+            .body(body)),
+        Err(e) => Ok(error::calendar_error(locale, e)),
+    }
+}
+
+/// `/api` スコープ内のルーティング
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/calendar", web::get().to(get_feed_url))
+        .route("/calendar/rotate", web::post().to(rotate_feed_url))
+        .route(
+            "/calendar/caldav/rotate",
+            web::post().to(rotate_caldav_password),
+        );
+}
+
+/// フィードのルーティング（カレンダーアプリが購読するため `/api` の外に置く）
+pub fn feed_config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/ical/{token}.ics", web::get().to(get_feed));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::persistence::memory::{MemoryFeedRepository, MemoryTodoRepository};
+    use actix_web::{test, App};
+
+    /// 購読URLはクライアントが送った Host ヘッダーではなく、設定した公開URLから組み立てる
+    #[actix_web::test]
+    async fn test_feed_url_ignores_host_header() {
+        let service = Arc::new(CalendarService::new(
+            Arc::new(MemoryFeedRepository::default().with_token(TokenKind::Feed, "secret")),
+            Arc::new(MemoryTodoRepository::default()),
+        ));
+        let server = ServerConfig {
+            public_url: "https://todo.example.com/".to_string(),
+            ..ServerConfig::default()
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(service))
+                .app_data(web::Data::new(server))
+                .service(web::scope("/api").configure(config)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/calendar")
+            .insert_header((header::HOST, "attacker.example"))
+            .insert_header(("X-Forwarded-Host", "attacker.example"))
+            .to_request();
+        let dto: CalendarFeedDto = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(dto.url, "https://todo.example.com/ical/secret.ics");
+    }
+}
@@ -0,0 +1,99 @@
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::http::{header, StatusCode};
+use actix_web::{HttpRequest, HttpResponse};
+use fluent_bundle::FluentArgs;
+
+use crate::domain::calendar::CalendarError;
+use crate::domain::todo::TodoError;
+use crate::presentation::i18n::Locale;
+
+// エラーレスポンスの型とエラーコードはフロントエンドと共有する
+pub use api_types::{ErrorCode, ErrorResponse};
+
+/// エラーコードに対応するHTTPステータス
+fn status(code: ErrorCode) -> StatusCode {
+    match code {
+        ErrorCode::TitleEmpty
+        | ErrorCode::InvalidMove
+        | ErrorCode::InvalidDue
+        | ErrorCode::InvalidSyncToken
+        | ErrorCode::UnsupportedFormat
+        | ErrorCode::InvalidImportFile
+        | ErrorCode::InvalidImportRow
+        | ErrorCode::InvalidJson
+        | ErrorCode::BatchTooLarge => StatusCode::BAD_REQUEST,
+        ErrorCode::TodoNotFound
+        | ErrorCode::MoveTargetNotFound
+        | ErrorCode::CalendarFeedNotFound => StatusCode::NOT_FOUND,
+        ErrorCode::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+        ErrorCode::UnsupportedApiVersion => StatusCode::NOT_ACCEPTABLE,
+        ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+        ErrorCode::CsrfTokenMismatch => StatusCode::FORBIDDEN,
+        ErrorCode::InternalError | ErrorCode::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// `locale` の言語のメッセージを付けたエラーレスポンスの本文
+pub fn localized(locale: Locale, code: ErrorCode, args: Option<&FluentArgs>) -> ErrorResponse {
+    ErrorResponse::new(code, locale.message(&code.message_id(), args))
+}
+
+/// エラーコードに対応するステータスのJSONレスポンスに変換
+pub fn respond(locale: Locale, body: ErrorResponse) -> HttpResponse {
+    HttpResponse::build(status(body.error))
+        .insert_header((header::CONTENT_LANGUAGE, locale.as_str()))
+        .json(body)
+}
+
+/// 引数のないメッセージのエラーレスポンス
+pub fn error(locale: Locale, code: ErrorCode) -> HttpResponse {
+    respond(locale, localized(locale, code, None))
+}
+
+/// ユースケースのエラーをレスポンスに変換
+pub fn todo_error(locale: Locale, err: TodoError) -> HttpResponse {
+    error(locale, todo_error_code(err))
+}
+
+/// ユースケースのエラーに対応するエラーコード
+/// 永続化の失敗の詳細はログにだけ出し、クライアントには内部エラーとして返す
+pub fn todo_error_code(err: TodoError) -> ErrorCode {
+    match err {
+        TodoError::EmptyTitle => ErrorCode::TitleEmpty,
+        TodoError::NotFound => ErrorCode::TodoNotFound,
+        TodoError::MoveTargetNotFound => ErrorCode::MoveTargetNotFound,
+        TodoError::InvalidMove => ErrorCode::InvalidMove,
+        TodoError::InvalidDue => ErrorCode::InvalidDue,
+        TodoError::Repository(e) => {
+            tracing::error!(error = %e, "リクエストの処理に失敗しました");
+            ErrorCode::InternalError
+        }
+    }
+}
+
+/// カレンダーフィードのエラーをレスポンスに変換
+pub fn calendar_error(locale: Locale, err: CalendarError) -> HttpResponse {
+    match err {
+        CalendarError::FeedNotFound => error(locale, ErrorCode::CalendarFeedNotFound),
+        CalendarError::Repository(e) => todo_error(locale, TodoError::Repository(e)),
+    }
+}
+
+/// JSONボディの読み取りに失敗した場合もエラーレスポンスの形式を揃える
+pub fn json_error_handler(err: JsonPayloadError, req: &HttpRequest) -> actix_web::Error {
+    let locale = Locale::from_head(req.head());
+    let response = match &err {
+        JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. } => {
+            error(locale, ErrorCode::PayloadTooLarge)
+        }
+        _ => {
+            let mut args = FluentArgs::new();
+            args.set("detail", err.to_string());
+            respond(
+                locale,
+                localized(locale, ErrorCode::InvalidJson, Some(&args)),
+            )
+        }
+    };
+    InternalError::from_response(err, response).into()
+}
@@ -0,0 +1,122 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::http::header::{self, CacheControl, CacheDirective};
+use actix_web::web::{self, Bytes};
+use actix_web::HttpResponse;
+use futures_util::stream::{self, Stream, StreamExt};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::timeout;
+
+use crate::application::todo::dto::TodoEventDto;
+use crate::application::todo::TodoService;
+use crate::domain::todo::TodoEvent;
+
+/// 通知がない間に送るコメントの間隔（プロキシに接続を切られないようにする）
+pub(crate) const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// 切断されたときにクライアントが再接続するまでの時間（ミリ秒）
+const RETRY_MILLIS: u64 = 5000;
+
+/// Todoの変更の通知を購読
+/// Server-Sent Events（`text/event-stream`）で、変更のたびに `data` を1件送る。
+/// 通知には変更の内容を含めないため、受け取ったら `GET /api/v1/sync` で差分を取得する。
+/// 同じプロセスでの変更だけを通知する（複数インスタンスでは差分同期の定期実行と併用する）
+#[utoipa::path(
+    get,
+    path = "/api/v1/events",
+    tag = "todos",
+    responses(
+        (status = 200, description = "変更の通知のストリーム", content_type = "text/event-stream", body = TodoEventDto),
+    )
+)]
+pub async fn stream_events(service: web::Data<Arc<TodoService>>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        // nginxにバッファリングさせない
+        .insert_header(("x-accel-buffering", "no"))
+        .insert_header((header::CONNECTION, "keep-alive"))
+        .streaming(event_stream(service.subscribe(), KEEP_ALIVE))
+}
+
+/// 購読した通知をServer-Sent Eventsの形式にする
+fn event_stream(
+    events: broadcast::Receiver<TodoEvent>,
+    keep_alive: Duration,
+) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    let opening = stream::once(async { Ok(Bytes::from(format!("retry: {}\n\n", RETRY_MILLIS))) });
+    let rest = stream::unfold(events, move |mut events| async move {
+        let frame = match timeout(keep_alive, events.recv()).await {
+            Ok(Ok(event)) => message(&dto(event)),
+            Ok(Err(RecvError::Lagged(_))) => message(&TodoEventDto::Resync),
+            Ok(Err(RecvError::Closed)) => return None,
+            Err(_) => ": keep-alive\n\n".to_string(),
+        };
+        Some((Ok(Bytes::from(frame)), events))
+    });
+    opening.chain(rest)
+}
+
+fn dto(event: TodoEvent) -> TodoEventDto {
+    match event {
+        TodoEvent::Saved(todo) => match todo.id() {
+            Some(id) => TodoEventDto::Changed { id },
+            None => TodoEventDto::Resync,
+        },
+        TodoEvent::Deleted(id) => TodoEventDto::Deleted { id },
+    }
+}
+
+fn message(event: &TodoEventDto) -> String {
+    format!(
+        "data: {}\n\n",
+        serde_json::to_string(event).unwrap_or_default()
+    )
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/events", web::get().to(stream_events));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::todo::Todo;
+
+    async fn next_frame(
+        stream: &mut (impl Stream<Item = Result<Bytes, Infallible>> + Unpin),
+    ) -> String {
+        let frame = stream.next().await.unwrap().unwrap();
+        String::from_utf8(frame.to_vec()).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_event_stream_sends_changes_and_keep_alive() {
+        let (sender, receiver) = broadcast::channel(2);
+        let mut stream = Box::pin(event_stream(receiver, Duration::from_millis(20)));
+
+        assert_eq!(next_frame(&mut stream).await, "retry: 5000\n\n");
+        let todo = Todo::reconstruct(7, "牛乳を買う".to_string(), false, 1.0, None, None, None);
+        sender.send(TodoEvent::Saved(todo)).unwrap();
+        assert_eq!(
+            next_frame(&mut stream).await,
+            "data: {\"type\":\"changed\",\"id\":7}\n\n"
+        );
+        assert_eq!(next_frame(&mut stream).await, ": keep-alive\n\n");
+
+        // 溜めておける件数を超えた購読者には取りこぼしを知らせる
+        for id in 1..=3 {
+            sender.send(TodoEvent::Deleted(id)).unwrap();
+        }
+        assert_eq!(
+            next_frame(&mut stream).await,
+            "data: {\"type\":\"resync\"}\n\n"
+        );
+        assert_eq!(
+            next_frame(&mut stream).await,
+            "data: {\"type\":\"deleted\",\"id\":2}\n\n"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json::json;
+use std::sync::Arc;
+use crate::infrastructure::{check_database, Database};
+
+/// プロセスが応答可能かどうか（依存先は確認しない）
+pub async fn liveness() -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(json!({ "status": "ok" })))
+}
+
+/// リクエストを処理できる状態かどうか（SurrealDBの接続・認証・スキーマを確認）
+pub async fn readiness(database: web::Data<Arc<Database>>) -> Result<HttpResponse> {
+    let checks = check_database(&database).await;
+    let ready = checks.iter().all(|check| check.ok);
+    let body = json!({
+        "status": if ready { "ready" } else { "not_ready" },
+        "checks": checks,
+    });
+
+    if ready {
+        Ok(HttpResponse::Ok().json(body))
+    } else {
+        Ok(HttpResponse::ServiceUnavailable().json(body))
+    }
+}
+
+/// ルーティング設定
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/healthz", web::get().to(liveness))
+        .route("/readyz", web::get().to(readiness));
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use actix_web::{web, HttpResponse, Result};
+use crate::application::todo::TodoService;
+use crate::infrastructure::Metrics;
+
+/// Prometheus形式のメトリクスを出力
+pub async fn get_metrics(
+    service: web::Data<Arc<TodoService>>,
+    metrics: web::Data<Arc<Metrics>>,
+) -> Result<HttpResponse> {
+    // Todo件数はスクレイプ時点の値を反映する（DBに接続できない場合は前回値のまま）
+    match service.get_stats().await {
+        Ok(stats) => {
+            metrics.todos.with_label_values(&["total"]).set(stats.total as i64);
+            metrics.todos.with_label_values(&["open"]).set(stats.open as i64);
+            metrics.todos.with_label_values(&["completed"]).set(stats.completed as i64);
+        }
+        Err(e) => tracing::warn!(error = %e, "Todo件数の集計に失敗しました"),
+    }
+
+    match metrics.render() {
+        Ok(body) => Ok(HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4; charset=utf-8")
+            .body(body)),
+        Err(e) => Ok(HttpResponse::InternalServerError().body(e)),
+    }
+}
+
+/// ルーティング設定
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/metrics", web::get().to(get_metrics));
+}
@@ -1,3 +1,23 @@
+pub mod calendar_handler;
+pub mod error;
+pub mod events_handler;
+pub mod health_handler;
+pub mod metrics_handler;
+pub mod openapi_handler;
+pub mod sync_handler;
 pub mod todo_handler;
+pub mod transfer_handler;
+pub mod versioning;
 
-pub use todo_handler::config;
+use actix_web::web;
+
+pub use versioning::Versioning;
+
+/// ルーティング設定
+/// バージョン付きのAPIは `/api/v1` などに、バージョンに依存しないものは `/api` 直下に置く
+pub fn config(cfg: &mut web::ServiceConfig, versioning: &Versioning) {
+    versioning.configure(cfg, openapi_handler::config);
+    cfg.configure(metrics_handler::config)
+        .configure(health_handler::config)
+        .configure(calendar_handler::feed_config);
+}
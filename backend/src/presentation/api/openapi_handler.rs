@@ -0,0 +1,267 @@
+use actix_web::http::header;
+use actix_web::{web, HttpResponse, Result};
+use utoipa::OpenApi;
+
+use super::error::{ErrorCode, ErrorResponse};
+use super::{calendar_handler, events_handler, sync_handler, todo_handler, transfer_handler};
+use crate::application::todo::dto::{
+    CreateTodoDto, MoveTodoDto, SyncResponseDto, TodoEventDto, TodoResponseDto, UpdateTodoDto,
+};
+use api_types::{CalendarFeedDto, ImportReportDto, ImportRowDto, ImportRowStatus, TransferFormat};
+
+/// Swagger UIの配信元（バージョンを固定する）
+const SWAGGER_UI_CDN: &str = "https://cdn.jsdelivr.net/npm/swagger-ui-dist@5.17.14";
+
+/// APIドキュメントページ用のCSP（APIレスポンス用の既定のCSPを上書きする）
+const DOCS_CSP: &str = "default-src 'none'; script-src 'self' https://cdn.jsdelivr.net; \
+    style-src 'unsafe-inline' https://cdn.jsdelivr.net; img-src 'self' data: https://cdn.jsdelivr.net; \
+    connect-src 'self'; frame-ancestors 'none'";
+
+/// OpenAPIドキュメント
+/// ハンドラーを追加・変更したら `paths` と `schemas` も更新する（テストで差分を検出する）
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Todo API",
+        description = "Todoを管理するREST API。`/api` 配下のリクエストはレート制限の対象で、超過時は429を返す。\
+            バージョンはパス（`/api/v1/...`）で指定するほか、`/api/...` に対して `Api-Version: 1` ヘッダーか \
+            `Accept: application/vnd.todo.v1+json` でも指定できる。非推奨のバージョンには `Deprecation` / `Sunset` ヘッダーが付く。\
+            エラーは `error` のコードで判定し、`message` は `Accept-Language` の言語（`en` / `ja`）で返す"
+    ),
+    paths(
+        todo_handler::get_todos,
+        todo_handler::create_todo,
+        todo_handler::get_todo,
+        todo_handler::update_todo,
+        todo_handler::delete_todo,
+        todo_handler::move_todo,
+        sync_handler::get_changes,
+        events_handler::stream_events,
+        transfer_handler::export_todos,
+        transfer_handler::import_todos,
+        calendar_handler::get_feed_url,
+        calendar_handler::rotate_feed_url,
+        calendar_handler::rotate_caldav_password,
+    ),
+    components(schemas(
+        CreateTodoDto,
+        UpdateTodoDto,
+        MoveTodoDto,
+        TodoResponseDto,
+        SyncResponseDto,
+        TodoEventDto,
+        TransferFormat,
+        ImportReportDto,
+        ImportRowDto,
+        ImportRowStatus,
+        transfer_handler::ImportUpload,
+        CalendarFeedDto,
+        ErrorCode,
+        ErrorResponse
+    )),
+    tags(
+        (name = "todos", description = "Todoの操作"),
+        (name = "calendar", description = "期限のあるTodoのカレンダーフィード（iCalendar）")
+    )
+)]
+pub struct ApiDoc;
+
+/// OpenAPIドキュメント(JSON)を取得
+pub async fn get_openapi() -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(ApiDoc::openapi()))
+}
+
+/// Swagger UIを表示
+pub async fn get_docs() -> Result<HttpResponse> {
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="ja">
+  <head>
+    <meta charset="utf-8" />
+    <title>Todo API</title>
+    <link rel="stylesheet" href="{cdn}/swagger-ui.css" />
+  </head>
+  <body>
+    <div id="swagger-ui"></div>
+    <script src="{cdn}/swagger-ui-bundle.js" crossorigin></script>
+    <script src="/api/docs/init.js"></script>
+  </body>
+</html>
+"#,
+        cdn = SWAGGER_UI_CDN
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .insert_header((header::CONTENT_SECURITY_POLICY, DOCS_CSP))
+        .body(html))
+}
+
+/// Swagger UIの初期化スクリプト（CSPでインラインスクリプトを許可しないため別ファイルにする）
+pub async fn get_docs_init() -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok()
+        .content_type("text/javascript; charset=utf-8")
+        .body(
+            r##"window.onload = () => {
+  window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });
+};
+"##,
+        ))
+}
+
+/// ルーティング設定（`/api` スコープ内に登録される）
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/openapi.json", web::get().to(get_openapi))
+        .route("/docs", web::get().to(get_docs))
+        .route("/docs/init.js", web::get().to(get_docs_init));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::todo::TodoService;
+    use crate::config::ApiConfig;
+    use crate::domain::todo::Todo;
+    use crate::infrastructure::persistence::memory::MemoryTodoRepository;
+    use crate::presentation::api::Versioning;
+    use actix_web::http::{Method, StatusCode};
+    use actix_web::{test, App};
+    use std::sync::Arc;
+    use utoipa::openapi::path::{HttpMethod, PathItem};
+
+    /// `/api/v1` に登録しているルート（ルートを追加・削除したらここも更新する）
+    const V1_ROUTES: &[(HttpMethod, &str)] = &[
+        (HttpMethod::Get, "/api/v1/todos"),
+        (HttpMethod::Post, "/api/v1/todos"),
+        (HttpMethod::Get, "/api/v1/todos/{id}"),
+        (HttpMethod::Put, "/api/v1/todos/{id}"),
+        (HttpMethod::Delete, "/api/v1/todos/{id}"),
+        (HttpMethod::Post, "/api/v1/todos/{id}/move"),
+        (HttpMethod::Get, "/api/v1/sync"),
+        (HttpMethod::Get, "/api/v1/events"),
+        (HttpMethod::Get, "/api/v1/export"),
+        (HttpMethod::Post, "/api/v1/import"),
+        (HttpMethod::Get, "/api/v1/calendar"),
+        (HttpMethod::Post, "/api/v1/calendar/rotate"),
+        (HttpMethod::Post, "/api/v1/calendar/caldav/rotate"),
+    ];
+
+    fn is_documented(item: &PathItem, method: &HttpMethod) -> bool {
+        match method {
+            HttpMethod::Get => item.get.is_some(),
+            HttpMethod::Post => item.post.is_some(),
+            HttpMethod::Put => item.put.is_some(),
+            HttpMethod::Delete => item.delete.is_some(),
+            HttpMethod::Patch => item.patch.is_some(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// どのルートにも一致しなかったリクエストには418を返すので、
+    /// ハンドラーが返す404（Todoが見つからない）と区別できる
+    fn is_routed(status: StatusCode) -> bool {
+        status != StatusCode::IM_A_TEAPOT && status != StatusCode::METHOD_NOT_ALLOWED
+    }
+
+    /// ドキュメントに記載されたパスについて、記載されたメソッドはすべてルーティングされ、
+    /// 記載されていないメソッドはルーティングされていないことを確認する。
+    /// 逆方向として、登録しているルートがすべてドキュメントに記載されていることも確認する
+    #[actix_web::test]
+    async fn test_spec_matches_routes() {
+        let todo = Todo::reconstruct(1, "stub".to_string(), false, 1.0, None, None, None);
+        let service = Arc::new(TodoService::new(Arc::new(
+            MemoryTodoRepository::with_todos([todo]),
+        )));
+        let versioning = Versioning::from_config(&ApiConfig::default()).unwrap();
+        let app = test::init_service(
+            App::new()
+                .default_service(web::to(HttpResponse::ImATeapot))
+                .app_data(web::Data::new(service))
+                .configure(|cfg| crate::presentation::api::config(cfg, &versioning)),
+        )
+        .await;
+
+        let spec = ApiDoc::openapi();
+        let json = serde_json::to_value(&spec).unwrap();
+        assert!(json["openapi"].as_str().unwrap().starts_with("3.1"));
+        assert!(!spec.paths.paths.is_empty());
+
+        let methods = [
+            (Method::GET, HttpMethod::Get),
+            (Method::POST, HttpMethod::Post),
+            (Method::PUT, HttpMethod::Put),
+            (Method::DELETE, HttpMethod::Delete),
+            (Method::PATCH, HttpMethod::Patch),
+        ];
+
+        for (path, item) in &spec.paths.paths {
+            let uri = path.replace("{id}", "1");
+            for (method, spec_method) in &methods {
+                let documented = is_documented(item, spec_method);
+                let req = test::TestRequest::default()
+                    .method(method.clone())
+                    .uri(&uri)
+                    .set_json(serde_json::json!({ "title": "stub" }))
+                    .to_request();
+                let status = test::call_service(&app, req).await.status();
+                let routed = is_routed(status);
+
+                assert_eq!(
+                    documented,
+                    routed,
+                    "{} {} はドキュメント{}が、ルーティング{} (status: {})",
+                    method,
+                    path,
+                    if documented {
+                        "にあります"
+                    } else {
+                        "にありません"
+                    },
+                    if routed {
+                        "されています"
+                    } else {
+                        "されていません"
+                    },
+                    status
+                );
+                assert_eq!(
+                    documented,
+                    V1_ROUTES.contains(&(spec_method.clone(), path.as_str())),
+                    "{} {} のドキュメントとルート表が一致しません",
+                    method,
+                    path
+                );
+            }
+        }
+
+        // ルート表のルートはすべてドキュメントに記載され、実際にルーティングされている
+        for (spec_method, path) in V1_ROUTES {
+            let item = spec
+                .paths
+                .paths
+                .get(*path)
+                .unwrap_or_else(|| panic!("{} がドキュメントにありません", path));
+            let (method, _) = methods.iter().find(|(_, m)| m == spec_method).unwrap();
+            assert!(
+                is_documented(item, spec_method),
+                "{} {} がドキュメントにありません",
+                method,
+                path
+            );
+
+            let req = test::TestRequest::default()
+                .method(method.clone())
+                .uri(&path.replace("{id}", "1"))
+                .set_json(serde_json::json!({ "title": "stub" }))
+                .to_request();
+            let status = test::call_service(&app, req).await.status();
+            assert!(
+                is_routed(status),
+                "{} {} がルーティングされていません (status: {})",
+                method,
+                path,
+                status
+            );
+        }
+    }
+}
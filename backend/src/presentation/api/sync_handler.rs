@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use super::error::{self, ErrorCode, ErrorResponse};
+use crate::application::todo::dto::SyncResponseDto;
+use crate::application::todo::TodoService;
+use crate::presentation::i18n::Locale;
+
+/// 差分同期のクエリ
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SyncQuery {
+    /// 前回のレスポンスの `token`。省略すると全件を返す
+    pub since: Option<String>,
+}
+
+/// 前回の同期以降の変更を取得
+#[utoipa::path(
+    get,
+    path = "/api/v1/sync",
+    tag = "todos",
+    params(SyncQuery),
+    responses(
+        (status = 200, description = "変更されたTodoと削除されたTodoのID。`full` がtrueなら全件", body = SyncResponseDto),
+        (status = 400, description = "同期トークンが不正", body = ErrorResponse),
+        (status = 500, description = "データベースエラー", body = ErrorResponse),
+    )
+)]
+pub async fn get_changes(
+    service: web::Data<Arc<TodoService>>,
+    query: web::Query<SyncQuery>,
+    locale: Locale,
+) -> Result<HttpResponse> {
+    let since = match query.since.as_deref().map(str::parse::<u64>).transpose() {
+        Ok(since) => since,
+        Err(_) => return Ok(error::error(locale, ErrorCode::InvalidSyncToken)),
+    };
+
+    match service.sync(since).await {
+        Ok(changes) => Ok(HttpResponse::Ok().json(changes)),
+        Err(e) => Ok(error::todo_error(locale, e)),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/sync", web::get().to(get_changes));
+}
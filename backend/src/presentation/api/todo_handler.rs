@@ -1,70 +1,186 @@
 use std::sync::Arc;
 use actix_web::{web, HttpResponse, Result};
-use crate::application::todo::{TodoService, CreateTodoDto, UpdateTodoDto};
+use crate::application::todo::{TodoService, CreateTodoDto, MoveTodoDto, UpdateTodoDto};
+use crate::application::todo::dto::TodoResponseDto;
+use super::error::{self, ErrorResponse};
+use crate::presentation::i18n::Locale;
 
 /// 全Todoを取得
+#[utoipa::path(
+    get,
+    path = "/api/v1/todos",
+    tag = "todos",
+    responses(
+        (status = 200, description = "Todo一覧", body = Vec<TodoResponseDto>),
+        (status = 500, description = "データベースエラー", body = ErrorResponse),
+    )
+)]
 pub async fn get_todos(
-    service: web::Data<Arc<TodoService>>
+    service: web::Data<Arc<TodoService>>,
+    locale: Locale,
 ) -> Result<HttpResponse> {
     match service.get_all_todos().await {
         Ok(todos) => Ok(HttpResponse::Ok().json(todos)),
-        Err(e) => Ok(HttpResponse::InternalServerError().body(e)),
+        Err(e) => Ok(error::todo_error(locale, e)),
     }
 }
 
 /// IDでTodoを取得
+#[utoipa::path(
+    get,
+    path = "/api/v1/todos/{id}",
+    tag = "todos",
+    params(("id" = i32, Path, description = "TodoのID")),
+    responses(
+        (status = 200, description = "Todo", body = TodoResponseDto),
+        (status = 404, description = "Todoが見つからない", body = ErrorResponse),
+    )
+)]
 pub async fn get_todo(
     service: web::Data<Arc<TodoService>>,
+    locale: Locale,
     id: web::Path<i32>
 ) -> Result<HttpResponse> {
     match service.get_todo_by_id(id.into_inner()).await {
         Ok(todo) => Ok(HttpResponse::Ok().json(todo)),
-        Err(e) => Ok(HttpResponse::NotFound().body(e)),
+        Err(e) => Ok(error::todo_error(locale, e)),
     }
 }
 
 /// Todoを作成
+#[utoipa::path(
+    post,
+    path = "/api/v1/todos",
+    tag = "todos",
+    request_body = CreateTodoDto,
+    responses(
+        (status = 201, description = "作成したTodo", body = TodoResponseDto),
+        (status = 400, description = "タイトルが空など入力が不正", body = ErrorResponse),
+    )
+)]
 pub async fn create_todo(
     service: web::Data<Arc<TodoService>>,
+    locale: Locale,
     dto: web::Json<CreateTodoDto>
 ) -> Result<HttpResponse> {
     match service.create_todo(dto.into_inner()).await {
         Ok(todo) => Ok(HttpResponse::Created().json(todo)),
-        Err(e) => Ok(HttpResponse::BadRequest().body(e)),
+        Err(e) => Ok(error::todo_error(locale, e)),
     }
 }
 
 /// Todoを更新
+#[utoipa::path(
+    put,
+    path = "/api/v1/todos/{id}",
+    tag = "todos",
+    params(("id" = i32, Path, description = "TodoのID")),
+    request_body = UpdateTodoDto,
+    responses(
+        (status = 200, description = "更新後のTodo", body = TodoResponseDto),
+        (status = 404, description = "Todoが見つからない", body = ErrorResponse),
+    )
+)]
 pub async fn update_todo(
     service: web::Data<Arc<TodoService>>,
+    locale: Locale,
     id: web::Path<i32>,
     dto: web::Json<UpdateTodoDto>
 ) -> Result<HttpResponse> {
     match service.update_todo(id.into_inner(), dto.into_inner()).await {
         Ok(todo) => Ok(HttpResponse::Ok().json(todo)),
-        Err(e) => Ok(HttpResponse::NotFound().body(e)),
+        Err(e) => Ok(error::todo_error(locale, e)),
     }
 }
 
 /// Todoを削除
+#[utoipa::path(
+    delete,
+    path = "/api/v1/todos/{id}",
+    tag = "todos",
+    params(("id" = i32, Path, description = "TodoのID")),
+    responses(
+        (status = 204, description = "削除完了"),
+        (status = 404, description = "Todoが見つからない", body = ErrorResponse),
+    )
+)]
 pub async fn delete_todo(
     service: web::Data<Arc<TodoService>>,
+    locale: Locale,
     id: web::Path<i32>
 ) -> Result<HttpResponse> {
     match service.delete_todo(id.into_inner()).await {
-        Ok(_) => Ok(HttpResponse::Ok().body("削除しました")),
-        Err(e) => Ok(HttpResponse::NotFound().body(e)),
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(e) => Ok(error::todo_error(locale, e)),
+    }
+}
+
+/// Todoを並べ替え
+#[utoipa::path(
+    post,
+    path = "/api/v1/todos/{id}/move",
+    tag = "todos",
+    params(("id" = i32, Path, description = "TodoのID")),
+    request_body = MoveTodoDto,
+    responses(
+        (status = 200, description = "移動後のTodo", body = TodoResponseDto),
+        (status = 400, description = "before と after の指定が不正", body = ErrorResponse),
+        (status = 404, description = "Todoか移動先のTodoが見つからない", body = ErrorResponse),
+    )
+)]
+pub async fn move_todo(
+    service: web::Data<Arc<TodoService>>,
+    locale: Locale,
+    id: web::Path<i32>,
+    dto: web::Json<MoveTodoDto>
+) -> Result<HttpResponse> {
+    match service.move_todo(id.into_inner(), dto.into_inner()).await {
+        Ok(todo) => Ok(HttpResponse::Ok().json(todo)),
+        Err(e) => Ok(error::todo_error(locale, e)),
     }
 }
 
-/// ルーティング設定
+/// ルーティング設定（`/api` スコープ内に登録される）
 pub fn config(cfg: &mut web::ServiceConfig) {
-    cfg.service(
-        web::scope("/api")
-            .route("/todos", web::get().to(get_todos))
-            .route("/todos", web::post().to(create_todo))
-            .route("/todos/{id}", web::get().to(get_todo))
-            .route("/todos/{id}", web::put().to(update_todo))
-            .route("/todos/{id}", web::delete().to(delete_todo))
-    );
+    cfg.route("/todos", web::get().to(get_todos))
+        .route("/todos", web::post().to(create_todo))
+        .route("/todos/{id}", web::get().to(get_todo))
+        .route("/todos/{id}", web::put().to(update_todo))
+        .route("/todos/{id}", web::delete().to(delete_todo))
+        .route("/todos/{id}/move", web::post().to(move_todo));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::todo::Todo;
+    use crate::infrastructure::persistence::memory::MemoryTodoRepository;
+    use actix_web::http::StatusCode;
+    use actix_web::{test, App};
+    use api_types::ErrorCode;
+
+    /// 存在しないTodoや削除済みのTodoの削除は404になる（オフラインのキューが再試行し続けない）
+    #[actix_web::test]
+    async fn test_delete_unknown_todo_is_not_found() {
+        let todo = Todo::reconstruct(1, "牛乳を買う".to_string(), false, 1.0, None, None, None);
+        let repository = MemoryTodoRepository::with_todos([todo]);
+        let service = Arc::new(TodoService::new(Arc::new(repository)));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(service))
+                .service(web::scope("/api").configure(config)),
+        )
+        .await;
+        let delete = |uri: &str| test::TestRequest::delete().uri(uri).to_request();
+
+        let res = test::call_service(&app, delete("/api/todos/1")).await;
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+        for uri in ["/api/todos/1", "/api/todos/99"] {
+            let res = test::call_service(&app, delete(uri)).await;
+            assert_eq!(res.status(), StatusCode::NOT_FOUND);
+            let body: ErrorResponse = test::read_body_json(res).await;
+            assert_eq!(body.error, ErrorCode::TodoNotFound);
+        }
+    }
 }
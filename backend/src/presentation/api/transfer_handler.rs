@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use actix_multipart::Multipart;
+use actix_web::http::header::ContentDisposition;
+use actix_web::{web, HttpResponse, Result};
+use fluent_bundle::FluentArgs;
+use futures_util::StreamExt;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::error::{self, ErrorCode, ErrorResponse};
+use crate::application::todo::transfer::{self, TransferError};
+use crate::application::todo::TodoService;
+use crate::config::LimitsConfig;
+use crate::presentation::i18n::Locale;
+use api_types::{ImportReportDto, TransferFormat};
+
+/// エクスポートのクエリ
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportQuery {
+    /// 形式（`csv` / `json` / `md` / `todotxt`）。省略するとJSON
+    pub format: Option<String>,
+}
+
+/// インポートのクエリ
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ImportQuery {
+    /// 形式（`csv` / `json` / `md` / `todotxt`）。省略するとファイル名の拡張子から推測する
+    pub format: Option<String>,
+    /// trueなら作成せずに結果だけを返す
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// インポートするファイル（OpenAPIドキュメント用）
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct ImportUpload {
+    /// UTF-8のテキストファイル
+    #[schema(value_type = String, format = Binary)]
+    file: Vec<u8>,
+}
+
+/// すべてのTodoをファイルとして書き出す
+#[utoipa::path(
+    get,
+    path = "/api/v1/export",
+    tag = "todos",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "書き出したファイル（`Content-Disposition: attachment`）", body = String, content_type = "text/plain"),
+        (status = 400, description = "未対応の形式", body = ErrorResponse),
+    )
+)]
+pub async fn export_todos(
+    service: web::Data<Arc<TodoService>>,
+    query: web::Query<ExportQuery>,
+    locale: Locale,
+) -> Result<HttpResponse> {
+    let format = match query.format.as_deref() {
+        None => TransferFormat::Json,
+        Some(value) => match TransferFormat::parse(value) {
+            Some(format) => format,
+            None => return Ok(error::error(locale, ErrorCode::UnsupportedFormat)),
+        },
+    };
+
+    match transfer::export(&service, format).await {
+        Ok(body) => Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header(ContentDisposition::attachment(format.file_name()))
+            .body(body)),
+        Err(e) => Ok(transfer_error(locale, e)),
+    }
+}
+
+/// ファイルからTodoを作成
+/// 既存のTodoと同じタイトルの行は読み飛ばし、読み込めない行は行ごとのエラーとして返す
+#[utoipa::path(
+    post,
+    path = "/api/v1/import",
+    tag = "todos",
+    params(ImportQuery),
+    request_body(content = ImportUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "行ごとの結果（`dry_run` ならTodoは作成していない）", body = ImportReportDto),
+        (status = 400, description = "未対応の形式か、ファイルを読み込めない", body = ErrorResponse),
+        (status = 413, description = "ファイルが大きすぎる", body = ErrorResponse),
+    )
+)]
+pub async fn import_todos(
+    service: web::Data<Arc<TodoService>>,
+    limits: web::Data<LimitsConfig>,
+    query: web::Query<ImportQuery>,
+    locale: Locale,
+    mut payload: Multipart,
+) -> Result<HttpResponse> {
+    let upload = match read_upload(&mut payload, limits.import_file_bytes).await {
+        Ok(upload) => upload,
+        Err(UploadError::TooLarge) => return Ok(error::error(locale, ErrorCode::PayloadTooLarge)),
+        Err(UploadError::Invalid(detail)) => return Ok(invalid_file(locale, detail)),
+    };
+    let format = match query.format.as_deref() {
+        Some(value) => TransferFormat::parse(value),
+        None => upload
+            .file_name
+            .as_deref()
+            .and_then(TransferFormat::from_file_name),
+    };
+    let Some(format) = format else {
+        return Ok(error::error(locale, ErrorCode::UnsupportedFormat));
+    };
+
+    match transfer::import(&service, format, &upload.content, query.dry_run).await {
+        Ok(report) => Ok(HttpResponse::Ok().json(report)),
+        Err(e) => Ok(transfer_error(locale, e)),
+    }
+}
+
+/// multipartの `file` フィールド
+struct Upload {
+    file_name: Option<String>,
+    content: String,
+}
+
+enum UploadError {
+    TooLarge,
+    Invalid(String),
+}
+
+/// `file` フィールドを `limit` バイトまで読み込む（他のフィールドは読み飛ばす）
+async fn read_upload(payload: &mut Multipart, limit: usize) -> Result<Upload, UploadError> {
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| UploadError::Invalid(e.to_string()))?;
+        let is_file = field.name() == Some("file");
+        let file_name = field
+            .content_disposition()
+            .and_then(|disposition| disposition.get_filename())
+            .map(str::to_string);
+
+        let mut content = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| UploadError::Invalid(e.to_string()))?;
+            if is_file {
+                if content.len() + chunk.len() > limit {
+                    return Err(UploadError::TooLarge);
+                }
+                content.extend_from_slice(&chunk);
+            }
+        }
+        if is_file {
+            let content = String::from_utf8(content)
+                .map_err(|_| UploadError::Invalid("not UTF-8 text".to_string()))?;
+            return Ok(Upload { file_name, content });
+        }
+    }
+    Err(UploadError::Invalid("missing \"file\" field".to_string()))
+}
+
+fn invalid_file(locale: Locale, detail: String) -> HttpResponse {
+    let mut args = FluentArgs::new();
+    args.set("detail", detail);
+    error::respond(
+        locale,
+        error::localized(locale, ErrorCode::InvalidImportFile, Some(&args)),
+    )
+}
+
+fn transfer_error(locale: Locale, err: TransferError) -> HttpResponse {
+    match err {
+        TransferError::InvalidFile(detail) => invalid_file(locale, detail),
+        TransferError::Todo(e) => error::todo_error(locale, e),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/export", web::get().to(export_todos))
+        .route("/import", web::post().to(import_todos));
+}
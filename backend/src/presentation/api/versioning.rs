@@ -0,0 +1,283 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{RequestHead, ServiceRequest, ServiceResponse};
+use actix_web::guard::{Guard, GuardContext};
+use actix_web::http::header::{self, HeaderName};
+use actix_web::middleware::{DefaultHeaders, Next};
+use actix_web::{web, Error};
+
+use fluent_bundle::FluentArgs;
+
+use super::error::{self, ErrorCode};
+use super::{calendar_handler, events_handler, sync_handler, todo_handler, transfer_handler};
+use crate::config::ApiConfig;
+use crate::presentation::i18n::Locale;
+
+/// バージョンを指定するリクエストヘッダー
+pub const API_VERSION_HEADER: &str = "api-version";
+
+/// `Accept: application/vnd.todo.v1+json` 形式でのバージョン指定
+const VENDOR_MEDIA_TYPE_PREFIX: &str = "application/vnd.todo.";
+
+/// APIのバージョン
+/// 新しいバージョンを追加する場合は列挙子と `configure` を追加する（TodoServiceは全バージョンで共有）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiVersion {
+    V1,
+}
+
+impl ApiVersion {
+    /// 提供中のすべてのバージョン（古い順）
+    pub const ALL: [ApiVersion; 1] = [ApiVersion::V1];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+        }
+    }
+
+    /// `1` / `v1` / `V1` を受け付ける
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let number = value
+            .strip_prefix('v')
+            .or_else(|| value.strip_prefix('V'))
+            .unwrap_or(value);
+        Self::ALL
+            .into_iter()
+            .find(|version| &version.as_str()[1..] == number)
+    }
+
+    /// このバージョンのルーティング
+    fn configure(self, cfg: &mut web::ServiceConfig) {
+        match self {
+            ApiVersion::V1 => {
+                todo_handler::config(cfg);
+                sync_handler::config(cfg);
+                events_handler::config(cfg);
+                transfer_handler::config(cfg);
+                calendar_handler::config(cfg);
+            }
+        }
+    }
+
+    /// 次のバージョン
+    fn successor(self) -> Option<Self> {
+        Self::ALL.into_iter().find(|version| *version > self)
+    }
+}
+
+/// 設定から組み立てたバージョン管理
+#[derive(Debug, Clone)]
+pub struct Versioning {
+    default_version: ApiVersion,
+    config: ApiConfig,
+}
+
+impl Versioning {
+    /// 設定に未知のバージョンが含まれる場合はエラー
+    pub fn from_config(config: &ApiConfig) -> Result<Self, String> {
+        let default_version = ApiVersion::parse(&config.default_version).ok_or_else(|| {
+            format!(
+                "api.default_version の {} は提供されていません",
+                config.default_version
+            )
+        })?;
+        for deprecation in &config.deprecations {
+            if ApiVersion::parse(&deprecation.version).is_none() {
+                return Err(format!(
+                    "api.deprecations の {} は提供されていません",
+                    deprecation.version
+                ));
+            }
+        }
+        Ok(Self {
+            default_version,
+            config: config.clone(),
+        })
+    }
+
+    /// ルーティング設定
+    /// `/api/v1/...` のようにパスで指定するか、`/api/...` に対してヘッダーで指定する（省略時は既定のバージョン）
+    pub fn configure(&self, cfg: &mut web::ServiceConfig, shared: fn(&mut web::ServiceConfig)) {
+        for version in ApiVersion::ALL {
+            cfg.service(
+                web::scope(&format!("/api/{}", version.as_str()))
+                    .wrap(self.version_headers(version))
+                    .configure(|cfg| version.configure(cfg)),
+            );
+        }
+
+        let mut unversioned = web::scope("/api")
+            .wrap(actix_web::middleware::from_fn(reject_unsupported_version))
+            .configure(shared);
+        for version in ApiVersion::ALL {
+            unversioned = unversioned.service(
+                web::scope("")
+                    .guard(VersionGuard {
+                        version,
+                        is_default: version == self.default_version,
+                    })
+                    .wrap(self.version_headers(version))
+                    .configure(|cfg| version.configure(cfg)),
+            );
+        }
+        cfg.service(unversioned);
+    }
+
+    /// バージョンを示すヘッダーと、非推奨の場合は `Deprecation` / `Sunset` / `Link` ヘッダー
+    fn version_headers(&self, version: ApiVersion) -> DefaultHeaders {
+        let mut headers = DefaultHeaders::new().add((API_VERSION_HEADER, version.as_str()));
+
+        let Some(deprecation) = self
+            .config
+            .deprecations
+            .iter()
+            .find(|d| ApiVersion::parse(&d.version) == Some(version))
+        else {
+            return headers;
+        };
+
+        // RFC 9745: Unix時刻を `@` に続けて表す
+        headers = headers.add((
+            HeaderName::from_static("deprecation"),
+            format!("@{}", deprecation.deprecated_at.timestamp()),
+        ));
+        // RFC 8594: HTTP-date
+        if let Some(sunset) = deprecation.sunset {
+            headers = headers.add((
+                HeaderName::from_static("sunset"),
+                sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ));
+        }
+        if let Some(successor) = version.successor() {
+            headers = headers.add((
+                header::LINK,
+                format!("</api/{}>; rel=\"successor-version\"", successor.as_str()),
+            ));
+        }
+        headers
+    }
+}
+
+/// リクエストで指定されたバージョン
+/// `Api-Version` ヘッダーを優先し、なければ `Accept` のベンダーメディアタイプを見る
+/// 戻り値の外側の `None` は指定なし、内側の `None` は未対応のバージョン
+fn requested_version(head: &RequestHead) -> Option<Option<ApiVersion>> {
+    if let Some(value) = head.headers().get(API_VERSION_HEADER) {
+        return Some(value.to_str().ok().and_then(ApiVersion::parse));
+    }
+
+    let accept = head.headers().get(header::ACCEPT)?.to_str().ok()?;
+    accept
+        .split(',')
+        .filter_map(|media_type| {
+            media_type
+                .trim()
+                .split(';')
+                .next()?
+                .strip_prefix(VENDOR_MEDIA_TYPE_PREFIX)
+        })
+        .map(|rest| ApiVersion::parse(rest.split('+').next().unwrap_or(rest)))
+        .next()
+}
+
+/// ヘッダーで指定されたバージョン、または指定がない場合の既定のバージョンに一致するガード
+struct VersionGuard {
+    version: ApiVersion,
+    is_default: bool,
+}
+
+impl Guard for VersionGuard {
+    fn check(&self, ctx: &GuardContext<'_>) -> bool {
+        match requested_version(ctx.head()) {
+            Some(requested) => requested == Some(self.version),
+            None => self.is_default,
+        }
+    }
+}
+
+/// 未対応のバージョンが指定された場合は406を返す
+async fn reject_unsupported_version(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    if let Some(None) = requested_version(req.head()) {
+        let supported = ApiVersion::ALL
+            .iter()
+            .map(|v| v.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let locale = Locale::from_head(req.head());
+        let mut args = FluentArgs::new();
+        args.set("supported", supported);
+        let res = error::respond(
+            locale,
+            error::localized(locale, ErrorCode::UnsupportedApiVersion, Some(&args)),
+        );
+        return Ok(req.into_response(res));
+    }
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_boxed_body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VersionDeprecation;
+    use actix_web::http::StatusCode;
+    use actix_web::{test, App, HttpResponse};
+
+    fn app_config(cfg: &mut web::ServiceConfig) {
+        let config = ApiConfig {
+            deprecations: vec![VersionDeprecation {
+                version: "v1".to_string(),
+                deprecated_at: "2026-01-01T00:00:00Z".parse().unwrap(),
+                sunset: Some("2026-07-01T00:00:00Z".parse().unwrap()),
+            }],
+            ..Default::default()
+        };
+        Versioning::from_config(&config)
+            .unwrap()
+            .configure(cfg, |cfg| {
+                cfg.route("/shared", web::get().to(HttpResponse::Ok));
+            });
+    }
+
+    #[actix_web::test]
+    async fn test_version_selection_and_deprecation() {
+        let app = test::init_service(App::new().configure(app_config)).await;
+
+        // パスでの指定（todo_handlerはサービスがないため500になるがルーティングはされる）
+        let req = test::TestRequest::get().uri("/api/v1/todos/1").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_ne!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(res.headers().get(API_VERSION_HEADER).unwrap(), "v1");
+        assert_eq!(res.headers().get("deprecation").unwrap(), "@1767225600");
+        assert_eq!(
+            res.headers().get("sunset").unwrap(),
+            "Wed, 01 Jul 2026 00:00:00 GMT"
+        );
+
+        // ヘッダーでの指定
+        let req = test::TestRequest::get()
+            .uri("/api/todos/1")
+            .insert_header((header::ACCEPT, "application/vnd.todo.v1+json"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(API_VERSION_HEADER).unwrap(), "v1");
+
+        // 未対応のバージョン
+        let req = test::TestRequest::get()
+            .uri("/api/todos")
+            .insert_header((API_VERSION_HEADER, "9"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+
+        // バージョンに依存しないルート
+        let req = test::TestRequest::get().uri("/api/shared").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,853 @@
+//! CalDAV（RFC 4791）
+//!
+//! すべてのTodoを1つのカレンダーコレクション（`/dav/todos/`）の VTODO として公開する。
+//! `/dav/` がプリンシパルとカレンダーホームを兼ね、`/.well-known/caldav` からそこへ案内する。
+//! 同期はETagとコレクションの `getctag` で行う（sync-collection には対応しない）。
+//! 認証はBasic認証で、ユーザー名は問わず、パスワードにはカレンダーフィードのURLとは別に発行したトークンを使う。
+
+pub mod xml;
+
+use std::sync::Arc;
+
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderMap};
+use actix_web::http::{Method, StatusCode};
+use actix_web::middleware::{from_fn, Next};
+use actix_web::{web, Error, HttpRequest, HttpResponse, Route};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use self::xml::{PropName, PropRequest, Report, RequestError, CALDAV, CALENDAR_SERVER, DAV};
+use crate::application::calendar::{CalDavObject, CalDavService, Preconditions};
+use crate::domain::calendar::CalDavError;
+
+/// プリンシパルとカレンダーホーム
+pub const ROOT_PATH: &str = "/dav/";
+/// Todoのコレクション
+const COLLECTION_PATH: &str = "/dav/todos/";
+
+const ALLOW: &str = "OPTIONS, GET, HEAD, PUT, DELETE, PROPFIND, REPORT";
+const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8; component=VTODO";
+
+/// PROPFIND・REPORT の対象
+enum Resource<'a> {
+    Root,
+    Collection { ctag: &'a str },
+    Object(&'a CalDavObject),
+}
+
+impl Resource<'_> {
+    fn href(&self) -> String {
+        match self {
+            Resource::Root => ROOT_PATH.to_string(),
+            Resource::Collection { .. } => COLLECTION_PATH.to_string(),
+            Resource::Object(object) => object_href(&object.name),
+        }
+    }
+
+    /// `allprop` で返すプロパティ
+    fn all_props(&self) -> Vec<PropName> {
+        let names: &[(&str, &str)] = match self {
+            Resource::Root => &[
+                (DAV, "resourcetype"),
+                (DAV, "displayname"),
+                (DAV, "current-user-principal"),
+                (DAV, "principal-URL"),
+                (CALDAV, "calendar-home-set"),
+            ],
+            Resource::Collection { .. } => &[
+                (DAV, "resourcetype"),
+                (DAV, "displayname"),
+                (DAV, "getetag"),
+                (CALENDAR_SERVER, "getctag"),
+                (CALDAV, "supported-calendar-component-set"),
+            ],
+            Resource::Object(_) => &[
+                (DAV, "resourcetype"),
+                (DAV, "getetag"),
+                (DAV, "getcontenttype"),
+                (DAV, "getcontentlength"),
+            ],
+        };
+        names
+            .iter()
+            .map(|(namespace, name)| PropName::new(namespace, name))
+            .collect()
+    }
+
+    /// プロパティの値（XML）。このリソースにないプロパティは `None`
+    fn prop(&self, prop: &PropName) -> Option<String> {
+        let principal = xml::href(ROOT_PATH);
+        match (prop.namespace.as_str(), prop.name.as_str(), self) {
+            (DAV, "resourcetype", Resource::Root) => {
+                Some("<d:collection/><d:principal/>".to_string())
+            }
+            (DAV, "resourcetype", Resource::Collection { .. }) => {
+                Some("<d:collection/><c:calendar/>".to_string())
+            }
+            (DAV, "resourcetype", Resource::Object(_)) => Some(String::new()),
+            (DAV, "displayname", Resource::Root) => Some("todo-app".to_string()),
+            (DAV, "displayname", Resource::Collection { .. }) => Some("Todos".to_string()),
+            (DAV, "current-user-principal" | "principal-URL" | "owner", _) => Some(principal),
+            (CALDAV, "calendar-home-set", Resource::Root) => Some(principal),
+            (DAV, "current-user-privilege-set", _) => Some(
+                [
+                    "read",
+                    "write",
+                    "write-content",
+                    "write-properties",
+                    "bind",
+                    "unbind",
+                ]
+                .iter()
+                .map(|privilege| format!("<d:privilege><d:{}/></d:privilege>", privilege))
+                .collect(),
+            ),
+            (CALDAV, "supported-calendar-component-set", Resource::Collection { .. }) => {
+                Some("<c:comp name=\"VTODO\"/>".to_string())
+            }
+            (DAV, "supported-report-set", Resource::Collection { .. }) => Some(
+                ["calendar-query", "calendar-multiget"]
+                    .iter()
+                    .map(|report| {
+                        format!(
+                            "<d:supported-report><d:report><c:{}/></d:report></d:supported-report>",
+                            report
+                        )
+                    })
+                    .collect(),
+            ),
+            (DAV, "getetag", Resource::Collection { ctag })
+            | (CALENDAR_SERVER, "getctag", Resource::Collection { ctag }) => {
+                Some(xml::escape(ctag))
+            }
+            (DAV, "getetag", Resource::Object(object)) => Some(xml::escape(&object.etag)),
+            (DAV, "getcontenttype", Resource::Object(_)) => Some(CALENDAR_CONTENT_TYPE.to_string()),
+            (DAV, "getcontentlength", Resource::Object(object)) => {
+                Some(object.body.len().to_string())
+            }
+            (CALDAV, "calendar-data", Resource::Object(object)) => Some(xml::escape(&object.body)),
+            _ => None,
+        }
+    }
+
+    /// `<d:response>`
+    fn response(&self, props: &PropRequest) -> String {
+        let names = match props {
+            PropRequest::All => self.all_props(),
+            PropRequest::Names(names) => names.clone(),
+        };
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+        for name in names {
+            match self.prop(&name) {
+                Some(value) => found.push((name, value)),
+                None => missing.push(name),
+            }
+        }
+        xml::response(&self.href(), &found, &missing)
+    }
+}
+
+/// リソース名のパス（名前はパーセントエンコードする）
+fn object_href(name: &str) -> String {
+    let mut href = COLLECTION_PATH.to_string();
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~@".contains(&byte) {
+            href.push(byte as char);
+        } else {
+            href.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    href
+}
+
+/// calendar-multiget の href（パスかURL）からリソース名を取り出す
+fn object_name(href: &str) -> Option<String> {
+    let path = match href.split_once("://") {
+        Some((_, rest)) => &rest[rest.find('/')?..],
+        None => href,
+    };
+    let encoded = path.strip_prefix(COLLECTION_PATH)?;
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut rest = encoded.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' && tail.len() >= 2 {
+            let hex = std::str::from_utf8(&tail[..2]).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes)
+        .ok()
+        .filter(|name| !name.is_empty() && !name.contains('/'))
+}
+
+fn multistatus(responses: &[String]) -> HttpResponse {
+    HttpResponse::build(StatusCode::MULTI_STATUS)
+        .content_type("application/xml; charset=utf-8")
+        .body(xml::multistatus(responses))
+}
+
+/// 条件を満たさなかった理由を付けた403
+fn forbidden(condition: &str) -> HttpResponse {
+    HttpResponse::Forbidden()
+        .content_type("application/xml; charset=utf-8")
+        .body(xml::error(condition))
+}
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized()
+        .insert_header((
+            header::WWW_AUTHENTICATE,
+            "Basic realm=\"todo-app\", charset=\"UTF-8\"",
+        ))
+        .finish()
+}
+
+fn dav_error(err: CalDavError) -> HttpResponse {
+    match err {
+        CalDavError::Unauthorized => unauthorized(),
+        CalDavError::NotFound => HttpResponse::NotFound().finish(),
+        CalDavError::PreconditionFailed => HttpResponse::PreconditionFailed().finish(),
+        CalDavError::InvalidCalendarData(e) => {
+            tracing::debug!(error = %e, "VTODO を保存できません");
+            forbidden("<c:valid-calendar-data/>")
+        }
+        CalDavError::UnsupportedComponent => forbidden("<c:supported-calendar-component/>"),
+        CalDavError::UidConflict { name } => forbidden(&format!(
+            "<c:no-uid-conflict>{}</c:no-uid-conflict>",
+            xml::href(&object_href(&name))
+        )),
+        CalDavError::Repository(e) => {
+            tracing::error!(error = %e, "CalDAVのリクエストを処理できません");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+fn request_error(err: RequestError) -> HttpResponse {
+    match err {
+        RequestError::Invalid(e) => {
+            tracing::debug!(error = %e, "CalDAVのリクエストのXMLを読めません");
+            HttpResponse::BadRequest().finish()
+        }
+        RequestError::UnsupportedReport => forbidden("<d:supported-report/>"),
+    }
+}
+
+/// `Depth` ヘッダー（省略時と `infinity` は1として扱う）
+fn depth(req: &HttpRequest) -> u8 {
+    match req
+        .headers()
+        .get("Depth")
+        .and_then(|value| value.to_str().ok())
+    {
+        Some("0") => 0,
+        _ => 1,
+    }
+}
+
+fn preconditions(headers: &HeaderMap) -> Preconditions {
+    let value = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    Preconditions {
+        if_match: value(header::IF_MATCH),
+        if_none_match: value(header::IF_NONE_MATCH).is_some_and(|value| value.trim() == "*"),
+    }
+}
+
+/// Basic認証のパスワード
+fn basic_password(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, credentials) = value.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+    let decoded = String::from_utf8(STANDARD.decode(credentials.trim()).ok()?).ok()?;
+    let (_, password) = decoded.split_once(':')?;
+    Some(password.to_string())
+}
+
+/// Basic認証のミドルウェア（OPTIONS は認証なしで答える）
+async fn authenticate(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    if req.method() == Method::OPTIONS {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body);
+    }
+    let result = match (
+        req.app_data::<web::Data<Arc<CalDavService>>>(),
+        basic_password(req.headers()),
+    ) {
+        (Some(service), Some(password)) => service.authenticate(&password).await,
+        (Some(_), None) => Err(CalDavError::Unauthorized),
+        (None, _) => Err(CalDavError::Repository(
+            "CalDAVのサービスが登録されていません".to_string(),
+        )),
+    };
+    match result {
+        Ok(()) => next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body),
+        Err(e) => Ok(req.into_response(dav_error(e))),
+    }
+}
+
+/// 対応するメソッドとCalDAVへの対応を伝える
+async fn options() -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(("DAV", "1, calendar-access"))
+        .insert_header((header::ALLOW, ALLOW))
+        .finish()
+}
+
+/// `/.well-known/caldav`（RFC 6764）からプリンシパルへ案内する
+async fn well_known() -> HttpResponse {
+    HttpResponse::MovedPermanently()
+        .insert_header((header::LOCATION, ROOT_PATH))
+        .finish()
+}
+
+/// プリンシパルとカレンダーホーム（`Depth: 1` ならコレクションも返す）
+async fn propfind_root(
+    service: web::Data<Arc<CalDavService>>,
+    req: HttpRequest,
+    body: String,
+) -> HttpResponse {
+    let props = match xml::parse_propfind(&body) {
+        Ok(props) => props,
+        Err(e) => return request_error(e),
+    };
+    let mut responses = vec![Resource::Root.response(&props)];
+    if depth(&req) > 0 {
+        match service.list().await {
+            Ok(objects) => {
+                let ctag = CalDavService::collection_tag(&objects);
+                responses.push(Resource::Collection { ctag: &ctag }.response(&props));
+            }
+            Err(e) => return dav_error(e),
+        }
+    }
+    multistatus(&responses)
+}
+
+/// コレクション（`Depth: 1` ならすべてのTodoも返す）
+async fn propfind_collection(
+    service: web::Data<Arc<CalDavService>>,
+    req: HttpRequest,
+    body: String,
+) -> HttpResponse {
+    let props = match xml::parse_propfind(&body) {
+        Ok(props) => props,
+        Err(e) => return request_error(e),
+    };
+    let objects = match service.list().await {
+        Ok(objects) => objects,
+        Err(e) => return dav_error(e),
+    };
+    let ctag = CalDavService::collection_tag(&objects);
+    let mut responses = vec![Resource::Collection { ctag: &ctag }.response(&props)];
+    if depth(&req) > 0 {
+        responses.extend(
+            objects
+                .iter()
+                .map(|object| Resource::Object(object).response(&props)),
+        );
+    }
+    multistatus(&responses)
+}
+
+/// calendar-query（すべての VTODO）と calendar-multiget（指定したTodo）
+async fn report(service: web::Data<Arc<CalDavService>>, body: String) -> HttpResponse {
+    let report = match xml::parse_report(&body) {
+        Ok(report) => report,
+        Err(e) => return request_error(e),
+    };
+    let objects = match service.list().await {
+        Ok(objects) => objects,
+        Err(e) => return dav_error(e),
+    };
+    let responses = match report {
+        // 期間などの絞り込みは見ず、VTODO を求められたらすべて返す
+        Report::Query { props, component } => {
+            if component.is_some_and(|component| component != "VTODO") {
+                Vec::new()
+            } else {
+                objects
+                    .iter()
+                    .map(|object| Resource::Object(object).response(&props))
+                    .collect()
+            }
+        }
+        Report::Multiget { props, hrefs } => hrefs
+            .iter()
+            .map(|href| {
+                object_name(href)
+                    .and_then(|name| objects.iter().find(|object| object.name == name))
+                    .map(|object| Resource::Object(object).response(&props))
+                    .unwrap_or_else(|| xml::not_found(href))
+            })
+            .collect(),
+    };
+    multistatus(&responses)
+}
+
+/// 1件のTodo
+async fn propfind_object(
+    service: web::Data<Arc<CalDavService>>,
+    name: web::Path<String>,
+    body: String,
+) -> HttpResponse {
+    let props = match xml::parse_propfind(&body) {
+        Ok(props) => props,
+        Err(e) => return request_error(e),
+    };
+    match service.get(&name).await {
+        Ok(object) => multistatus(&[Resource::Object(&object).response(&props)]),
+        Err(e) => dav_error(e),
+    }
+}
+
+async fn get_object(
+    service: web::Data<Arc<CalDavService>>,
+    name: web::Path<String>,
+) -> HttpResponse {
+    match service.get(&name).await {
+        Ok(object) => HttpResponse::Ok()
+            .content_type(CALENDAR_CONTENT_TYPE)
+            .insert_header((header::ETAG, object.etag))
+            .body(object.body),
+        Err(e) => dav_error(e),
+    }
+}
+
+/// Todoを作成・更新する
+/// 受け取った内容のうちTodoにない項目は保存しないため、ETagは返さずにクライアントに取得し直させる
+async fn put_object(
+    service: web::Data<Arc<CalDavService>>,
+    name: web::Path<String>,
+    req: HttpRequest,
+    body: web::Bytes,
+) -> HttpResponse {
+    let Ok(body) = std::str::from_utf8(&body) else {
+        return dav_error(CalDavError::InvalidCalendarData(
+            "UTF-8ではありません".to_string(),
+        ));
+    };
+    match service
+        .put(&name, body, &preconditions(req.headers()))
+        .await
+    {
+        Ok(true) => {
+            tracing::info!("CalDAVからTodoを作成しました");
+            HttpResponse::Created().finish()
+        }
+        Ok(false) => HttpResponse::NoContent().finish(),
+        Err(e) => dav_error(e),
+    }
+}
+
+async fn delete_object(
+    service: web::Data<Arc<CalDavService>>,
+    name: web::Path<String>,
+    req: HttpRequest,
+) -> HttpResponse {
+    match service.delete(&name, &preconditions(req.headers())).await {
+        Ok(()) => {
+            tracing::info!("CalDAVからTodoを削除しました");
+            HttpResponse::NoContent().finish()
+        }
+        Err(e) => dav_error(e),
+    }
+}
+
+fn method(name: &'static [u8]) -> Route {
+    web::method(Method::from_bytes(name).expect("メソッド名が不正です"))
+}
+
+/// ルーティング設定（`/api` の外に置く）
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/.well-known/caldav", web::route().to(well_known))
+        .service(
+            web::scope("/dav")
+                .wrap(from_fn(authenticate))
+                .service(
+                    web::resource(["", "/"])
+                        .route(web::method(Method::OPTIONS).to(options))
+                        .route(method(b"PROPFIND").to(propfind_root)),
+                )
+                .service(
+                    web::resource(["/todos", "/todos/"])
+                        .route(web::method(Method::OPTIONS).to(options))
+                        .route(method(b"PROPFIND").to(propfind_collection))
+                        .route(method(b"REPORT").to(report)),
+                )
+                .service(
+                    web::resource("/todos/{name}")
+                        .route(web::method(Method::OPTIONS).to(options))
+                        .route(method(b"PROPFIND").to(propfind_object))
+                        .route(web::get().to(get_object))
+                        .route(web::head().to(get_object))
+                        .route(web::put().to(put_object))
+                        .route(web::delete().to(delete_object)),
+                ),
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::calendar::{CalendarResource, CalendarResourceRepository, TokenKind};
+    use crate::domain::todo::{Todo, TodoRepository};
+    use crate::infrastructure::persistence::memory::{MemoryFeedRepository, MemoryTodoRepository};
+    use actix_web::{test, App};
+    use async_trait::async_trait;
+    use std::io::BufReader;
+    use std::sync::Mutex;
+
+    /// フィードのトークン `feed` と、CalDAVのパスワード `secret` を持つ保存先
+    fn feeds() -> Arc<MemoryFeedRepository> {
+        Arc::new(
+            MemoryFeedRepository::default()
+                .with_token(TokenKind::Feed, "feed")
+                .with_token(TokenKind::CalDav, "secret"),
+        )
+    }
+
+    #[derive(Default)]
+    struct MemoryResourceRepository {
+        resources: Mutex<Vec<CalendarResource>>,
+    }
+
+    #[async_trait]
+    impl CalendarResourceRepository for MemoryResourceRepository {
+        async fn find_all(&self) -> Result<Vec<CalendarResource>, String> {
+            Ok(self.resources.lock().unwrap().clone())
+        }
+        async fn save(&self, resource: &CalendarResource) -> Result<(), String> {
+            let mut resources = self.resources.lock().unwrap();
+            resources.retain(|other| other.todo_id != resource.todo_id);
+            resources.push(resource.clone());
+            Ok(())
+        }
+        async fn delete(&self, todo_id: i32) -> Result<(), String> {
+            self.resources
+                .lock()
+                .unwrap()
+                .retain(|resource| resource.todo_id != todo_id);
+            Ok(())
+        }
+    }
+
+    fn vtodo(uid: &str, summary: &str, status: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\nBEGIN:VTODO\r\nUID:{}\r\n\
+             DTSTAMP:20261019T000000Z\r\nSUMMARY:{}\r\nDUE;VALUE=DATE:20261020\r\nSTATUS:{}\r\n\
+             END:VTODO\r\nEND:VCALENDAR\r\n",
+            uid, summary, status
+        )
+    }
+
+    /// multistatus の中の、`href` のリソースの `name` プロパティの値
+    fn prop(xml: &str, href: &str, name: &str) -> Option<String> {
+        let document = roxmltree::Document::parse(xml).unwrap();
+        let response = document.descendants().find(|node| {
+            node.tag_name().name() == "response"
+                && node
+                    .children()
+                    .any(|child| child.tag_name().name() == "href" && child.text() == Some(href))
+        })?;
+        let value = response
+            .descendants()
+            .find(|node| node.tag_name().name() == name)?;
+        Some(value.text().unwrap_or_default().to_string())
+    }
+
+    /// ディスカバリーからETagでの同期、書き込みの競合までをクライアントと同じ手順で確認する
+    #[actix_web::test]
+    async fn test_client_discovers_syncs_and_writes_todos() {
+        let todos = Arc::new(MemoryTodoRepository::default());
+        todos
+            .save(&Todo::new("牛乳を買う".to_string()).unwrap())
+            .await
+            .unwrap();
+        let service = Arc::new(CalDavService::new(
+            feeds(),
+            Arc::new(MemoryResourceRepository::default()),
+            todos.clone(),
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(service))
+                .configure(config),
+        )
+        .await;
+        let auth = (
+            header::AUTHORIZATION,
+            format!("Basic {}", STANDARD.encode("me:secret")),
+        );
+        let request = |method: &'static [u8], uri: &str| {
+            test::TestRequest::default()
+                .method(Method::from_bytes(method).unwrap())
+                .uri(uri)
+                .insert_header(auth.clone())
+        };
+        let body = |res: ServiceResponse| async move {
+            String::from_utf8(test::read_body(res).await.to_vec()).unwrap()
+        };
+
+        // ディスカバリー: well-known → プリンシパル → カレンダーホーム → コレクション
+        let res = test::call_service(
+            &app,
+            request(b"PROPFIND", "/.well-known/caldav").to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(res.headers().get(header::LOCATION).unwrap(), ROOT_PATH);
+
+        let unauthorized = test::TestRequest::default()
+            .method(Method::from_bytes(b"PROPFIND").unwrap())
+            .uri(ROOT_PATH)
+            .to_request();
+        let res = test::call_service(&app, unauthorized).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert!(res.headers().contains_key(header::WWW_AUTHENTICATE));
+
+        // フィードのURLのトークンはCalDAVのパスワードにならない
+        let feed_token = test::TestRequest::default()
+            .method(Method::from_bytes(b"PROPFIND").unwrap())
+            .uri(ROOT_PATH)
+            .insert_header((
+                header::AUTHORIZATION,
+                format!("Basic {}", STANDARD.encode("me:feed")),
+            ))
+            .to_request();
+        let res = test::call_service(&app, feed_token).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = test::call_service(
+            &app,
+            request(b"PROPFIND", ROOT_PATH)
+                .insert_header(("Depth", "1"))
+                .set_payload(
+                    r#"<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+                         <d:prop><d:current-user-principal/><c:calendar-home-set/><d:resourcetype/></d:prop>
+                       </d:propfind>"#,
+                )
+                .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::MULTI_STATUS);
+        let xml = body(res).await;
+        assert!(prop(&xml, ROOT_PATH, "current-user-principal").is_some());
+        assert!(prop(&xml, ROOT_PATH, "calendar-home-set").is_some());
+        assert!(xml.contains("<d:response><d:href>/dav/todos/</d:href>"));
+        assert!(xml.contains("<c:calendar/>"));
+
+        // 一覧のETagを取得する
+        let propfind_etags = r#"<d:propfind xmlns:d="DAV:" xmlns:cs="http://calendarserver.org/ns/">
+                                  <d:prop><d:getetag/><cs:getctag/></d:prop></d:propfind>"#;
+        let res = test::call_service(
+            &app,
+            request(b"PROPFIND", COLLECTION_PATH)
+                .insert_header(("Depth", "1"))
+                .set_payload(propfind_etags)
+                .to_request(),
+        )
+        .await;
+        let xml = body(res).await;
+        let ctag = prop(&xml, COLLECTION_PATH, "getctag").unwrap();
+        let etag = prop(&xml, "/dav/todos/todo-1.ics", "getetag").unwrap();
+
+        // クライアントで作ったTodoは、その名前とUIDのまま読める
+        let created = "/dav/todos/3F2504E0-4F89.ics";
+        let put = |uri: &str, body: String| {
+            request(b"PUT", uri)
+                .insert_header((header::CONTENT_TYPE, "text/calendar; charset=utf-8"))
+                .set_payload(body)
+        };
+        let res = test::call_service(
+            &app,
+            put(
+                created,
+                vtodo("3F2504E0-4F89", "報告書\\, 下書き", "NEEDS-ACTION"),
+            )
+            .insert_header((header::IF_NONE_MATCH, "*"))
+            .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let res = test::call_service(
+            &app,
+            put(created, vtodo("3F2504E0-4F89", "二重", "NEEDS-ACTION"))
+                .insert_header((header::IF_NONE_MATCH, "*"))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+
+        let multiget = format!(
+            r#"<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+                 <d:prop><d:getetag/><c:calendar-data/></d:prop>
+                 <d:href>{}</d:href><d:href>/dav/todos/missing.ics</d:href>
+               </c:calendar-multiget>"#,
+            created
+        );
+        let res = test::call_service(
+            &app,
+            request(b"REPORT", COLLECTION_PATH)
+                .set_payload(multiget)
+                .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::MULTI_STATUS);
+        let xml = body(res).await;
+        assert!(
+            xml.contains("<d:href>/dav/todos/missing.ics</d:href><d:status>HTTP/1.1 404 Not Found")
+        );
+        let data = prop(&xml, created, "calendar-data").unwrap();
+        let calendar = ical::IcalParser::new(BufReader::new(data.as_bytes()))
+            .next()
+            .unwrap()
+            .unwrap();
+        let value = |name: &str| {
+            calendar.todos[0]
+                .properties
+                .iter()
+                .find(|property| property.name == name)
+                .and_then(|property| property.value.clone())
+        };
+        assert_eq!(value("UID").as_deref(), Some("3F2504E0-4F89"));
+        assert_eq!(value("SUMMARY").as_deref(), Some("報告書\\, 下書き"));
+        assert_eq!(value("DUE").as_deref(), Some("20261020"));
+
+        // 古いETagでの更新は拒否し、新しいETagなら反映する
+        let res =
+            test::call_service(&app, request(b"GET", "/dav/todos/todo-1.ics").to_request()).await;
+        assert_eq!(
+            res.headers().get(header::ETAG).unwrap().to_str().unwrap(),
+            etag
+        );
+        let res = test::call_service(
+            &app,
+            put(
+                "/dav/todos/todo-1.ics",
+                vtodo("todo-1@todo-app", "牛乳を買う", "COMPLETED"),
+            )
+            .insert_header((header::IF_MATCH, "\"0000000000000000\""))
+            .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+        let res = test::call_service(
+            &app,
+            put(
+                "/dav/todos/todo-1.ics",
+                vtodo("todo-1@todo-app", "牛乳を買う", "COMPLETED"),
+            )
+            .insert_header((header::IF_MATCH, etag.as_str()))
+            .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        assert!(todos.find_by_id(1).await.unwrap().unwrap().is_completed());
+
+        // UIDの重複と VTODO 以外は保存しない
+        let res = test::call_service(
+            &app,
+            put(
+                "/dav/todos/other.ics",
+                vtodo("todo-1@todo-app", "重複", "NEEDS-ACTION"),
+            )
+            .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert!(body(res)
+            .await
+            .contains("<c:no-uid-conflict><d:href>/dav/todos/todo-1.ics</d:href>"));
+        let event = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:e\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let res = test::call_service(
+            &app,
+            put("/dav/todos/event.ics", event.to_string()).to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // 削除すると一覧から消え、コレクションのETagが変わる
+        let res = test::call_service(&app, request(b"DELETE", created).to_request()).await;
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        let res = test::call_service(&app, request(b"GET", created).to_request()).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let res = test::call_service(
+            &app,
+            request(b"PROPFIND", COLLECTION_PATH)
+                .insert_header(("Depth", "1"))
+                .set_payload(propfind_etags)
+                .to_request(),
+        )
+        .await;
+        let xml = body(res).await;
+        assert_ne!(prop(&xml, COLLECTION_PATH, "getctag").unwrap(), ctag);
+        assert!(prop(&xml, created, "getetag").is_none());
+    }
+
+    /// 同じ名前への同時の作成と、同じETagを確認した同時の更新は、どちらか一方だけが成功する
+    #[actix_web::test]
+    async fn test_concurrent_conditional_writes_do_not_race() {
+        let todos = Arc::new(MemoryTodoRepository::default());
+        let service = CalDavService::new(
+            feeds(),
+            Arc::new(MemoryResourceRepository::default()),
+            todos.clone(),
+        );
+        let create = Preconditions {
+            if_match: None,
+            if_none_match: true,
+        };
+        let (first, second) = (
+            vtodo("a", "一方", "NEEDS-ACTION"),
+            vtodo("a", "他方", "NEEDS-ACTION"),
+        );
+        let (a, b) = futures_util::join!(
+            service.put("a.ics", &first, &create),
+            service.put("a.ics", &second, &create),
+        );
+        assert_eq!(a, Ok(true));
+        assert_eq!(b, Err(CalDavError::PreconditionFailed));
+        assert_eq!(todos.find_all().await.unwrap().len(), 1);
+
+        let etag = service.get("a.ics").await.unwrap().etag;
+        let update = Preconditions {
+            if_match: Some(etag),
+            if_none_match: false,
+        };
+        let updated = vtodo("a", "更新1", "NEEDS-ACTION");
+        let (a, b) = futures_util::join!(
+            service.put("a.ics", &updated, &update),
+            service.delete("a.ics", &update),
+        );
+        assert_eq!(a, Ok(false));
+        assert_eq!(b, Err(CalDavError::PreconditionFailed));
+        assert!(service
+            .get("a.ics")
+            .await
+            .unwrap()
+            .body
+            .contains("SUMMARY:更新1"));
+    }
+}
@@ -0,0 +1,308 @@
+//! WebDAV・CalDAVのXML
+//!
+//! リクエストは PROPFIND と REPORT（calendar-query / calendar-multiget）だけを読み、
+//! レスポンスは multistatus を組み立てる。
+
+use roxmltree::{Document, Node};
+
+pub const DAV: &str = "DAV:";
+pub const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+pub const CALENDAR_SERVER: &str = "http://calendarserver.org/ns/";
+
+/// プロパティの名前（名前空間とローカル名）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropName {
+    pub namespace: String,
+    pub name: String,
+}
+
+impl PropName {
+    pub fn new(namespace: &str, name: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    /// 空要素（`<d:getetag/>`）
+    fn empty_element(&self) -> String {
+        match prefix(&self.namespace) {
+            Some(prefix) => format!("<{}:{}/>", prefix, self.name),
+            None => format!("<x:{} xmlns:x=\"{}\"/>", self.name, escape(&self.namespace)),
+        }
+    }
+
+    /// 値を持つ要素（`<d:getetag>...</d:getetag>`）
+    fn element(&self, content: &str) -> String {
+        match prefix(&self.namespace) {
+            Some(prefix) => format!(
+                "<{p}:{n}>{c}</{p}:{n}>",
+                p = prefix,
+                n = self.name,
+                c = content
+            ),
+            None => format!(
+                "<x:{n} xmlns:x=\"{ns}\">{c}</x:{n}>",
+                n = self.name,
+                ns = escape(&self.namespace),
+                c = content
+            ),
+        }
+    }
+}
+
+/// multistatus で宣言する名前空間の接頭辞
+fn prefix(namespace: &str) -> Option<&'static str> {
+    match namespace {
+        DAV => Some("d"),
+        CALDAV => Some("c"),
+        CALENDAR_SERVER => Some("cs"),
+        _ => None,
+    }
+}
+
+/// 返すプロパティ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropRequest {
+    /// `allprop`（またはボディなし）。calendar-data は含めない
+    All,
+    Names(Vec<PropName>),
+}
+
+/// REPORT の種類
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Report {
+    /// calendar-query（コンポーネントの絞り込みだけを見る。`None` は絞り込みなし）
+    Query {
+        props: PropRequest,
+        component: Option<String>,
+    },
+    /// calendar-multiget（`hrefs` のリソースを返す）
+    Multiget {
+        props: PropRequest,
+        hrefs: Vec<String>,
+    },
+}
+
+/// XMLとして読めない、または対応していないリクエスト
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestError {
+    Invalid(String),
+    UnsupportedReport,
+}
+
+fn parse_document(body: &str) -> Result<Document<'_>, RequestError> {
+    Document::parse(body).map_err(|e| RequestError::Invalid(e.to_string()))
+}
+
+fn is(node: &Node, namespace: &str, name: &str) -> bool {
+    node.is_element()
+        && node.tag_name().name() == name
+        && node.tag_name().namespace() == Some(namespace)
+}
+
+fn child<'a, 'input>(
+    node: &Node<'a, 'input>,
+    namespace: &str,
+    name: &str,
+) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| is(child, namespace, name))
+}
+
+/// `<d:prop>` などの直下の要素の名前
+fn prop_names(node: &Node) -> Vec<PropName> {
+    node.children()
+        .filter(Node::is_element)
+        .map(|child| {
+            PropName::new(
+                child.tag_name().namespace().unwrap_or_default(),
+                child.tag_name().name(),
+            )
+        })
+        .collect()
+}
+
+/// `<d:prop>` か `<d:allprop>` を読む（どちらもなければ `allprop`）
+fn prop_request(node: &Node) -> PropRequest {
+    match child(node, DAV, "prop") {
+        Some(prop) => PropRequest::Names(prop_names(&prop)),
+        None => PropRequest::All,
+    }
+}
+
+/// PROPFIND のボディ（空なら `allprop`）
+pub fn parse_propfind(body: &str) -> Result<PropRequest, RequestError> {
+    if body.trim().is_empty() {
+        return Ok(PropRequest::All);
+    }
+    let document = parse_document(body)?;
+    let root = document.root_element();
+    if !is(&root, DAV, "propfind") {
+        return Err(RequestError::Invalid("propfind ではありません".to_string()));
+    }
+    Ok(prop_request(&root))
+}
+
+/// REPORT のボディ
+pub fn parse_report(body: &str) -> Result<Report, RequestError> {
+    let document = parse_document(body)?;
+    let root = document.root_element();
+    if is(&root, CALDAV, "calendar-multiget") {
+        let hrefs = root
+            .children()
+            .filter(|child| is(child, DAV, "href"))
+            .filter_map(|href| href.text())
+            .map(|href| href.trim().to_string())
+            .collect();
+        return Ok(Report::Multiget {
+            props: prop_request(&root),
+            hrefs,
+        });
+    }
+    if is(&root, CALDAV, "calendar-query") {
+        // <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/>
+        let component = child(&root, CALDAV, "filter")
+            .and_then(|filter| child(&filter, CALDAV, "comp-filter"))
+            .and_then(|calendar| child(&calendar, CALDAV, "comp-filter"))
+            .and_then(|component| component.attribute("name"))
+            .map(str::to_ascii_uppercase);
+        return Ok(Report::Query {
+            props: prop_request(&root),
+            component,
+        });
+    }
+    Err(RequestError::UnsupportedReport)
+}
+
+/// 1つのリソースの `<d:response>`
+/// `found` は値のあるプロパティ、`missing` はこのリソースにないプロパティ
+pub fn response(href: &str, found: &[(PropName, String)], missing: &[PropName]) -> String {
+    let mut xml = format!("<d:response><d:href>{}</d:href>", escape(href));
+    if !found.is_empty() {
+        xml.push_str("<d:propstat><d:prop>");
+        for (name, content) in found {
+            if content.is_empty() {
+                xml.push_str(&name.empty_element());
+            } else {
+                xml.push_str(&name.element(content));
+            }
+        }
+        xml.push_str("</d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat>");
+    }
+    if !missing.is_empty() {
+        xml.push_str("<d:propstat><d:prop>");
+        for name in missing {
+            xml.push_str(&name.empty_element());
+        }
+        xml.push_str("</d:prop><d:status>HTTP/1.1 404 Not Found</d:status></d:propstat>");
+    }
+    xml.push_str("</d:response>");
+    xml
+}
+
+/// 見つからなかったリソースの `<d:response>`（calendar-multiget 用）
+pub fn not_found(href: &str) -> String {
+    format!(
+        "<d:response><d:href>{}</d:href><d:status>HTTP/1.1 404 Not Found</d:status></d:response>",
+        escape(href)
+    )
+}
+
+/// `<d:response>` をまとめた multistatus の文書
+pub fn multistatus(responses: &[String]) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <d:multistatus xmlns:d=\"{}\" xmlns:c=\"{}\" xmlns:cs=\"{}\">{}</d:multistatus>",
+        DAV,
+        CALDAV,
+        CALENDAR_SERVER,
+        responses.concat()
+    )
+}
+
+/// 条件を満たさなかった理由（`<d:error><c:valid-calendar-data/></d:error>` など）
+pub fn error(condition: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <d:error xmlns:d=\"{}\" xmlns:c=\"{}\">{}</d:error>",
+        DAV, CALDAV, condition
+    )
+}
+
+/// `<d:href>` の要素
+pub fn href(path: &str) -> String {
+    format!("<d:href>{}</d:href>", escape(path))
+}
+
+/// テキストと属性値のエスケープ
+pub fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_reports_and_writes_multistatus() {
+        let query = r#"<?xml version="1.0"?>
+            <C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+              <D:prop><D:getetag/><C:calendar-data/></D:prop>
+              <C:filter><C:comp-filter name="VCALENDAR"><C:comp-filter name="VTODO"/></C:comp-filter></C:filter>
+            </C:calendar-query>"#;
+        assert_eq!(
+            parse_report(query),
+            Ok(Report::Query {
+                props: PropRequest::Names(vec![
+                    PropName::new(DAV, "getetag"),
+                    PropName::new(CALDAV, "calendar-data"),
+                ]),
+                component: Some("VTODO".to_string()),
+            })
+        );
+
+        let multiget = r#"<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+              <d:prop><d:getetag/></d:prop>
+              <d:href>/dav/todos/a.ics</d:href><d:href> /dav/todos/b.ics </d:href>
+            </c:calendar-multiget>"#;
+        assert!(matches!(
+            parse_report(multiget),
+            Ok(Report::Multiget { hrefs, .. }) if hrefs == ["/dav/todos/a.ics", "/dav/todos/b.ics"]
+        ));
+        let sync = r#"<d:sync-collection xmlns:d="DAV:"><d:sync-token/></d:sync-collection>"#;
+        assert_eq!(parse_report(sync), Err(RequestError::UnsupportedReport));
+        assert_eq!(parse_propfind(""), Ok(PropRequest::All));
+        assert!(matches!(
+            parse_propfind("<a"),
+            Err(RequestError::Invalid(_))
+        ));
+
+        // 知らない名前空間のプロパティは、その場で名前空間を宣言する
+        let xml = multistatus(&[response(
+            "/dav/todos/a&b.ics",
+            &[(PropName::new(DAV, "getetag"), "\"1\"".to_string())],
+            &[PropName::new("http://apple.com/ns/ical/", "calendar-color")],
+        )]);
+        let document = Document::parse(&xml).unwrap();
+        let missing = document
+            .descendants()
+            .find(|node| node.tag_name().name() == "calendar-color")
+            .unwrap();
+        assert_eq!(
+            missing.tag_name().namespace(),
+            Some("http://apple.com/ns/ical/")
+        );
+        assert!(xml.contains("<d:href>/dav/todos/a&amp;b.ics</d:href>"));
+        assert!(xml.contains("<d:getetag>\"1\"</d:getetag>"));
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::dataloader::Loader;
+
+use crate::application::todo::dto::TodoResponseDto;
+use crate::application::todo::TodoService;
+use crate::domain::todo::TodoError;
+
+/// IDからTodoを取得するローダー
+/// 同じリクエストの中で要求されたIDをまとめ、1回のクエリで取得する（N+1クエリを避ける）。
+/// 結果はキャッシュしないため、ミューテーションの後に古い値を返すことはない
+pub struct TodoLoader {
+    service: Arc<TodoService>,
+}
+
+impl TodoLoader {
+    pub fn new(service: Arc<TodoService>) -> Self {
+        Self { service }
+    }
+}
+
+impl Loader<i32> for TodoLoader {
+    type Value = TodoResponseDto;
+    type Error = TodoError;
+
+    async fn load(&self, ids: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+        let todos = self.service.get_todos_by_ids(ids).await?;
+        Ok(todos.into_iter().map(|todo| (todo.id, todo)).collect())
+    }
+}
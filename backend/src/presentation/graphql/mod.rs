@@ -0,0 +1,247 @@
+//! GraphQL API（`/api/graphql`）
+//!
+//! RESTと同じ `TodoService` の上に、クエリ・ミューテーション・サブスクリプションを提供する。
+//! サブスクリプションは `Accept: text/event-stream` を付けた POST に対して
+//! Server-Sent Events（graphql-sse の distinct connections mode）で返す。
+
+mod loader;
+mod schema;
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::http::header::{self, CacheControl, CacheDirective};
+use actix_web::web::{self, Bytes};
+use actix_web::{HttpRequest, HttpResponse};
+use async_graphql::dataloader::DataLoader;
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{BatchRequest, Request, Response, Schema};
+use fluent_bundle::FluentArgs;
+use futures_util::stream::{self, BoxStream, Stream, StreamExt};
+use tokio::time::timeout;
+
+use crate::application::todo::TodoService;
+use crate::config::GraphqlConfig;
+use crate::presentation::api::error::{self, ErrorCode};
+use crate::presentation::api::events_handler::KEEP_ALIVE;
+use crate::presentation::i18n::Locale;
+use loader::TodoLoader;
+use schema::{MutationRoot, QueryRoot, SubscriptionRoot};
+
+pub type TodoSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+/// GraphQLのエンドポイント
+const ENDPOINT: &str = "/api/graphql";
+
+/// GraphiQLの画面用のCSP（スクリプトとスタイルをunpkgから読み込む）
+const GRAPHIQL_CSP: &str = "default-src 'none'; script-src 'unsafe-inline' https://unpkg.com; \
+     style-src 'unsafe-inline' https://unpkg.com; font-src https://unpkg.com data:; \
+     img-src https://graphql.org data:; connect-src 'self'; frame-ancestors 'none'";
+
+/// スキーマを組み立てる（深さ・複雑さの上限を超えるクエリは実行しない）
+pub fn schema(service: Arc<TodoService>, config: &GraphqlConfig) -> TodoSchema {
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(service)
+        .limit_depth(config.max_depth)
+        .limit_complexity(config.max_complexity)
+        .finish()
+}
+
+/// リクエストごとのデータ（言語とローダー）を付ける
+fn prepare(request: Request, service: &Arc<TodoService>, locale: Locale) -> Request {
+    request.data(locale).data(DataLoader::new(
+        TodoLoader::new(service.clone()),
+        tokio::spawn,
+    ))
+}
+
+/// GraphQLのリクエストを実行
+/// 配列で送られた場合はまとめて実行し（`graphql.max_batch_size` 件まで）、
+/// `Accept: text/event-stream` の場合は結果をストリームで返す
+pub async fn execute(
+    req: HttpRequest,
+    schema: web::Data<TodoSchema>,
+    service: web::Data<Arc<TodoService>>,
+    config: web::Data<GraphqlConfig>,
+    locale: Locale,
+    body: web::Json<BatchRequest>,
+) -> HttpResponse {
+    let accepts_stream = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/event-stream"));
+
+    match body.into_inner() {
+        BatchRequest::Single(request) if accepts_stream => {
+            let responses = schema.execute_stream(prepare(request, &service, locale));
+            HttpResponse::Ok()
+                .content_type("text/event-stream")
+                .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+                .insert_header(("x-accel-buffering", "no"))
+                .streaming(event_stream(responses, KEEP_ALIVE))
+        }
+        BatchRequest::Single(request) => {
+            HttpResponse::Ok().json(schema.execute(prepare(request, &service, locale)).await)
+        }
+        BatchRequest::Batch(requests) if requests.len() > config.max_batch_size => {
+            let mut args = FluentArgs::new();
+            args.set("max", config.max_batch_size);
+            error::respond(
+                locale,
+                error::localized(locale, ErrorCode::BatchTooLarge, Some(&args)),
+            )
+        }
+        BatchRequest::Batch(requests) => {
+            let requests = requests
+                .into_iter()
+                .map(|request| prepare(request, &service, locale))
+                .collect();
+            HttpResponse::Ok().json(schema.execute_batch(BatchRequest::Batch(requests)).await)
+        }
+    }
+}
+
+/// 実行結果をServer-Sent Eventsの形式にする（結果ごとに `next`、終わりに `complete`）
+fn event_stream(
+    responses: BoxStream<'static, Response>,
+    keep_alive: Duration,
+) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    stream::unfold(Some(responses), move |responses| async move {
+        let mut responses = responses?;
+        let frame = match timeout(keep_alive, responses.next()).await {
+            Ok(Some(response)) => format!(
+                "event: next\ndata: {}\n\n",
+                serde_json::to_string(&response).unwrap_or_default()
+            ),
+            Ok(None) => return Some((Ok(Bytes::from("event: complete\ndata:\n\n")), None)),
+            Err(_) => ": keep-alive\n\n".to_string(),
+        };
+        Some((Ok(Bytes::from(frame)), Some(responses)))
+    })
+}
+
+/// GraphiQL（`graphql.playground` が有効な場合のみ）
+pub async fn graphiql(config: web::Data<GraphqlConfig>) -> HttpResponse {
+    if !config.playground {
+        return HttpResponse::NotFound().finish();
+    }
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .insert_header((header::CONTENT_SECURITY_POLICY, GRAPHIQL_CSP))
+        .body(
+            GraphiQLSource::build()
+                .endpoint(ENDPOINT)
+                .title("Todo GraphQL")
+                .finish(),
+        )
+}
+
+/// ルーティング設定（バージョン付きのAPIとは別に `/api/graphql` に置く）
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource(ENDPOINT)
+            .route(web::post().to(execute))
+            .route(web::get().to(graphiql)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::todo::Todo;
+    use crate::infrastructure::persistence::memory::MemoryTodoRepository;
+    use actix_web::{test, App};
+
+    fn todo(id: i32) -> Todo {
+        Todo::reconstruct(
+            id,
+            format!("Todo {}", id),
+            false,
+            id as f64,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[actix_web::test]
+    async fn test_batches_lookups_and_rejects_complex_queries() {
+        let repository = Arc::new(MemoryTodoRepository::with_todos((1..=3).map(todo)));
+        let service = Arc::new(TodoService::new(repository.clone()));
+        let config = GraphqlConfig {
+            max_complexity: 50,
+            max_batch_size: 2,
+            ..GraphqlConfig::default()
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(schema(service.clone(), &config)))
+                .app_data(web::Data::new(service))
+                .app_data(web::Data::new(config))
+                .configure(super::config),
+        )
+        .await;
+        let post = |query: &str| {
+            test::TestRequest::post()
+                .uri(ENDPOINT)
+                .insert_header((header::ACCEPT_LANGUAGE, "ja"))
+                .set_json(serde_json::json!({ "query": query }))
+                .to_request()
+        };
+
+        // 同じクエリ内の `todo` は1回の取得にまとめる
+        let body: serde_json::Value = test::call_and_read_body_json(
+            &app,
+            post("{ a: todo(id: 1) { title } b: todo(id: 3) { title } c: todo(id: 9) { title } }"),
+        )
+        .await;
+        assert_eq!(body["data"]["a"]["title"], "Todo 1");
+        assert_eq!(body["data"]["b"]["title"], "Todo 3");
+        assert!(body["data"]["c"].is_null());
+        assert_eq!(repository.find_by_ids_calls(), 1);
+        assert_eq!(repository.find_by_id_calls(), 0);
+
+        // ユースケースのエラーはRESTと同じコードを返す
+        let body: serde_json::Value = test::call_and_read_body_json(
+            &app,
+            post("mutation { updateTodo(id: 9, input: { title: \"x\" }) { id } }"),
+        )
+        .await;
+        assert_eq!(body["errors"][0]["extensions"]["code"], "todo_not_found");
+        assert_eq!(body["errors"][0]["message"], "Todoが見つかりません");
+
+        // 件数 × フィールド数で見積もった複雑さが上限を超えれば実行しない
+        let body: serde_json::Value =
+            test::call_and_read_body_json(&app, post("{ todos(first: 20) { id title due } }"))
+                .await;
+        assert!(body["data"].is_null());
+        assert!(body["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("too complex"));
+
+        // 上限までの操作はまとめて実行し、超えた場合は何も実行しない
+        let batch = |size: usize| {
+            let operations: Vec<_> = (0..size)
+                .map(|_| serde_json::json!({ "query": "{ todo(id: 1) { title } }" }))
+                .collect();
+            test::TestRequest::post()
+                .uri(ENDPOINT)
+                .insert_header((header::ACCEPT_LANGUAGE, "ja"))
+                .set_json(operations)
+                .to_request()
+        };
+        let body: serde_json::Value = test::call_and_read_body_json(&app, batch(2)).await;
+        assert_eq!(body[1]["data"]["todo"]["title"], "Todo 1");
+
+        let calls = repository.find_by_ids_calls();
+        let resp = test::call_service(&app, batch(3)).await;
+        assert_eq!(resp.status(), 400);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"], "batch_too_large");
+        assert_eq!(body["message"], "まとめて実行できる操作は2件までです");
+        assert_eq!(repository.find_by_ids_calls(), calls);
+    }
+}
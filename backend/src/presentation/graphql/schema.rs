@@ -0,0 +1,315 @@
+use std::sync::Arc;
+
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{
+    Context, Enum, ErrorExtensions, InputObject, Object, Result, SimpleObject, Subscription,
+};
+use futures_util::stream::{self, Stream};
+use tokio::sync::broadcast::error::RecvError;
+
+use super::loader::TodoLoader;
+use crate::application::todo::dto::{
+    CreateTodoDto, MoveTodoDto, TodoResponseDto, TodoStatsDto, UpdateTodoDto,
+};
+use crate::application::todo::TodoService;
+use crate::domain::todo::{TodoError, TodoEvent};
+use crate::presentation::api::error;
+use crate::presentation::i18n::Locale;
+
+fn service<'a>(ctx: &Context<'a>) -> Result<&'a Arc<TodoService>> {
+    ctx.data::<Arc<TodoService>>()
+}
+
+fn loader<'a>(ctx: &Context<'a>) -> Result<&'a DataLoader<TodoLoader>> {
+    ctx.data::<DataLoader<TodoLoader>>()
+}
+
+/// ユースケースのエラーをGraphQLのエラーに変換
+/// RESTと同じエラーコードを `extensions.code` に入れ、メッセージはリクエストの言語にする
+fn todo_error(ctx: &Context<'_>, err: TodoError) -> async_graphql::Error {
+    let locale = ctx.data_opt::<Locale>().copied().unwrap_or(Locale::DEFAULT);
+    let code = error::todo_error_code(err);
+    async_graphql::Error::new(locale.message(&code.message_id(), None))
+        .extend_with(|_, extensions| extensions.set("code", code.as_str()))
+}
+
+/// Todo
+pub struct Todo(TodoResponseDto);
+
+#[Object]
+impl Todo {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn completed(&self) -> bool {
+        self.0.completed
+    }
+
+    /// 並び順（昇順に並べる）。値そのものに意味はなく、並べ替えのたびに変わりうる
+    async fn position(&self) -> f64 {
+        self.0.position
+    }
+
+    /// 作成日時（RFC 3339）
+    async fn created_at(&self) -> Option<&str> {
+        self.0.created_at.as_deref()
+    }
+
+    /// 期限（`YYYY-MM-DD` か、UTCの RFC 3339 の日時）
+    async fn due(&self) -> Option<&str> {
+        self.0.due.as_deref()
+    }
+
+    /// 完了日時（RFC 3339）
+    async fn completed_at(&self) -> Option<&str> {
+        self.0.completed_at.as_deref()
+    }
+}
+
+/// 状態別のTodo件数
+#[derive(SimpleObject)]
+pub struct TodoStats {
+    total: usize,
+    open: usize,
+    completed: usize,
+}
+
+impl From<TodoStatsDto> for TodoStats {
+    fn from(dto: TodoStatsDto) -> Self {
+        Self {
+            total: dto.total,
+            open: dto.open,
+            completed: dto.completed,
+        }
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object(name = "Query")]
+impl QueryRoot {
+    /// Todoを並び順に取得（`first` は省略時100件、最大500件）
+    #[graphql(complexity = "first as usize * child_complexity")]
+    async fn todos(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "完了状態で絞り込む")] completed: Option<bool>,
+        #[graphql(desc = "タイトルに含まれる文字列（大文字・小文字を区別しない）")] search: Option<
+            String,
+        >,
+        #[graphql(default = 100, validator(minimum = 1, maximum = 500))] first: i32,
+        #[graphql(default, validator(minimum = 0))] offset: i32,
+    ) -> Result<Vec<Todo>> {
+        let todos = service(ctx)?
+            .get_all_todos()
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        let search = search.map(|search| search.to_lowercase());
+        Ok(todos
+            .into_iter()
+            .filter(|todo| completed.is_none_or(|completed| todo.completed == completed))
+            .filter(|todo| {
+                search
+                    .as_ref()
+                    .is_none_or(|search| todo.title.to_lowercase().contains(search))
+            })
+            .skip(offset as usize)
+            .take(first as usize)
+            .map(Todo)
+            .collect())
+    }
+
+    /// IDでTodoを取得（同じクエリ内の複数の `todo` はまとめて取得する）
+    async fn todo(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Todo>> {
+        let todo = loader(ctx)?
+            .load_one(id)
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        Ok(todo.map(Todo))
+    }
+
+    /// 複数のIDのTodoを指定した順に取得（存在しないIDは含めない）
+    #[graphql(complexity = "ids.len() * child_complexity")]
+    async fn todos_by_ids(&self, ctx: &Context<'_>, ids: Vec<i32>) -> Result<Vec<Todo>> {
+        let mut todos = loader(ctx)?
+            .load_many(ids.iter().copied())
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| todos.remove(id))
+            .map(Todo)
+            .collect())
+    }
+
+    /// 状態別のTodo件数
+    async fn stats(&self, ctx: &Context<'_>) -> Result<TodoStats> {
+        let stats = service(ctx)?
+            .get_stats()
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        Ok(stats.into())
+    }
+}
+
+/// Todo作成の入力
+#[derive(InputObject)]
+pub struct CreateTodoInput {
+    /// 空白のみは不可
+    title: String,
+    #[graphql(default)]
+    completed: bool,
+    /// 期限（`YYYY-MM-DD` か、オフセット付きの RFC 3339 の日時）
+    due: Option<String>,
+}
+
+/// Todo更新の入力（指定した項目だけを更新する）
+#[derive(InputObject)]
+pub struct UpdateTodoInput {
+    title: Option<String>,
+    completed: Option<bool>,
+    /// 期限（`YYYY-MM-DD` か、オフセット付きの RFC 3339 の日時）。空文字列で期限なしにする
+    due: Option<String>,
+}
+
+pub struct MutationRoot;
+
+#[Object(name = "Mutation")]
+impl MutationRoot {
+    /// Todoを作成
+    async fn create_todo(&self, ctx: &Context<'_>, input: CreateTodoInput) -> Result<Todo> {
+        let dto = CreateTodoDto {
+            title: input.title,
+            completed: input.completed,
+            due: input.due,
+        };
+        let todo = service(ctx)?
+            .create_todo(dto)
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        Ok(Todo(todo))
+    }
+
+    /// Todoを更新
+    async fn update_todo(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdateTodoInput,
+    ) -> Result<Todo> {
+        let dto = UpdateTodoDto {
+            title: input.title,
+            completed: input.completed,
+            due: input.due,
+        };
+        let todo = service(ctx)?
+            .update_todo(id, dto)
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        Ok(Todo(todo))
+    }
+
+    /// Todoを別のTodoの直前（`before`）か直後（`after`）に移動
+    async fn move_todo(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        before: Option<i32>,
+        after: Option<i32>,
+    ) -> Result<Todo> {
+        let todo = service(ctx)?
+            .move_todo(id, MoveTodoDto { before, after })
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        Ok(Todo(todo))
+    }
+
+    /// Todoを削除し、削除したIDを返す
+    async fn delete_todo(&self, ctx: &Context<'_>, id: i32) -> Result<i32> {
+        service(ctx)?
+            .delete_todo(id)
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        Ok(id)
+    }
+}
+
+/// 変更の種類
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+pub enum TodoEventKind {
+    /// 作成・更新された
+    Changed,
+    Deleted,
+    /// 通知を取りこぼしたため、一覧を取得し直す必要がある
+    Resync,
+}
+
+/// Todoの変更の通知
+pub struct TodoChange {
+    kind: TodoEventKind,
+    id: Option<i32>,
+}
+
+impl From<TodoEvent> for TodoChange {
+    fn from(event: TodoEvent) -> Self {
+        match event {
+            TodoEvent::Saved(todo) => Self {
+                kind: TodoEventKind::Changed,
+                id: todo.id(),
+            },
+            TodoEvent::Deleted(id) => Self {
+                kind: TodoEventKind::Deleted,
+                id: Some(id),
+            },
+        }
+    }
+}
+
+#[Object(name = "TodoEvent")]
+impl TodoChange {
+    async fn kind(&self) -> TodoEventKind {
+        self.kind
+    }
+
+    async fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// 変更後のTodo（`CHANGED` の場合のみ）
+    async fn todo(&self, ctx: &Context<'_>) -> Result<Option<Todo>> {
+        let Some(id) = self.id.filter(|_| self.kind == TodoEventKind::Changed) else {
+            return Ok(None);
+        };
+        let todo = loader(ctx)?
+            .load_one(id)
+            .await
+            .map_err(|e| todo_error(ctx, e))?;
+        Ok(todo.map(Todo))
+    }
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription(name = "Subscription")]
+impl SubscriptionRoot {
+    /// Todoの変更を購読（同じプロセスでの変更だけが届く）
+    async fn todo_events(&self, ctx: &Context<'_>) -> Result<impl Stream<Item = TodoChange>> {
+        let events = service(ctx)?.subscribe();
+        Ok(stream::unfold(events, |mut events| async move {
+            let change = match events.recv().await {
+                Ok(event) => event.into(),
+                Err(RecvError::Lagged(_)) => TodoChange {
+                    kind: TodoEventKind::Resync,
+                    id: None,
+                },
+                Err(RecvError::Closed) => return None,
+            };
+            Some((change, events))
+        }))
+    }
+}
@@ -0,0 +1,169 @@
+//! APIの多言語対応
+//!
+//! エラーメッセージはFluentのカタログ（`backend/locales/*.ftl`）から、
+//! リクエストの `Accept-Language` で選んだ言語で返す。
+
+use std::future::{ready, Ready};
+use std::sync::OnceLock;
+
+use actix_web::dev::{Payload, RequestHead};
+use actix_web::http::header;
+use actix_web::{FromRequest, HttpRequest};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use fluent_langneg::{accepted_languages, negotiate_languages, NegotiationStrategy};
+use unic_langid::{langid, LanguageIdentifier};
+
+/// 対応している言語
+/// 追加する場合は `locales/` にカタログを置き、列挙子と `ALL`・`source` を追加する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Ja,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Ja];
+
+    /// `Accept-Language` がない場合や、対応していない言語だけが指定された場合の言語
+    pub const DEFAULT: Locale = Locale::En;
+
+    /// 言語タグ（`Content-Language` に使う）
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ja => "ja",
+        }
+    }
+
+    fn langid(self) -> LanguageIdentifier {
+        match self {
+            Locale::En => langid!("en"),
+            Locale::Ja => langid!("ja"),
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../../locales/en.ftl"),
+            Locale::Ja => include_str!("../../locales/ja.ftl"),
+        }
+    }
+
+    /// `Accept-Language` の値から言語を選ぶ（`ja-JP` は `ja` に、品質値の高いものを優先する）
+    pub fn negotiate(accept_language: Option<&str>) -> Locale {
+        let requested = accept_language
+            .map(accepted_languages::parse)
+            .unwrap_or_default();
+        let available = Self::ALL.map(Self::langid);
+        let default = Self::DEFAULT.langid();
+        let supported = negotiate_languages(
+            &requested,
+            &available,
+            Some(&default),
+            NegotiationStrategy::Lookup,
+        );
+        supported
+            .first()
+            .and_then(|id| Self::ALL.into_iter().find(|locale| locale.langid() == **id))
+            .unwrap_or(Self::DEFAULT)
+    }
+
+    /// リクエストヘッダーから言語を選ぶ（ミドルウェアなどエクストラクターを使えない場所用）
+    pub fn from_head(head: &RequestHead) -> Locale {
+        Self::negotiate(
+            head.headers
+                .get(header::ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok()),
+        )
+    }
+
+    /// カタログのメッセージ（IDが見つからなければIDをそのまま返す）
+    pub fn message(self, id: &str, args: Option<&FluentArgs>) -> String {
+        let bundle = &bundles()[self as usize];
+        let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+            tracing::warn!(
+                id,
+                locale = self.as_str(),
+                "メッセージがカタログにありません"
+            );
+            return id.to_string();
+        };
+        let mut errors = Vec::new();
+        let message = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            tracing::warn!(id, ?errors, "メッセージの整形に失敗しました");
+        }
+        message.into_owned()
+    }
+}
+
+/// 言語ごとのカタログ（`Locale` の並びと同じ順）
+fn bundles() -> &'static [FluentBundle<FluentResource>; 2] {
+    static BUNDLES: OnceLock<[FluentBundle<FluentResource>; 2]> = OnceLock::new();
+    BUNDLES.get_or_init(|| {
+        Locale::ALL.map(|locale| {
+            let resource = FluentResource::try_new(locale.source().to_string())
+                .expect("メッセージカタログの構文が不正です");
+            let mut bundle = FluentBundle::new_concurrent(vec![locale.langid()]);
+            // 引数の前後に方向制御文字を入れない（メッセージはJSONでそのまま返す）
+            bundle.set_use_isolating(false);
+            bundle
+                .add_resource(resource)
+                .expect("メッセージIDが重複しています");
+            bundle
+        })
+    })
+}
+
+/// ハンドラーの引数で受け取る
+impl FromRequest for Locale {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Ok(Self::from_head(req.head())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api_types::ErrorCode;
+
+    #[test]
+    fn test_negotiates_locale_and_formats_every_error_code() {
+        assert_eq!(Locale::negotiate(None), Locale::En);
+        assert_eq!(Locale::negotiate(Some("ja-JP,ja;q=0.9")), Locale::Ja);
+        assert_eq!(
+            Locale::negotiate(Some("fr, ja;q=0.8, en;q=0.5")),
+            Locale::Ja
+        );
+        assert_eq!(Locale::negotiate(Some("de")), Locale::En);
+        assert_eq!(Locale::negotiate(Some("not a language tag")), Locale::En);
+
+        let mut args = FluentArgs::new();
+        args.set("seconds", 3);
+        assert_eq!(
+            Locale::En.message("error-rate-limited", Some(&args)),
+            "Too many requests. Please retry in 3 seconds."
+        );
+        assert_eq!(
+            Locale::Ja.message("error-rate-limited", Some(&args)),
+            "リクエストが多すぎます。3秒後に再試行してください"
+        );
+
+        // すべてのエラーコードにすべての言語のメッセージがある
+        for locale in Locale::ALL {
+            for code in ErrorCode::ALL {
+                let id = code.message_id();
+                assert!(
+                    bundles()[locale as usize].has_message(&id),
+                    "{} に {} がありません",
+                    locale.as_str(),
+                    id
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use actix_cors::Cors;
+
+use crate::config::CorsConfig;
+
+/// 設定からCORSミドルウェアを構築
+/// `allowed_origins` が空の場合はクロスオリジンのリクエストをすべて拒否する
+pub fn cors(config: &CorsConfig) -> Cors {
+    let cors = if config.allows_any_origin() {
+        Cors::default().allow_any_origin()
+    } else {
+        config
+            .allowed_origins
+            .iter()
+            .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
+    };
+
+    let cors = cors
+        .allowed_methods(config.allowed_methods.iter().map(String::as_str))
+        .allowed_headers(config.allowed_headers.iter().map(String::as_str))
+        .expose_headers(config.expose_headers.iter().map(String::as_str))
+        .max_age(config.max_age_secs);
+
+    if config.allow_credentials {
+        cors.supports_credentials()
+    } else {
+        cors
+    }
+}
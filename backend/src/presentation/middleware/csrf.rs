@@ -0,0 +1,137 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+use uuid::Uuid;
+
+use crate::config::CsrfConfig;
+use crate::presentation::api::error::{self, ErrorCode};
+use crate::presentation::i18n::Locale;
+
+/// ダブルサブミットCookie方式のCSRF対策ミドルウェア
+///
+/// セッションCookieを持つリクエストにCSRFトークンのCookieを発行し、
+/// 書き込みリクエストでは同じ値がヘッダーで送り返されていることを確認する。
+/// セッションCookieを持たないリクエスト（Bearerトークンなど）は対象外
+pub async fn csrf(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let config = match req.app_data::<web::Data<CsrfConfig>>() {
+        Some(config) if config.enabled && req.cookie(&config.session_cookie).is_some() => {
+            config.clone()
+        }
+        _ => {
+            return next
+                .call(req)
+                .await
+                .map(ServiceResponse::map_into_boxed_body)
+        }
+    };
+
+    let cookie_token = req
+        .cookie(&config.cookie_name)
+        .map(|cookie| cookie.value().to_string());
+
+    if !is_safe_method(req.method()) {
+        let header_token = req
+            .headers()
+            .get(config.header_name.as_str())
+            .and_then(|value| value.to_str().ok());
+        let valid = matches!(
+            (cookie_token.as_deref(), header_token),
+            (Some(cookie), Some(header)) if !cookie.is_empty() && constant_time_eq(cookie, header)
+        );
+        if !valid {
+            tracing::warn!("CSRFトークンが一致しません");
+            let res = error::error(Locale::from_head(req.head()), ErrorCode::CsrfTokenMismatch);
+            return Ok(req.into_response(res));
+        }
+    }
+
+    let secure = req.connection_info().scheme() == "https";
+    let mut res = next.call(req).await?.map_into_boxed_body();
+
+    if cookie_token.is_none() {
+        // JavaScriptから読み取ってヘッダーに載せるため HttpOnly にはしない
+        let cookie = Cookie::build(
+            config.cookie_name.clone(),
+            Uuid::new_v4().simple().to_string(),
+        )
+        .path("/")
+        .same_site(SameSite::Strict)
+        .secure(secure)
+        .finish();
+        res.response_mut().add_cookie(&cookie)?;
+    }
+
+    Ok(res)
+}
+
+fn is_safe_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// 比較にかかる時間から一致した長さを推測されないよう、常に全体を比較する
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::middleware::from_fn;
+    use actix_web::{test, App, HttpResponse};
+
+    #[actix_web::test]
+    async fn test_requires_token_for_cookie_sessions() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(CsrfConfig::default()))
+                .wrap(from_fn(csrf))
+                .route("/api/todos", web::get().to(HttpResponse::Ok))
+                .route("/api/todos", web::post().to(HttpResponse::Created)),
+        )
+        .await;
+        let session = Cookie::new("session", "abc");
+
+        // Cookieセッションのない書き込みは対象外
+        let req = test::TestRequest::post().uri("/api/todos").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 201);
+
+        // 読み取りでトークンが発行される
+        let req = test::TestRequest::get()
+            .uri("/api/todos")
+            .cookie(session.clone())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let token = res
+            .response()
+            .cookies()
+            .find(|c| c.name() == "csrf_token")
+            .unwrap()
+            .value()
+            .to_string();
+
+        let req = test::TestRequest::post()
+            .uri("/api/todos")
+            .cookie(session.clone())
+            .cookie(Cookie::new("csrf_token", token.clone()))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+        let req = test::TestRequest::post()
+            .uri("/api/todos")
+            .cookie(session)
+            .cookie(Cookie::new("csrf_token", token.clone()))
+            .insert_header(("x-csrf-token", token))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 201);
+    }
+}
@@ -0,0 +1,78 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::infrastructure::Metrics;
+
+/// HTTPリクエスト数と処理時間をルート・ステータス別に記録するミドルウェア
+/// ラベルの種類が増えすぎないよう、パスではなくルート定義（例: `/api/v1/todos/{id}`）を使う
+pub async fn http_metrics(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let metrics = req.app_data::<web::Data<Arc<Metrics>>>().cloned();
+    let method = req.method().to_string();
+    let started = Instant::now();
+
+    let result = next.call(req).await;
+
+    if let Some(metrics) = metrics {
+        let (route, status) = match &result {
+            Ok(res) => (
+                res.request()
+                    .match_pattern()
+                    .unwrap_or_else(|| "unmatched".to_string()),
+                res.status().as_u16(),
+            ),
+            Err(e) => (
+                "unmatched".to_string(),
+                e.as_response_error().status_code().as_u16(),
+            ),
+        };
+        let status = status.to_string();
+        let labels = [method.as_str(), route.as_str(), status.as_str()];
+        metrics.http_requests_total.with_label_values(&labels).inc();
+        metrics
+            .http_request_duration_seconds
+            .with_label_values(&labels)
+            .observe(started.elapsed().as_secs_f64());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::middleware::from_fn;
+    use actix_web::{test, App, HttpResponse};
+
+    #[actix_web::test]
+    async fn test_records_route_pattern() {
+        let metrics = Arc::new(Metrics::new());
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(metrics.clone()))
+                .wrap(from_fn(http_metrics))
+                .route("/todos/{id}", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        for id in [1, 2] {
+            let req = test::TestRequest::get()
+                .uri(&format!("/todos/{}", id))
+                .to_request();
+            test::call_service(&app, req).await;
+        }
+
+        let count = metrics
+            .http_requests_total
+            .with_label_values(&["GET", "/todos/{id}", "200"])
+            .get();
+        assert_eq!(count, 2);
+        assert!(metrics.render().unwrap().contains("http_request_duration_seconds"));
+    }
+}
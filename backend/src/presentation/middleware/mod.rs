@@ -0,0 +1,13 @@
+pub mod cors;
+pub mod csrf;
+pub mod http_metrics;
+pub mod rate_limit;
+pub mod request_tracing;
+pub mod security_headers;
+
+pub use cors::cors;
+pub use csrf::csrf;
+pub use http_metrics::http_metrics;
+pub use rate_limit::rate_limit;
+pub use request_tracing::request_tracing;
+pub use security_headers::security_headers;
@@ -0,0 +1,285 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderName, HeaderValue};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use fluent_bundle::FluentArgs;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use crate::infrastructure::rate_limit::{ClientScope, RateLimitDecision, RateLimiter, RequestKind};
+use crate::presentation::api::error::{self, ErrorCode};
+use crate::presentation::i18n::Locale;
+
+/// レート制限の対象とするパスの接頭辞（ヘルスチェックやメトリクスは対象外）
+const LIMITED_PATH_PREFIX: &str = "/api";
+
+/// APIリクエストをクライアントごとにトークンバケットで制限するミドルウェア
+/// 制限内であれば `RateLimit-*` ヘッダーを付与し、超過時は `Retry-After` 付きの429を返す
+pub async fn rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let limiter = match req.app_data::<web::Data<RateLimiter>>() {
+        Some(limiter)
+            if limiter.config().enabled
+                && req.method() != Method::OPTIONS
+                && req.path().starts_with(LIMITED_PATH_PREFIX) =>
+        {
+            limiter.clone()
+        }
+        _ => {
+            return next
+                .call(req)
+                .await
+                .map(ServiceResponse::map_into_boxed_body)
+        }
+    };
+
+    let kind = if matches!(*req.method(), Method::GET | Method::HEAD) {
+        RequestKind::Read
+    } else {
+        RequestKind::Write
+    };
+    let decision = limiter
+        .check(&clients(&req, limiter.config().trust_forwarded_for), kind)
+        .await;
+
+    if !decision.allowed {
+        let retry_after = decision.retry_after.unwrap_or_default();
+        tracing::warn!(
+            retry_after_secs = ceil_secs(retry_after),
+            "レート制限を超過しました"
+        );
+        let locale = Locale::from_head(req.head());
+        let mut args = FluentArgs::new();
+        args.set("seconds", ceil_secs(retry_after));
+        let mut body = error::localized(locale, ErrorCode::RateLimited, Some(&args));
+        body.retry_after_secs = Some(ceil_secs(retry_after));
+        let mut res = error::respond(locale, body);
+        insert_headers(res.headers_mut(), &decision);
+        res.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(ceil_secs(retry_after)),
+        );
+        return Ok(req.into_response(res));
+    }
+
+    let mut res = next.call(req).await?.map_into_boxed_body();
+    insert_headers(res.headers_mut(), &decision);
+    Ok(res)
+}
+
+/// リクエストを数えるクライアント
+/// IPアドレスは常に数え、APIトークンとユーザーは送られた場合に加えて数える。
+/// トークンやユーザー名はサーバーで検証していないが、制限は厳しくなる方向にしか働かないため、
+/// 値を変えてもIPアドレスの制限は回避できない
+fn clients(req: &ServiceRequest, trust_forwarded_for: bool) -> Vec<(ClientScope, String)> {
+    let mut clients = vec![(ClientScope::Ip, client_ip(req, trust_forwarded_for))];
+    let authorization = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_once(' '));
+    match authorization {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") => {
+            // トークンそのものはメモリに残さない
+            let mut hasher = DefaultHasher::new();
+            token.trim().hash(&mut hasher);
+            clients.push((ClientScope::Token, format!("{:x}", hasher.finish())));
+        }
+        Some((scheme, credentials)) if scheme.eq_ignore_ascii_case("basic") => {
+            let user = STANDARD
+                .decode(credentials.trim())
+                .ok()
+                .and_then(|decoded| String::from_utf8(decoded).ok())
+                .and_then(|decoded| Some(decoded.split_once(':')?.0.to_string()));
+            clients.extend(user.map(|user| (ClientScope::User, user)));
+        }
+        _ => {}
+    }
+    clients
+}
+
+/// クライアントのIPアドレス
+/// プロキシ配下では、プロキシが付けた `X-Forwarded-For` の最後の値を使う。
+/// クライアントが送れる `Forwarded` ヘッダーや、`X-Forwarded-For` の前の方の値は使わない
+fn client_ip(req: &ServiceRequest, trust_forwarded_for: bool) -> String {
+    let forwarded_for = trust_forwarded_for
+        .then(|| req.headers().get(header::X_FORWARDED_FOR)?.to_str().ok())
+        .flatten()
+        .and_then(|value| value.rsplit(',').next())
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty());
+    match forwarded_for {
+        Some(ip) => ip.to_string(),
+        None => req
+            .peer_addr()
+            .map_or_else(|| "unknown".to_string(), |addr| addr.ip().to_string()),
+    }
+}
+
+fn insert_headers(headers: &mut actix_web::http::header::HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(
+        HeaderName::from_static("ratelimit-limit"),
+        HeaderValue::from(decision.limit),
+    );
+    headers.insert(
+        HeaderName::from_static("ratelimit-remaining"),
+        HeaderValue::from(decision.remaining),
+    );
+    headers.insert(
+        HeaderName::from_static("ratelimit-reset"),
+        HeaderValue::from(ceil_secs(decision.reset_after)),
+    );
+}
+
+/// 秒単位に切り上げ
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BucketConfig, RateLimitConfig, ScopedBuckets};
+    use crate::infrastructure::rate_limit::MemoryRateLimitStore;
+    use actix_web::middleware::from_fn;
+    use actix_web::{test, App, HttpResponse};
+    use std::sync::Arc;
+
+    #[actix_web::test]
+    async fn test_returns_429_after_write_budget() {
+        let config = RateLimitConfig {
+            write: BucketConfig {
+                capacity: 1,
+                refill_per_sec: 0.1,
+            },
+            ..Default::default()
+        };
+        let limiter = RateLimiter::new(config, Arc::new(MemoryRateLimitStore::new()));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(limiter))
+                .wrap(from_fn(rate_limit))
+                .route("/api/todos", web::post().to(HttpResponse::Created))
+                .route("/api/todos", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let post = || test::TestRequest::post().uri("/api/todos").to_request();
+        let res = test::call_service(&app, post()).await;
+        assert_eq!(res.status(), 201);
+        assert_eq!(res.headers().get("ratelimit-remaining").unwrap(), "0");
+
+        let res = test::call_service(&app, post()).await;
+        assert_eq!(res.status(), 429);
+        assert_eq!(res.headers().get(header::RETRY_AFTER).unwrap(), "10");
+
+        // トークンを付けてもIPアドレスの上限は回避できない
+        let req = test::TestRequest::post()
+            .uri("/api/todos")
+            .insert_header((header::AUTHORIZATION, "Bearer another"))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 429);
+
+        // 読み取りは別のバケット
+        let req = test::TestRequest::get().uri("/api/todos").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+
+    fn limiter(config: RateLimitConfig) -> web::Data<RateLimiter> {
+        web::Data::new(RateLimiter::new(
+            config,
+            Arc::new(MemoryRateLimitStore::new()),
+        ))
+    }
+
+    fn one_write() -> BucketConfig {
+        BucketConfig {
+            capacity: 1,
+            refill_per_sec: 0.1,
+        }
+    }
+
+    /// プロキシ配下でも、クライアントが送った `Forwarded` や `X-Forwarded-For` の前の方の値では
+    /// 別のクライアントにならない
+    #[actix_web::test]
+    async fn test_ignores_client_supplied_forwarding_headers() {
+        let config = RateLimitConfig {
+            trust_forwarded_for: true,
+            write: one_write(),
+            ..Default::default()
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(limiter(config))
+                .wrap(from_fn(rate_limit))
+                .route("/api/todos", web::post().to(HttpResponse::Created)),
+        )
+        .await;
+        let post = |forwarded: &str, forwarded_for: &str| {
+            test::TestRequest::post()
+                .uri("/api/todos")
+                .insert_header((header::FORWARDED, forwarded.to_string()))
+                .insert_header((header::X_FORWARDED_FOR, forwarded_for.to_string()))
+                .to_request()
+        };
+
+        let res = test::call_service(&app, post("for=192.0.2.1", "203.0.113.7")).await;
+        assert_eq!(res.status(), 201);
+        let res = test::call_service(&app, post("for=192.0.2.2", "192.0.2.3, 203.0.113.7")).await;
+        assert_eq!(res.status(), 429);
+
+        // プロキシが付けた値が違えば別のクライアント
+        let res = test::call_service(&app, post("for=192.0.2.2", "203.0.113.8")).await;
+        assert_eq!(res.status(), 201);
+    }
+
+    /// APIトークンとユーザーはIPアドレスとは別の上限で数える
+    #[actix_web::test]
+    async fn test_limits_each_token_and_user() {
+        let scoped = ScopedBuckets {
+            write: one_write(),
+            ..Default::default()
+        };
+        let config = RateLimitConfig {
+            token: scoped.clone(),
+            user: scoped,
+            ..Default::default()
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(limiter(config))
+                .wrap(from_fn(rate_limit))
+                .route("/api/todos", web::post().to(HttpResponse::Created)),
+        )
+        .await;
+        let post = |authorization: Option<&str>| {
+            let req = test::TestRequest::post().uri("/api/todos");
+            match authorization {
+                Some(value) => req.insert_header((header::AUTHORIZATION, value.to_string())),
+                None => req,
+            }
+            .to_request()
+        };
+        let basic = |credentials: &str| format!("Basic {}", STANDARD.encode(credentials));
+
+        for (authorization, status) in [
+            (Some("Bearer a".to_string()), 201),
+            (Some("Bearer a".to_string()), 429),
+            (Some("Bearer b".to_string()), 201),
+            (Some(basic("alice:x")), 201),
+            (Some(basic("alice:y")), 429),
+            (Some(basic("bob:x")), 201),
+            (None, 201),
+        ] {
+            let res = test::call_service(&app, post(authorization.as_deref())).await;
+            assert_eq!(res.status(), status, "{:?}", authorization);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::Error;
+use std::time::Instant;
+use tracing::{field, Instrument};
+use uuid::Uuid;
+
+/// リクエストIDを受け渡すヘッダー
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// リクエストIDとして受け付ける最大長（ログ汚染を防ぐ）
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// ログに出すパス（カレンダーフィードのURLのトークンは秘密のため伏せる）
+fn loggable_path(path: &str) -> &str {
+    if path.starts_with("/ical/") {
+        "/ical/{token}.ics"
+    } else {
+        path
+    }
+}
+
+/// HTTPリクエストごとにspanを作成し、メソッド・パス・ステータス・処理時間を記録するミドルウェア
+/// `X-Request-Id` ヘッダーがあればそれを引き継ぎ、なければ生成してレスポンスに付与する
+pub async fn request_tracing(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!(
+        "http_request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %loggable_path(req.path()),
+        status = field::Empty,
+        latency_ms = field::Empty,
+    );
+
+    let started = Instant::now();
+    let result = next.call(req).instrument(span.clone()).await;
+    let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+    span.record("latency_ms", latency_ms);
+
+    let _entered = span.enter();
+    match result {
+        Ok(mut res) => {
+            let status = res.status();
+            span.record("status", status.as_u16());
+            if status.is_server_error() {
+                tracing::error!("リクエスト処理失敗");
+            } else {
+                tracing::info!("リクエスト完了");
+            }
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                res.headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+            Ok(res)
+        }
+        Err(e) => {
+            span.record("status", e.as_response_error().status_code().as_u16());
+            tracing::error!(error = %e, "リクエスト処理エラー");
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::middleware::from_fn;
+    use actix_web::{test, web, App, HttpResponse};
+
+    #[actix_web::test]
+    async fn test_request_id_is_propagated() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(request_tracing))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header((REQUEST_ID_HEADER, "abc-123"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(REQUEST_ID_HEADER).unwrap(), "abc-123");
+
+        let req = test::TestRequest::get().uri("/").to_request();
+        let res = test::call_service(&app, req).await;
+        let generated = res.headers().get(REQUEST_ID_HEADER).unwrap();
+        assert!(Uuid::parse_str(generated.to_str().unwrap()).is_ok());
+    }
+}
@@ -0,0 +1,28 @@
+use actix_web::http::header;
+use actix_web::middleware::DefaultHeaders;
+
+use crate::config::SecurityConfig;
+
+/// すべてのレスポンスにセキュリティヘッダーを付与するミドルウェア
+/// ハンドラーが同じヘッダーを設定した場合はそちらが優先される
+pub fn security_headers(config: &SecurityConfig) -> DefaultHeaders {
+    let mut headers = DefaultHeaders::new()
+        .add((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .add((header::X_FRAME_OPTIONS, "DENY"))
+        .add((header::REFERRER_POLICY, config.referrer_policy.as_str()));
+
+    if !config.content_security_policy.is_empty() {
+        headers = headers.add((
+            header::CONTENT_SECURITY_POLICY,
+            config.content_security_policy.as_str(),
+        ));
+    }
+    if config.hsts_max_age_secs > 0 {
+        headers = headers.add((
+            header::STRICT_TRANSPORT_SECURITY,
+            format!("max-age={}; includeSubDomains", config.hsts_max_age_secs),
+        ));
+    }
+
+    headers
+}
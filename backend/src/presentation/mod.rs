@@ -1,3 +1,17 @@
 pub mod api;
+pub mod caldav;
+pub mod graphql;
+pub mod i18n;
+pub mod middleware;
 
-pub use api::config;
+use actix_web::web;
+
+use api::Versioning;
+
+/// ルーティング設定（APIと、`/api` の外に置くCalDAV）
+/// GraphQLはバージョン付きのAPIより先に登録する（`/api/...` のスコープに吸収されないようにする）
+pub fn config(cfg: &mut web::ServiceConfig, versioning: &Versioning) {
+    cfg.configure(graphql::config);
+    api::config(cfg, versioning);
+    cfg.configure(caldav::config);
+}
@@ -0,0 +1,138 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::GlobalArgs;
+
+/// サーバーを指定しなかった場合の接続先（nginx経由のバックエンド）
+const DEFAULT_SERVER: &str = "http://localhost";
+
+/// 設定ファイル（`~/.config/todo/config.toml` など）
+///
+/// ```toml
+/// server = "https://todo.example.com"
+/// token = "..."
+/// language = "ja"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// APIサーバーのURL（スキームとホスト）
+    pub server: String,
+    /// `Authorization: Bearer` で送るトークン
+    pub token: Option<String>,
+    /// エラーメッセージの言語（未指定なら `LANG` から決める）
+    pub language: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            server: DEFAULT_SERVER.to_string(),
+            token: None,
+            language: None,
+        }
+    }
+}
+
+impl Config {
+    /// 設定を読み込む（引数 > 環境変数 > 設定ファイル > デフォルト）
+    /// 設定ファイルを明示した場合はファイルが必須、既定のパスはなければ読まない
+    pub fn load(args: &GlobalArgs) -> Result<Self, String> {
+        let mut config = match &args.config {
+            Some(path) => Self::from_file(path)?,
+            None => match default_path() {
+                Some(path) if path.exists() => Self::from_file(&path)?,
+                _ => Self::default(),
+            },
+        };
+        if let Some(server) = &args.server {
+            config.server = server.clone();
+        }
+        if let Some(token) = &args.token {
+            config.token = Some(token.clone());
+        }
+        if config.language.is_none() {
+            config.language = std::env::var("LANG").ok().as_deref().and_then(language_of);
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// TOMLファイルから読み込む（未指定の項目はデフォルト値）
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("設定ファイル {} を読み込めません: {}", path.display(), e))?;
+        toml::from_str(&content)
+            .map_err(|e| format!("設定ファイル {} の形式が不正です: {}", path.display(), e))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !["http://", "https://"]
+            .iter()
+            .any(|scheme| self.server.starts_with(scheme))
+        {
+            return Err(format!(
+                "server は http:// か https:// で始まるURLを指定してください: {}",
+                self.server
+            ));
+        }
+        if self
+            .token
+            .as_deref()
+            .is_some_and(|token| token.trim().is_empty())
+        {
+            return Err("token が空です".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 既定の設定ファイルのパス（Linuxでは `~/.config/todo/config.toml`）
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("todo").join("config.toml"))
+}
+
+/// `LANG`（`ja_JP.UTF-8` など）の言語部分。`C` と `POSIX` は言語の指定とみなさない
+fn language_of(lang: &str) -> Option<String> {
+    let language = lang.split(['_', '.', '@']).next()?;
+    (!language.is_empty() && language != "C" && language != "POSIX").then(|| language.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_applies_file_then_arguments() {
+        let path = std::env::temp_dir().join(format!("todo-cli-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "server = \"https://todo.example.com\"\ntoken = \"secret\"\nlanguage = \"en\"\n",
+        )
+        .unwrap();
+
+        let mut args = GlobalArgs {
+            config: Some(path.clone()),
+            ..GlobalArgs::default()
+        };
+        let config = Config::load(&args).unwrap();
+        assert_eq!(config.server, "https://todo.example.com");
+        assert_eq!(config.token.as_deref(), Some("secret"));
+        assert_eq!(config.language.as_deref(), Some("en"));
+
+        args.server = Some("ftp://example.com".to_string());
+        assert!(Config::load(&args).unwrap_err().contains("http://"));
+        args.server = Some("http://127.0.0.1:8000".to_string());
+        args.token = Some("other".to_string());
+        let config = Config::load(&args).unwrap();
+        assert_eq!(config.server, "http://127.0.0.1:8000");
+        assert_eq!(config.token.as_deref(), Some("other"));
+
+        std::fs::write(&path, "sever = \"typo\"\n").unwrap();
+        assert!(Config::load(&args).unwrap_err().contains("形式が不正"));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(language_of("ja_JP.UTF-8").as_deref(), Some("ja"));
+        assert_eq!(language_of("C.UTF-8"), None);
+    }
+}
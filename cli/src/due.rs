@@ -0,0 +1,143 @@
+//! 期限の入力と表示
+//!
+//! `--due` には日付（`2026-10-20`、`today`、`tomorrow`、`+3d`、`fri` など）と、
+//! 続けて時刻（`tomorrow 09:00`）を指定できる。時刻付きの期限は手元のタイムゾーンで解釈し、
+//! APIにはUTCの RFC 3339 で送る。
+
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, TimeZone,
+    Utc, Weekday,
+};
+
+/// `--due` の値をAPIの形式（`YYYY-MM-DD` かUTCの RFC 3339）にする
+/// `now` は相対的な日付と時刻の基準（タイムゾーンもこれに従う）
+pub fn resolve<Tz: TimeZone>(input: &str, now: &DateTime<Tz>) -> Result<String, String> {
+    let input = input.trim();
+    let invalid = || {
+        format!(
+            "期限を解釈できません: {}（2026-10-20、today、tomorrow、+3d、fri、「tomorrow 09:00」などを指定してください）",
+            input
+        )
+    };
+
+    if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+        return Ok(utc(&at));
+    }
+    let (date, time) = match input.rsplit_once([' ', 'T']) {
+        Some((date, time)) => match NaiveTime::parse_from_str(time, "%H:%M") {
+            Ok(time) => (date.trim(), Some(time)),
+            Err(_) => (input, None),
+        },
+        None => (input, None),
+    };
+    let date = parse_date(date, now.date_naive()).ok_or_else(invalid)?;
+
+    match time {
+        None => Ok(date.format("%Y-%m-%d").to_string()),
+        Some(time) => {
+            let at = now
+                .timezone()
+                .from_local_datetime(&NaiveDateTime::new(date, time))
+                .earliest()
+                .ok_or_else(|| format!("{} は存在しない時刻です", input))?;
+            Ok(utc(&at))
+        }
+    }
+}
+
+/// 日付の部分（時刻を除く）
+fn parse_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let lower = input.to_ascii_lowercase();
+    match lower.as_str() {
+        "today" | "今日" => return Some(today),
+        "tomorrow" | "明日" => return today.succ_opt(),
+        _ => {}
+    }
+    if let Some(offset) = lower.strip_prefix('+') {
+        let (count, unit) = offset.split_at(offset.len().saturating_sub(1));
+        let count: i64 = count.parse().ok()?;
+        let days = match unit {
+            "d" => count,
+            "w" => count.checked_mul(7)?,
+            _ => return None,
+        };
+        return today.checked_add_signed(Duration::try_days(days)?);
+    }
+    if let Ok(weekday) = lower.parse::<Weekday>() {
+        // 次のその曜日（今日と同じ曜日なら1週間後）
+        let ahead =
+            (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday() - 1) % 7
+                + 1;
+        return today.checked_add_signed(Duration::days(i64::from(ahead)));
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d").ok()
+}
+
+fn utc<Tz: TimeZone>(at: &DateTime<Tz>) -> String {
+    at.with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// APIの期限を表示用にする（日時は `now` のタイムゾーンの `YYYY-MM-DD HH:MM`）
+pub fn label<Tz: TimeZone>(due: &str, now: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    match DateTime::parse_from_rfc3339(due) {
+        Ok(at) => at
+            .with_timezone(&now.timezone())
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+        Err(_) => due.to_string(),
+    }
+}
+
+/// 期限を過ぎているか（日付だけの期限はその日の終わりまで）
+pub fn is_overdue<Tz: TimeZone>(due: &str, now: &DateTime<Tz>) -> bool {
+    match DateTime::parse_from_rfc3339(due) {
+        Ok(at) => at < *now,
+        Err(_) => {
+            NaiveDate::parse_from_str(due, "%Y-%m-%d").is_ok_and(|date| date < now.date_naive())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_resolve_relative_dates_in_local_time() {
+        // 2026-10-19（月）10:30 JST
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let now = tokyo.with_ymd_and_hms(2026, 10, 19, 10, 30, 0).unwrap();
+
+        assert_eq!(resolve("today", &now).unwrap(), "2026-10-19");
+        assert_eq!(resolve("Tomorrow", &now).unwrap(), "2026-10-20");
+        assert_eq!(resolve("+2w", &now).unwrap(), "2026-11-02");
+        assert_eq!(resolve("fri", &now).unwrap(), "2026-10-23");
+        assert_eq!(resolve("monday", &now).unwrap(), "2026-10-26");
+        assert_eq!(resolve("2026-12-01", &now).unwrap(), "2026-12-01");
+        assert_eq!(
+            resolve("tomorrow 09:00", &now).unwrap(),
+            "2026-10-20T00:00:00Z"
+        );
+        assert_eq!(
+            resolve("2026-10-19T08:00", &now).unwrap(),
+            "2026-10-18T23:00:00Z"
+        );
+        assert_eq!(
+            resolve("2026-10-19T08:00:00+02:00", &now).unwrap(),
+            "2026-10-19T06:00:00Z"
+        );
+        assert!(resolve("someday", &now).is_err());
+        assert!(resolve("+3x", &now).is_err());
+
+        assert_eq!(label("2026-10-20T00:00:00Z", &now), "2026-10-20 09:00");
+        assert_eq!(label("2026-10-20", &now), "2026-10-20");
+        assert!(is_overdue("2026-10-18", &now));
+        assert!(!is_overdue("2026-10-19", &now));
+        assert!(is_overdue("2026-10-19T01:00:00Z", &now));
+    }
+}
@@ -0,0 +1,254 @@
+//! Todo APIのコマンドラインクライアント
+//!
+//! ```sh
+//! todo add "牛乳を買う" --due tomorrow
+//! todo ls --open
+//! todo done 12345
+//! todo tui
+//! ```
+
+mod config;
+mod due;
+mod output;
+mod tui;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use api_client::{ApiClient, ClientError, CreateTodoDto, TodoResponseDto, UpdateTodoDto};
+use chrono::Local;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+use config::Config;
+
+/// Todo APIをターミナルから操作する
+#[derive(Debug, Parser)]
+#[command(name = "todo", author, version, about)]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// すべてのサブコマンドで使える引数
+/// 接続先とトークンは 引数 > 環境変数 > 設定ファイル の順に優先される
+#[derive(Debug, Default, Args)]
+pub struct GlobalArgs {
+    /// 設定ファイル(TOML)のパス（既定は `~/.config/todo/config.toml`）
+    #[arg(long, global = true, env = "TODO_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// APIサーバーのURL（例: `http://localhost`）
+    #[arg(long, global = true, env = "TODO_SERVER")]
+    pub server: Option<String>,
+
+    /// APIトークン（`Authorization: Bearer` で送る）
+    #[arg(long, global = true, env = "TODO_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// 表ではなくJSONで出力する
+    #[arg(long, global = true)]
+    pub json: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Todoを作成する
+    Add {
+        /// タイトル
+        title: String,
+        /// 期限（`2026-10-20`、`today`、`tomorrow`、`+3d`、`fri`、`"tomorrow 09:00"` など）
+        #[arg(long)]
+        due: Option<String>,
+    },
+    /// Todoの一覧を表示する
+    #[command(visible_alias = "list")]
+    Ls {
+        /// 未完了のTodoだけを表示する
+        #[arg(long, conflicts_with = "done")]
+        open: bool,
+        /// 完了したTodoだけを表示する
+        #[arg(long)]
+        done: bool,
+    },
+    /// Todoを完了にする
+    Done {
+        /// TodoのID（複数指定できる）
+        #[arg(required = true, allow_negative_numbers = true)]
+        ids: Vec<i32>,
+        /// 未完了に戻す
+        #[arg(long)]
+        undo: bool,
+    },
+    /// Todoのタイトルや期限を変更する
+    Edit {
+        /// TodoのID
+        #[arg(allow_negative_numbers = true)]
+        id: i32,
+        /// 新しいタイトル
+        #[arg(long)]
+        title: Option<String>,
+        /// 新しい期限（`add --due` と同じ形式）
+        #[arg(long, conflicts_with = "no_due")]
+        due: Option<String>,
+        /// 期限をなくす
+        #[arg(long)]
+        no_due: bool,
+    },
+    /// Todoを削除する
+    #[command(visible_alias = "remove")]
+    Rm {
+        /// TodoのID（複数指定できる）
+        #[arg(required = true, allow_negative_numbers = true)]
+        ids: Vec<i32>,
+    },
+    /// 一覧を開いたまま操作する画面（変更はサーバーから自動で反映される）
+    Tui,
+    /// シェル補完のスクリプトを出力する（例: `todo completions zsh > ~/.zfunc/_todo`）
+    Completions { shell: Shell },
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    if let Command::Completions { shell } = cli.command {
+        clap_complete::generate(shell, &mut Cli::command(), "todo", &mut std::io::stdout());
+        return ExitCode::SUCCESS;
+    }
+
+    let config = match Config::load(&cli.global) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let mut client = ApiClient::new(&config.server);
+    if let Some(token) = &config.token {
+        client = client.with_token(token);
+    }
+    if let Some(language) = &config.language {
+        client.set_language(language);
+    }
+
+    match run(&client, cli.command, cli.global.json).await {
+        Ok(output) => {
+            print!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// サブコマンドを実行し、表示する内容を返す
+async fn run(client: &ApiClient, command: Command, json: bool) -> Result<String, String> {
+    let now = Local::now();
+    let show = |todos: &[TodoResponseDto]| {
+        if json {
+            output::json(todos)
+        } else {
+            output::table(todos, &now)
+        }
+    };
+
+    match command {
+        Command::Add { title, due } => {
+            let due = due.map(|due| due::resolve(&due, &now)).transpose()?;
+            let todo = client
+                .create_todo(&CreateTodoDto {
+                    title,
+                    completed: false,
+                    due,
+                })
+                .await
+                .map_err(describe)?;
+            Ok(if json {
+                output::json(&todo)
+            } else {
+                output::table(&[todo], &now)
+            })
+        }
+        Command::Ls { open, done } => {
+            let mut todos = client.list_todos().await.map_err(describe)?;
+            if open || done {
+                todos.retain(|todo| todo.completed == done);
+            }
+            Ok(show(&todos))
+        }
+        Command::Done { ids, undo } => {
+            let mut todos = Vec::new();
+            for id in ids {
+                let dto = UpdateTodoDto {
+                    completed: Some(!undo),
+                    ..UpdateTodoDto::default()
+                };
+                todos.push(client.update_todo(id, &dto).await.map_err(|e| at(id, e))?);
+            }
+            Ok(show(&todos))
+        }
+        Command::Edit {
+            id,
+            title,
+            due,
+            no_due,
+        } => {
+            let due = match (due, no_due) {
+                (Some(due), _) => Some(due::resolve(&due, &now)?),
+                (None, true) => Some(String::new()),
+                (None, false) => None,
+            };
+            if title.is_none() && due.is_none() {
+                return Err(
+                    "変更する項目（--title、--due、--no-due）を指定してください".to_string()
+                );
+            }
+            let dto = UpdateTodoDto {
+                title,
+                completed: None,
+                due,
+            };
+            let todo = client.update_todo(id, &dto).await.map_err(|e| at(id, e))?;
+            Ok(if json {
+                output::json(&todo)
+            } else {
+                output::table(&[todo], &now)
+            })
+        }
+        Command::Rm { ids } => {
+            for &id in &ids {
+                client.delete_todo(id).await.map_err(|e| at(id, e))?;
+            }
+            Ok(if json {
+                output::json(&serde_json::json!({ "deleted": ids }))
+            } else {
+                format!("{}件のTodoを削除しました\n", ids.len())
+            })
+        }
+        Command::Tui => tui::run(client.clone()).await.map(|()| String::new()),
+        Command::Completions { .. } => unreachable!("補完はサーバーに接続する前に出力する"),
+    }
+}
+
+/// APIのエラーを表示用にする
+pub(crate) fn describe(e: ClientError) -> String {
+    match e.status() {
+        Some(401 | 403) => format!("{}（token の設定を確認してください）", e),
+        _ => e.to_string(),
+    }
+}
+
+/// IDを指定した操作のエラー
+fn at(id: i32, e: ClientError) -> String {
+    if e.is_not_found() {
+        format!("ID {} のTodoが見つかりません", id)
+    } else {
+        format!("ID {}: {}", id, describe(e))
+    }
+}
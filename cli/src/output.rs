@@ -0,0 +1,94 @@
+//! 結果の表示（表とJSON）
+
+use api_client::TodoResponseDto;
+use chrono::{DateTime, TimeZone};
+use unicode_width::UnicodeWidthStr;
+
+use crate::due;
+
+/// Todoの表（ID・完了・期限・タイトル）。期限切れの未完了のTodoには `!` を付ける
+pub fn table<Tz: TimeZone>(todos: &[TodoResponseDto], now: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let header = ["ID", "完了", "期限", "タイトル"].map(str::to_string);
+    let rows: Vec<[String; 4]> = todos
+        .iter()
+        .map(|todo| {
+            let due = match &todo.due {
+                Some(value) if !todo.completed && due::is_overdue(value, now) => {
+                    format!("{} !", due::label(value, now))
+                }
+                Some(value) => due::label(value, now),
+                None => String::new(),
+            };
+            [
+                todo.id.to_string(),
+                if todo.completed { "✓" } else { "" }.to_string(),
+                due,
+                // 改行を含むタイトルも1行に収める
+                todo.title.replace(['\r', '\n'], " "),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.each_ref().map(|cell| cell.width());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    let mut output = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let mut line = String::new();
+        for (i, (cell, width)) in row.iter().zip(widths).enumerate() {
+            if i > 0 {
+                line.push_str("  ");
+            }
+            line.push_str(cell);
+            line.push_str(&" ".repeat(width - cell.width()));
+        }
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// 整形したJSON
+pub fn json<T: serde::Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_table_aligns_wide_characters() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap();
+        let todo = |id, title: &str, completed, due: Option<&str>| TodoResponseDto {
+            id,
+            title: title.to_string(),
+            completed,
+            position: 0.0,
+            created_at: None,
+            due: due.map(str::to_string),
+            completed_at: None,
+        };
+        let output = table(
+            &[
+                todo(-42, "牛乳を買う", false, Some("2026-10-18")),
+                todo(7, "report\nfinal", true, Some("2026-10-20T09:00:00Z")),
+            ],
+            &now,
+        );
+        assert_eq!(
+            output,
+            "ID   完了  期限              タイトル\n\
+             -42        2026-10-18 !      牛乳を買う\n\
+             7    ✓     2026-10-20 09:00  report final\n"
+        );
+    }
+}
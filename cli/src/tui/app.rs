@@ -0,0 +1,458 @@
+//! 画面の状態とキー操作（描画と通信から切り離して扱う）
+
+use api_client::{SyncResponseDto, TodoResponseDto, UpdateTodoDto};
+use chrono::{DateTime, FixedOffset};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::due;
+
+/// 表示するTodoの絞り込み
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    All,
+    Open,
+    Done,
+}
+
+impl Filter {
+    pub const ALL: [Filter; 3] = [Filter::All, Filter::Open, Filter::Done];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Filter::All => "すべて",
+            Filter::Open => "未完了",
+            Filter::Done => "完了",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Filter::All => Filter::Open,
+            Filter::Open => Filter::Done,
+            Filter::Done => Filter::All,
+        }
+    }
+
+    fn matches(self, todo: &TodoResponseDto) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Open => !todo.completed,
+            Filter::Done => todo.completed,
+        }
+    }
+}
+
+/// 入力中の内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// 一覧の操作
+    Normal,
+    /// 検索語の入力（入力のたびに絞り込む）
+    Search,
+    /// 新しいTodoのタイトルの入力
+    Add,
+    /// タイトルの変更
+    Edit { id: i32 },
+    /// 期限の変更
+    Due { id: i32 },
+    /// 削除の確認
+    ConfirmDelete { id: i32 },
+}
+
+/// 変更の受け取り方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Connection {
+    Connecting,
+    /// サーバーからの通知で更新している
+    Live,
+    /// サーバーが通知に対応していないため、定期的に差分を取得している
+    Polling,
+    /// 接続できない（再接続を待っている）
+    Offline(String),
+}
+
+/// キー操作の結果、サーバーに対して行うこと
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Quit,
+    /// 全件を取り直す
+    Refresh,
+    Create {
+        title: String,
+    },
+    Update {
+        id: i32,
+        dto: UpdateTodoDto,
+    },
+    Delete {
+        id: i32,
+    },
+}
+
+/// 画面の状態
+pub struct App {
+    todos: Vec<TodoResponseDto>,
+    pub filter: Filter,
+    pub search: String,
+    pub mode: Mode,
+    /// 入力欄の内容（`Normal` 以外のモード）
+    pub input: String,
+    /// 表示中の一覧での選択位置
+    pub selected: usize,
+    /// 最後の操作の結果やエラー
+    pub status: Option<String>,
+    pub connection: Connection,
+    /// 一覧を一度でも受け取ったか
+    pub loaded: bool,
+    /// 期限の解釈と期限切れの判定の基準
+    pub now: DateTime<FixedOffset>,
+}
+
+impl App {
+    pub fn new(now: DateTime<FixedOffset>) -> Self {
+        Self {
+            todos: Vec::new(),
+            filter: Filter::All,
+            search: String::new(),
+            mode: Mode::Normal,
+            input: String::new(),
+            selected: 0,
+            status: None,
+            connection: Connection::Connecting,
+            loaded: false,
+            now,
+        }
+    }
+
+    /// 絞り込みと検索に一致するTodo（並び順）
+    pub fn visible(&self) -> Vec<&TodoResponseDto> {
+        let search = self.search.to_lowercase();
+        self.todos
+            .iter()
+            .filter(|todo| self.filter.matches(todo))
+            .filter(|todo| search.is_empty() || todo.title.to_lowercase().contains(&search))
+            .collect()
+    }
+
+    /// 選択中のTodo
+    pub fn current(&self) -> Option<&TodoResponseDto> {
+        self.visible().get(self.selected).copied()
+    }
+
+    /// 差分同期の結果を反映する（選択中のTodoはできるだけ選択したままにする）
+    pub fn apply_sync(&mut self, changes: SyncResponseDto) {
+        let selected = self.current().map(|todo| todo.id);
+        if changes.full {
+            self.todos = changes.todos;
+        } else {
+            self.todos
+                .retain(|todo| !changes.deleted.contains(&todo.id));
+            for todo in changes.todos {
+                self.put(todo);
+            }
+        }
+        self.loaded = true;
+        self.sort();
+        self.reselect(selected);
+    }
+
+    /// 作成・更新したTodoを反映する
+    pub fn upsert(&mut self, todo: TodoResponseDto) {
+        let selected = self.current().map(|todo| todo.id);
+        self.put(todo);
+        self.sort();
+        self.reselect(selected);
+    }
+
+    /// 削除したTodoを反映する
+    pub fn remove(&mut self, id: i32) {
+        self.todos.retain(|todo| todo.id != id);
+        self.clamp();
+    }
+
+    fn put(&mut self, todo: TodoResponseDto) {
+        match self.todos.iter_mut().find(|current| current.id == todo.id) {
+            Some(current) => *current = todo,
+            None => self.todos.push(todo),
+        }
+    }
+
+    fn sort(&mut self) {
+        self.todos.sort_by(|a, b| a.position.total_cmp(&b.position));
+    }
+
+    fn reselect(&mut self, id: Option<i32>) {
+        if let Some(index) = id.and_then(|id| self.visible().iter().position(|todo| todo.id == id))
+        {
+            self.selected = index;
+        }
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        self.selected = self.selected.min(self.visible().len().saturating_sub(1));
+    }
+
+    /// キー操作を処理し、サーバーに対して行うことがあれば返す
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Command> {
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Command::Quit);
+        }
+        match self.mode {
+            Mode::Normal => self.handle_normal(key),
+            Mode::ConfirmDelete { id } => {
+                self.mode = Mode::Normal;
+                match key.code {
+                    KeyCode::Char('y' | 'Y') => Some(Command::Delete { id }),
+                    _ => {
+                        self.status = Some("削除を取り消しました".to_string());
+                        None
+                    }
+                }
+            }
+            _ => self.handle_input(key),
+        }
+    }
+
+    fn handle_normal(&mut self, key: KeyEvent) -> Option<Command> {
+        self.status = None;
+        let current = self.current().cloned();
+        match key.code {
+            KeyCode::Char('q') => return Some(Command::Quit),
+            KeyCode::Esc => {
+                self.search.clear();
+                self.clamp();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = self.selected.saturating_add(1);
+                self.clamp();
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => {
+                self.selected = usize::MAX;
+                self.clamp();
+            }
+            KeyCode::Tab | KeyCode::Char('f') => {
+                self.filter = self.filter.next();
+                self.clamp();
+            }
+            KeyCode::Char('/') => {
+                self.input = self.search.clone();
+                self.mode = Mode::Search;
+            }
+            KeyCode::Char('a') => {
+                self.input.clear();
+                self.mode = Mode::Add;
+            }
+            KeyCode::Char('r') => return Some(Command::Refresh),
+            KeyCode::Char(' ' | 'x') => {
+                let todo = current?;
+                return Some(Command::Update {
+                    id: todo.id,
+                    dto: UpdateTodoDto {
+                        completed: Some(!todo.completed),
+                        ..UpdateTodoDto::default()
+                    },
+                });
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                let todo = current?;
+                self.input = todo.title;
+                self.mode = Mode::Edit { id: todo.id };
+            }
+            KeyCode::Char('t') => {
+                let todo = current?;
+                self.input = todo
+                    .due
+                    .map(|value| due::label(&value, &self.now))
+                    .unwrap_or_default();
+                self.mode = Mode::Due { id: todo.id };
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                let todo = current?;
+                self.mode = Mode::ConfirmDelete { id: todo.id };
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Command> {
+        match key.code {
+            KeyCode::Esc => {
+                if self.mode == Mode::Search {
+                    self.search.clear();
+                }
+                self.mode = Mode::Normal;
+                self.clamp();
+                return None;
+            }
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input.clear();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => {}
+        }
+        if self.mode == Mode::Search {
+            self.search = self.input.clone();
+            self.selected = 0;
+        }
+        None
+    }
+
+    /// 入力を確定する
+    fn submit(&mut self) -> Option<Command> {
+        let mode = std::mem::replace(&mut self.mode, Mode::Normal);
+        let input = std::mem::take(&mut self.input);
+        let title = input.trim().to_string();
+        match mode {
+            Mode::Search => {
+                self.search = input;
+                self.clamp();
+                None
+            }
+            Mode::Add | Mode::Edit { .. } if title.is_empty() => {
+                self.status = Some("タイトルを入力してください".to_string());
+                None
+            }
+            Mode::Add => Some(Command::Create { title }),
+            Mode::Edit { id } => Some(Command::Update {
+                id,
+                dto: UpdateTodoDto {
+                    title: Some(title),
+                    ..UpdateTodoDto::default()
+                },
+            }),
+            Mode::Due { id } => {
+                // 空なら期限をなくす
+                let value = if title.is_empty() {
+                    String::new()
+                } else {
+                    match due::resolve(&title, &self.now) {
+                        Ok(value) => value,
+                        Err(e) => {
+                            self.status = Some(e);
+                            return None;
+                        }
+                    }
+                };
+                Some(Command::Update {
+                    id,
+                    dto: UpdateTodoDto {
+                        due: Some(value),
+                        ..UpdateTodoDto::default()
+                    },
+                })
+            }
+            Mode::Normal | Mode::ConfirmDelete { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn todo(id: i32, title: &str, completed: bool, position: f64) -> TodoResponseDto {
+        TodoResponseDto {
+            id,
+            title: title.to_string(),
+            completed,
+            position,
+            created_at: None,
+            due: None,
+            completed_at: None,
+        }
+    }
+
+    fn press(app: &mut App, keys: &str) -> Option<Command> {
+        keys.chars()
+            .map(|c| {
+                let code = match c {
+                    '\n' => KeyCode::Enter,
+                    '\x1b' => KeyCode::Esc,
+                    c => KeyCode::Char(c),
+                };
+                app.handle_key(KeyEvent::from(code))
+            })
+            .last()
+            .flatten()
+    }
+
+    #[test]
+    fn test_keys_filter_search_and_edit_against_synced_list() {
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let mut app = App::new(tokyo.with_ymd_and_hms(2026, 10, 19, 10, 0, 0).unwrap());
+        app.apply_sync(SyncResponseDto {
+            todos: vec![
+                todo(1, "牛乳を買う", false, 2.0),
+                todo(2, "Report", true, 1.0),
+                todo(3, "豆乳を買う", false, 3.0),
+            ],
+            deleted: vec![],
+            token: "3".to_string(),
+            full: true,
+        });
+        let titles = |app: &App| -> Vec<String> {
+            app.visible()
+                .iter()
+                .map(|todo| todo.title.clone())
+                .collect()
+        };
+        assert_eq!(titles(&app), ["Report", "牛乳を買う", "豆乳を買う"]);
+
+        // 未完了に絞り込み、検索して、選択したTodoを完了にする
+        press(&mut app, "f");
+        assert_eq!(titles(&app), ["牛乳を買う", "豆乳を買う"]);
+        press(&mut app, "/豆乳\n");
+        assert_eq!(titles(&app), ["豆乳を買う"]);
+        assert_eq!(
+            press(&mut app, " "),
+            Some(Command::Update {
+                id: 3,
+                dto: UpdateTodoDto {
+                    completed: Some(true),
+                    ..UpdateTodoDto::default()
+                },
+            })
+        );
+        press(&mut app, "\x1bj");
+        assert_eq!(app.current().map(|todo| todo.id), Some(3));
+
+        // 他のクライアントでの変更が届いても選択は同じTodoのまま
+        app.apply_sync(SyncResponseDto {
+            todos: vec![todo(4, "卵を買う", false, 0.5)],
+            deleted: vec![1],
+            token: "5".to_string(),
+            full: false,
+        });
+        assert_eq!(titles(&app), ["卵を買う", "豆乳を買う"]);
+        assert_eq!(app.current().map(|todo| todo.id), Some(3));
+
+        // 期限は `--due` と同じ形式で入力する
+        assert_eq!(
+            press(&mut app, "ttomorrow\n"),
+            Some(Command::Update {
+                id: 3,
+                dto: UpdateTodoDto {
+                    due: Some("2026-10-20".to_string()),
+                    ..UpdateTodoDto::default()
+                },
+            })
+        );
+        assert_eq!(press(&mut app, "a \n"), None);
+        assert_eq!(app.status.as_deref(), Some("タイトルを入力してください"));
+        assert_eq!(press(&mut app, "dn"), None);
+        assert_eq!(press(&mut app, "dy"), Some(Command::Delete { id: 3 }));
+        assert_eq!(press(&mut app, "q"), Some(Command::Quit));
+    }
+}
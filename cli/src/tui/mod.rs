@@ -0,0 +1,173 @@
+//! 対話的な画面（`todo tui`）
+//!
+//! 一覧を表示したまま、完了の切り替え・編集・絞り込み・検索を行う。サーバーが変更の通知
+//! （`GET /api/v1/events`）に対応していれば通知のたびに差分を取得し、対応していなければ定期的に取得する。
+
+mod app;
+mod ui;
+
+use std::time::Duration;
+
+use api_client::{ApiClient, ClientError, CreateTodoDto, SyncResponseDto};
+use chrono::Local;
+use ratatui::crossterm::event::{self, Event, KeyEvent};
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+use crate::describe;
+use app::{App, Command, Connection};
+
+/// 通知に対応していないサーバーから差分を取得する間隔
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 接続が切れたときに再接続するまでの時間
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// 画面に届くもの
+enum Message {
+    Key(KeyEvent),
+    /// 端末の大きさが変わった
+    Resize,
+    Synced(SyncResponseDto),
+    Connection(Connection),
+}
+
+/// 画面を開き、終了するまで操作を受け付ける
+pub async fn run(client: ApiClient) -> Result<(), String> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    spawn_input(sender.clone());
+    let updates = tokio::spawn(watch(client.clone(), sender));
+
+    let mut terminal = ratatui::try_init().map_err(|e| format!("端末を初期化できません: {}", e))?;
+    let mut app = App::new(Local::now().fixed_offset());
+    let result = loop {
+        app.now = Local::now().fixed_offset();
+        if let Err(e) = terminal.draw(|frame| ui::draw(frame, &app)) {
+            break Err(format!("画面を描画できません: {}", e));
+        }
+        let Some(message) = receiver.recv().await else {
+            break Ok(());
+        };
+        match message {
+            Message::Key(key) => match app.handle_key(key) {
+                Some(Command::Quit) => break Ok(()),
+                Some(command) => perform(&client, &mut app, command).await,
+                None => {}
+            },
+            Message::Resize => {}
+            Message::Synced(changes) => app.apply_sync(changes),
+            Message::Connection(connection) => app.connection = connection,
+        }
+    };
+
+    updates.abort();
+    ratatui::restore();
+    result
+}
+
+/// キー入力を読むスレッド（crossterm の読み取りはブロックするため）
+fn spawn_input(sender: UnboundedSender<Message>) {
+    std::thread::spawn(move || loop {
+        let message = match event::read() {
+            Ok(Event::Key(key)) => Message::Key(key),
+            Ok(Event::Resize(..)) => Message::Resize,
+            Ok(_) => continue,
+            Err(_) => return,
+        };
+        if sender.send(message).is_err() {
+            return;
+        }
+    });
+}
+
+/// サーバーに対して操作し、結果を画面に反映する
+async fn perform(client: &ApiClient, app: &mut App, command: Command) {
+    let result = match command {
+        Command::Quit => Ok(()),
+        Command::Refresh => client.sync(None).await.map(|changes| {
+            app.apply_sync(changes);
+            app.status = Some("再読み込みしました".to_string());
+        }),
+        Command::Create { title } => {
+            let dto = CreateTodoDto {
+                title,
+                completed: false,
+                due: None,
+            };
+            client.create_todo(&dto).await.map(|todo| {
+                app.status = Some(format!("「{}」を追加しました", todo.title));
+                app.upsert(todo);
+            })
+        }
+        Command::Update { id, dto } => client
+            .update_todo(id, &dto)
+            .await
+            .map(|todo| app.upsert(todo)),
+        Command::Delete { id } => client.delete_todo(id).await.map(|()| {
+            app.remove(id);
+            app.status = Some("削除しました".to_string());
+        }),
+    };
+    if let Err(e) = result {
+        app.status = Some(describe(e));
+    }
+}
+
+/// 変更を取得し続ける
+/// 通知を購読できればそれに従い、通知に対応していないサーバー（404）では定期的に差分を取得する
+async fn watch(client: ApiClient, sender: UnboundedSender<Message>) {
+    let send = |message| sender.send(message).is_ok();
+    let mut token: Option<String> = None;
+    let mut polling = false;
+
+    loop {
+        // 購読を始める前（切断中）の変更に追いつく
+        if let Err(e) = sync(&client, &mut token, &sender).await {
+            send(Message::Connection(Connection::Offline(describe(e))));
+            tokio::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        if polling {
+            send(Message::Connection(Connection::Polling));
+            tokio::time::sleep(POLL_INTERVAL).await;
+            continue;
+        }
+
+        match client.watch().await {
+            Ok(mut events) => {
+                send(Message::Connection(Connection::Live));
+                // 通知の中身に関わらず差分を取得する（知らない種類の通知も変更の合図とみなす）
+                while let Some(event) = events.next().await {
+                    if matches!(event, Err(ClientError::Network(_))) {
+                        break;
+                    }
+                    if sync(&client, &mut token, &sender).await.is_err() {
+                        break;
+                    }
+                }
+                if !send(Message::Connection(Connection::Connecting)) {
+                    return;
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+            Err(e) if matches!(e.status(), Some(404 | 405 | 501)) => polling = true,
+            Err(e) => {
+                if !send(Message::Connection(Connection::Offline(describe(e)))) {
+                    return;
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+/// 前回のトークン以降の変更を取得して画面に送る
+async fn sync(
+    client: &ApiClient,
+    token: &mut Option<String>,
+    sender: &UnboundedSender<Message>,
+) -> Result<(), ClientError> {
+    let changes = client.sync(token.as_deref()).await?;
+    *token = Some(changes.token.clone());
+    let _ = sender.send(Message::Synced(changes));
+    Ok(())
+}
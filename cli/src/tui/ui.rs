@@ -0,0 +1,150 @@
+//! 画面の描画
+
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Cell, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+
+use super::app::{App, Connection, Filter, Mode};
+use crate::due;
+
+/// 一覧の画面（見出し・一覧・入力欄またはヘルプ）
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, list, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(Paragraph::new(header_line(app)), header);
+
+    let todos = app.visible();
+    if todos.is_empty() {
+        let message = if !app.loaded {
+            "読み込み中…"
+        } else if app.search.is_empty() && app.filter == Filter::All {
+            "Todoはありません（a で追加）"
+        } else {
+            "一致するTodoはありません"
+        };
+        frame.render_widget(
+            Paragraph::new(message).style(Style::new().fg(Color::DarkGray)),
+            list,
+        );
+    } else {
+        let rows = todos.iter().map(|todo| {
+            let check = if todo.completed { "[x]" } else { "[ ]" };
+            let title_style = if todo.completed {
+                Style::new()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::new()
+            };
+            let due_cell = match &todo.due {
+                Some(value) => {
+                    let style = if !todo.completed && due::is_overdue(value, &app.now) {
+                        Style::new().fg(Color::Red)
+                    } else {
+                        Style::new().fg(Color::DarkGray)
+                    };
+                    Cell::from(due::label(value, &app.now)).style(style)
+                }
+                None => Cell::from(""),
+            };
+            Row::new([
+                Cell::from(check),
+                Cell::from(todo.title.replace(['\r', '\n'], " ")).style(title_style),
+                due_cell,
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Min(10),
+                Constraint::Length(16),
+            ],
+        )
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::new().with_selected(Some(app.selected));
+        frame.render_stateful_widget(table, list, &mut state);
+    }
+
+    frame.render_widget(Paragraph::new(footer_line(app)), footer);
+    if let Some(prompt) = prompt(&app.mode) {
+        // 入力欄のカーソル（全角文字は2桁として数える）
+        let width =
+            unicode_width::UnicodeWidthStr::width(format!("{}{}", prompt, app.input).as_str());
+        frame.set_cursor_position((footer.x + width as u16, footer.y));
+    }
+}
+
+/// 見出し（絞り込み・検索語・接続状態）
+fn header_line(app: &App) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        "Todo ",
+        Style::new().add_modifier(Modifier::BOLD),
+    )];
+    for filter in Filter::ALL {
+        let style = if filter == app.filter {
+            Style::new().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::new().fg(Color::DarkGray)
+        };
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(format!(" {} ", filter.label()), style));
+    }
+    if !app.search.is_empty() && app.mode != Mode::Search {
+        spans.push(Span::raw(format!("  検索: {}", app.search)));
+    }
+    let (label, color) = match &app.connection {
+        Connection::Connecting => ("○ 接続中".to_string(), Color::DarkGray),
+        Connection::Live => ("● ライブ".to_string(), Color::Green),
+        Connection::Polling => ("◌ 定期更新".to_string(), Color::Yellow),
+        Connection::Offline(e) => (format!("× {}", e), Color::Red),
+    };
+    spans.push(Span::raw("  "));
+    spans.push(Span::styled(label, Style::new().fg(color)));
+    Line::from(spans)
+}
+
+/// 入力欄の見出し
+fn prompt(mode: &Mode) -> Option<&'static str> {
+    match mode {
+        Mode::Search => Some("検索: "),
+        Mode::Add => Some("新しいTodo: "),
+        Mode::Edit { .. } => Some("タイトル: "),
+        Mode::Due { .. } => Some("期限（空で削除）: "),
+        Mode::Normal | Mode::ConfirmDelete { .. } => None,
+    }
+}
+
+/// 最下行（入力欄・確認・操作の結果・キーの説明）
+fn footer_line(app: &App) -> Line<'static> {
+    if let Some(prompt) = prompt(&app.mode) {
+        return Line::from(vec![
+            Span::styled(prompt, Style::new().fg(Color::Cyan)),
+            Span::raw(app.input.clone()),
+        ]);
+    }
+    if let Mode::ConfirmDelete { .. } = app.mode {
+        let title = app
+            .current()
+            .map(|todo| todo.title.clone())
+            .unwrap_or_default();
+        return Line::styled(
+            format!("「{}」を削除しますか？ (y/N)", title),
+            Style::new().fg(Color::Yellow),
+        );
+    }
+    match &app.status {
+        Some(status) => Line::raw(status.clone()),
+        None => Line::styled(
+            "j/k 移動  space 完了  a 追加  e 編集  t 期限  d 削除  f 絞り込み  / 検索  r 再読込  q 終了",
+            Style::new().fg(Color::DarkGray),
+        ),
+    }
+}
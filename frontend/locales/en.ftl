@@ -0,0 +1,126 @@
+# 画面の文言（英語）
+# IDを追加したら ja.ftl にも追加する（テストで差分を検出する）
+
+app-title = ✨ Modern Todo
+locale-label = Language
+new-todo-placeholder = What needs to be done? ✍️
+search-placeholder = Search todos 🔍
+loading = Loading
+retry = Retry
+dismiss = Dismiss
+save = Save
+delete = Delete
+title-label = Title
+completed-label = Completed
+edit-hint = Double-click to edit
+back-to-list = Back to the list
+created-at = Created { $date }
+
+due-label = Due
+due-time-label = Time (optional)
+due-on = Due { $date }
+
+filter-all = All
+filter-active = Active
+filter-completed = Completed
+empty-all = Nothing to do yet. Add your first todo above! 🎉
+empty-active = No active todos. All done! 🎉
+empty-completed = No completed todos yet.
+empty-search = No todos match “{ $query }”.
+todo-missing = This todo doesn't exist or was deleted.
+page-missing = This page doesn't exist.
+
+load-todos-failed = Couldn't load todos: { $error }
+load-todo-failed = Couldn't load the todo: { $error }
+save-failed = Couldn't save a change: { $error }
+
+sync-synced = ✓ Synced
+sync-syncing = ⟳ Syncing…
+sync-offline = Offline · { $count } pending
+sync-failed = Sync failed · { $count } pending
+
+shortcut-move = move
+shortcut-toggle = toggle
+shortcut-edit = edit
+shortcut-delete = delete
+shortcut-search = search
+shortcut-commands = commands
+
+transfer-label = Import / export
+export-label = Export:
+import-label = Import a file…
+import-read-failed = The file isn't a text file.
+import-failed = Couldn't import: { $error }
+import-preview-title = Import “{ $file }”
+import-summary = { $imported } new · { $duplicates } duplicates · { $invalid } invalid{ $failed ->
+    [0] {""}
+   *[other] {" · "}{ $failed } failed
+}
+import-line = Line
+import-status = Result
+import-status-imported = New
+import-status-duplicate = Duplicate (skipped)
+import-status-invalid = Invalid (skipped)
+import-status-failed = Failed
+import-confirm = Import { $count ->
+    [one] { $count } todo
+   *[other] { $count } todos
+}
+cancel = Cancel
+
+calendar-label = Calendar feed
+calendar-hint = Subscribe to this URL in your calendar app to see todos with due dates. Anyone with the URL can read them.
+calendar-subscribe = Open in calendar app
+calendar-rotate = Reset URL
+calendar-rotate-confirm = The current URL will stop working (the CalDAV password stays the same). Reset it?
+calendar-events = Also show due dates as events
+calendar-load-failed = Couldn't get the calendar URL: { $error }
+caldav-hint = To edit todos from Thunderbird or iOS Reminders, add a CalDAV account with this server and password (any user name).
+caldav-server = CalDAV server
+caldav-password = CalDAV password
+caldav-rotate = Reset password
+caldav-rotate-confirm = The current CalDAV password will stop working (the feed URL stays the same). Reset it?
+
+palette-label = Command palette
+palette-input = Command
+palette-placeholder = Type a command…
+palette-empty = No matching commands
+command-show-all = Show all todos
+command-show-active = Show active todos
+command-show-completed = Show completed todos
+command-search = Search todos
+command-new = New todo
+command-toggle = Toggle selected todo
+command-edit = Edit selected todo
+command-delete = Delete selected todo
+command-complete-all = Complete all todos
+command-activate-all = Mark all todos as active
+command-clear-completed = Delete completed todos
+command-sync = Sync now
+
+# APIのエラーコードごとの文言（IDは `error-` + エラーコード）
+error-title-empty = Title can't be empty.
+error-todo-not-found = Todo not found.
+error-move-target-not-found = The todo to move next to was not found.
+error-invalid-move = Specify exactly one of "before" or "after".
+error-invalid-due = Invalid due date.
+error-invalid-sync-token = Invalid sync token.
+error-unsupported-format = Unsupported file format. Use CSV, JSON, Markdown or todo.txt.
+error-invalid-import-file = The file could not be imported.
+error-invalid-import-row = This row could not be read.
+error-calendar-feed-not-found = Calendar feed not found.
+error-invalid-json = Invalid request body.
+error-batch-too-large = Too many operations in one batch.
+error-payload-too-large = The request body is too large.
+error-unsupported-api-version = Unsupported API version.
+error-rate-limited = Too many requests. Please retry in { $seconds ->
+    [one] { $seconds } second
+   *[other] { $seconds } seconds
+}.
+error-csrf-token-mismatch = CSRF token mismatch.
+error-internal-error = An internal error occurred. Please try again later.
+error-unknown = Something went wrong.
+# APIに届かなかった場合
+error-network = Couldn't reach the server.
+error-http = Server error (HTTP { $status }).
+error-decode = Couldn't read the server's response.
@@ -0,0 +1,120 @@
+# 画面の文言（日本語）
+# IDを追加したら en.ftl にも追加する（テストで差分を検出する）
+
+app-title = ✨ Modern Todo
+locale-label = 言語
+new-todo-placeholder = やることを入力 ✍️
+search-placeholder = Todoを検索 🔍
+loading = 読み込み中
+retry = 再試行
+dismiss = 閉じる
+save = 保存
+delete = 削除
+title-label = タイトル
+completed-label = 完了
+edit-hint = ダブルクリックで編集
+back-to-list = 一覧に戻る
+created-at = 作成: { $date }
+
+due-label = 期限
+due-time-label = 時刻（省略可）
+due-on = 期限 { $date }
+
+filter-all = すべて
+filter-active = 未完了
+filter-completed = 完了
+empty-all = まだTodoがありません。上の入力欄から追加しましょう 🎉
+empty-active = 未完了のTodoはありません。すべて完了です 🎉
+empty-completed = 完了したTodoはまだありません
+empty-search = 「{ $query }」に一致するTodoはありません
+todo-missing = このTodoは存在しないか、削除されました
+page-missing = このページは存在しません。
+
+load-todos-failed = Todoを読み込めませんでした: { $error }
+load-todo-failed = Todoを読み込めませんでした: { $error }
+save-failed = 変更を保存できませんでした: { $error }
+
+sync-synced = ✓ 同期済み
+sync-syncing = ⟳ 同期中…
+sync-offline = オフライン · 未送信 { $count } 件
+sync-failed = 同期に失敗 · 未送信 { $count } 件
+
+shortcut-move = 移動
+shortcut-toggle = 完了切り替え
+shortcut-edit = 編集
+shortcut-delete = 削除
+shortcut-search = 検索
+shortcut-commands = コマンド
+
+transfer-label = インポート・エクスポート
+export-label = エクスポート:
+import-label = ファイルをインポート…
+import-read-failed = テキストファイルではありません
+import-failed = インポートできませんでした: { $error }
+import-preview-title = 「{ $file }」のインポート
+import-summary = 新規 { $imported } 件 · 重複 { $duplicates } 件 · 不正 { $invalid } 件{ $failed ->
+    [0] {""}
+   *[other] {" · "}失敗 { $failed } 件
+}
+import-line = 行
+import-status = 結果
+import-status-imported = 新規
+import-status-duplicate = 重複（スキップ）
+import-status-invalid = 不正（スキップ）
+import-status-failed = 作成に失敗
+import-confirm = { $count } 件をインポート
+cancel = キャンセル
+
+calendar-label = カレンダーフィード
+calendar-hint = このURLをカレンダーアプリで購読すると、期限のあるTodoが表示されます。URLを知っていれば誰でも読めるので共有しないでください
+calendar-subscribe = カレンダーアプリで開く
+calendar-rotate = URLを再発行
+calendar-rotate-confirm = 今のURLは使えなくなります（CalDAVのパスワードは変わりません）。再発行しますか？
+calendar-events = 期限を予定としても表示する
+calendar-load-failed = カレンダーのURLを取得できませんでした: { $error }
+caldav-hint = ThunderbirdやiOSのリマインダーから編集するには、このサーバーとパスワードでCalDAVアカウントを追加してください（ユーザー名は任意）
+caldav-server = CalDAVサーバー
+caldav-password = CalDAVパスワード
+caldav-rotate = パスワードを再発行
+caldav-rotate-confirm = 今のCalDAVのパスワードは使えなくなります（購読URLは変わりません）。再発行しますか？
+
+palette-label = コマンドパレット
+palette-input = コマンド
+palette-placeholder = コマンドを入力…
+palette-empty = 一致するコマンドはありません
+command-show-all = すべてのTodoを表示
+command-show-active = 未完了のTodoを表示
+command-show-completed = 完了したTodoを表示
+command-search = Todoを検索
+command-new = 新しいTodo
+command-toggle = 選択中のTodoの完了を切り替え
+command-edit = 選択中のTodoを編集
+command-delete = 選択中のTodoを削除
+command-complete-all = すべてのTodoを完了にする
+command-activate-all = すべてのTodoを未完了にする
+command-clear-completed = 完了したTodoを削除
+command-sync = 今すぐ同期
+
+# APIのエラーコードごとの文言（IDは `error-` + エラーコード）
+error-title-empty = タイトルは空にできません
+error-todo-not-found = Todoが見つかりません
+error-move-target-not-found = 移動先のTodoが見つかりません
+error-invalid-move = before か after のどちらか一方を指定してください
+error-invalid-due = 期限が不正です
+error-invalid-sync-token = 不正な同期トークンです
+error-unsupported-format = 未対応のファイル形式です（CSV, JSON, Markdown, todo.txt に対応しています）
+error-invalid-import-file = ファイルをインポートできません
+error-invalid-import-row = この行を読み込めません
+error-calendar-feed-not-found = カレンダーフィードが見つかりません
+error-invalid-json = リクエストボディが不正です
+error-batch-too-large = まとめて実行する操作が多すぎます
+error-payload-too-large = リクエストボディが大きすぎます
+error-unsupported-api-version = 未対応のAPIバージョンです
+error-rate-limited = リクエストが多すぎます。{ $seconds }秒後に再試行してください
+error-csrf-token-mismatch = CSRFトークンが一致しません
+error-internal-error = サーバーでエラーが発生しました。しばらくしてから再試行してください
+error-unknown = エラーが発生しました
+# APIに届かなかった場合
+error-network = サーバーに接続できませんでした
+error-http = サーバーエラー (HTTP { $status })
+error-decode = レスポンスを解釈できませんでした
@@ -0,0 +1,172 @@
+//! カレンダーフィードの購読URLとCalDAVの接続先
+//!
+//! URLは初めて開いたときに発行されるため、パネルを開くまでは取得しない。
+//! CalDAVのパスワードはフィードのURLとは別に発行され、それぞれ別に再発行する。
+
+use api_client::{CalendarFeedDto, ClientError};
+use leptos::*;
+
+use crate::i18n::use_i18n;
+use crate::state::use_ctx;
+
+/// 購読URLの取得か、再発行
+#[derive(Clone, Copy)]
+enum Load {
+    Get,
+    RotateFeed,
+    RotateCalDav,
+}
+
+#[component]
+pub fn CalendarFeedPanel() -> impl IntoView {
+    let ctx = use_ctx();
+    let i18n = use_i18n();
+    let feed = create_rw_signal(None::<CalendarFeedDto>);
+    let failure = create_rw_signal(None::<ClientError>);
+    let events = create_rw_signal(false);
+    let loading = store_value(false);
+
+    let load = move |load: Load| {
+        loading.set_value(true);
+        spawn_local(async move {
+            let client = ctx.client();
+            let result = match load {
+                Load::Get => client.calendar_feed().await,
+                Load::RotateFeed => client.rotate_calendar_feed().await,
+                Load::RotateCalDav => client.rotate_caldav_password().await,
+            };
+            loading.set_value(false);
+            match result {
+                Ok(dto) => {
+                    failure.set(None);
+                    feed.set(Some(dto));
+                }
+                Err(e) => failure.set(Some(e)),
+            }
+        });
+    };
+    // このページのオリジンからのURL（プロキシ越しでもブラウザから見えるホストになる）
+    let origin = move || window().location().origin().unwrap_or_default();
+    let url = move || {
+        feed.with(|feed| {
+            feed.as_ref().map(|feed| {
+                let query = if events.get() { "?events=true" } else { "" };
+                format!("{}{}{}", origin(), feed.path, query)
+            })
+        })
+    };
+
+    view! {
+        <details
+            class="transfer"
+            on:toggle=move |ev| {
+                let open = event_target::<web_sys::HtmlDetailsElement>(&ev).open();
+                if open && feed.get_untracked().is_none() && !loading.get_value() {
+                    load(Load::Get);
+                }
+            }
+        >
+            <summary>{move || i18n.t("calendar-label")}</summary>
+            <p class="calendar-hint">{move || i18n.t("calendar-hint")}</p>
+            {move || {
+                url()
+                    .map(|url| {
+                        let webcal = url
+                            .split_once("://")
+                            .map(|(_, rest)| format!("webcal://{}", rest))
+                            .unwrap_or_default();
+                        view! {
+                            <div class="transfer-body">
+                                <CopyField label=move || i18n.t("calendar-label") value=url/>
+                                <a class="transfer-link" href=webcal>
+                                    {move || i18n.t("calendar-subscribe")}
+                                </a>
+                                <button
+                                    class="transfer-link"
+                                    on:click=move |_| {
+                                        let confirmed = window()
+                                            .confirm_with_message(&i18n.t("calendar-rotate-confirm"))
+                                            .unwrap_or(false);
+                                        if confirmed {
+                                            load(Load::RotateFeed);
+                                        }
+                                    }
+                                >
+                                    {move || i18n.t("calendar-rotate")}
+                                </button>
+                            </div>
+                        }
+                    })
+            }}
+            <label class="checkbox">
+                <input
+                    type="checkbox"
+                    prop:checked=events
+                    on:change=move |ev| events.set(event_target_checked(&ev))
+                />
+                {move || i18n.t("calendar-events")}
+            </label>
+            {move || {
+                feed.get()
+                    .map(|feed| {
+                        view! {
+                            <p class="calendar-hint">{move || i18n.t("caldav-hint")}</p>
+                            <div class="transfer-body">
+                                <CopyField
+                                    label=move || i18n.t("caldav-server")
+                                    value=format!("{}{}", origin(), feed.caldav_path)
+                                />
+                                <CopyField
+                                    label=move || i18n.t("caldav-password")
+                                    value=feed.caldav_password
+                                />
+                                <button
+                                    class="transfer-link"
+                                    on:click=move |_| {
+                                        let confirmed = window()
+                                            .confirm_with_message(&i18n.t("caldav-rotate-confirm"))
+                                            .unwrap_or(false);
+                                        if confirmed {
+                                            load(Load::RotateCalDav);
+                                        }
+                                    }
+                                >
+                                    {move || i18n.t("caldav-rotate")}
+                                </button>
+                            </div>
+                        }
+                    })
+            }}
+            {move || {
+                failure
+                    .get()
+                    .map(|error| {
+                        view! {
+                            <p class="field-error" role="alert">
+                                {i18n.t_args("calendar-load-failed", [("error", i18n.error(&error).into())])}
+                            </p>
+                        }
+                    })
+            }}
+        </details>
+    }
+}
+
+/// 選択してコピーするための読み取り専用の欄
+#[component]
+fn CopyField(
+    #[prop(into)] label: Signal<String>,
+    #[prop(into)] value: MaybeSignal<String>,
+) -> impl IntoView {
+    view! {
+        <input
+            class="calendar-url"
+            readonly=true
+            aria-label=label
+            prop:value=value
+            on:focus=move |ev| {
+                event_target::<web_sys::HtmlInputElement>(&ev).select();
+            }
+        />
+    }
+}
@@ -0,0 +1,145 @@
+use api_client::ErrorCode;
+use leptos::*;
+
+use crate::i18n::use_i18n;
+use crate::state::{use_ctx, validate_title, SyncStatus};
+
+#[component]
+pub fn Spinner() -> impl IntoView {
+    let i18n = use_i18n();
+    view! { <div class="spinner" role="status" aria-label=move || i18n.t("loading")></div> }
+}
+
+#[component]
+pub fn ErrorBanner(
+    message: String,
+    #[prop(optional)] on_retry: Option<Callback<()>>,
+    #[prop(optional)] on_dismiss: Option<Callback<()>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    view! {
+        <div class="error-banner" role="alert">
+            <span class="error-message">{message}</span>
+            {on_retry.map(|retry| view! { <button on:click=move |_| retry.call(())>{move || i18n.t("retry")}</button> })}
+            {on_dismiss
+                .map(|dismiss| {
+                    view! {
+                        <button
+                            class="dismiss"
+                            aria-label=move || i18n.t("dismiss")
+                            on:click=move |_| dismiss.call(())
+                        >
+                            "✕"
+                        </button>
+                    }
+                })}
+        </div>
+    }
+}
+
+/// タイトルのインライン編集
+/// Enterで保存、Escapeで取り消し、フォーカスが外れたら保存する
+#[component]
+pub fn TitleEditor(
+    initial: String,
+    on_save: Callback<String>,
+    on_cancel: Callback<()>,
+) -> impl IntoView {
+    let (value, set_value) = create_signal(initial.clone());
+    let initial = store_value(initial);
+    let i18n = use_i18n();
+    let (error, set_error) = create_signal(None::<ErrorCode>);
+    // 保存・取り消しの後に要素が外れてblurが発生しても二重に処理しない
+    let done = store_value(false);
+    let input_ref = create_node_ref::<html::Input>();
+
+    create_effect(move |_| {
+        if let Some(input) = input_ref.get() {
+            let _ = input.focus();
+            input.select();
+        }
+    });
+
+    let finish = move |save: bool| {
+        if done.get_value() {
+            return;
+        }
+        if !save {
+            done.set_value(true);
+            on_cancel.call(());
+            return;
+        }
+        match validate_title(&value.get_untracked()) {
+            Ok(title) => {
+                done.set_value(true);
+                if initial.with_value(|initial| title == *initial) {
+                    on_cancel.call(());
+                } else {
+                    on_save.call(title);
+                }
+            }
+            Err(code) => set_error.set(Some(code)),
+        }
+    };
+
+    view! {
+        <div class="title-editor">
+            <input
+                node_ref=input_ref
+                class="todo-text-input"
+                aria-label=move || i18n.t("title-label")
+                aria-invalid=move || error.get().is_some().to_string()
+                prop:value=value
+                on:input=move |ev| {
+                    set_value.set(event_target_value(&ev));
+                    set_error.set(None);
+                }
+                on:keydown=move |ev| match ev.key().as_str() {
+                    "Enter" => {
+                        ev.prevent_default();
+                        finish(true);
+                    }
+                    "Escape" => finish(false),
+                    _ => {}
+                }
+                on:blur=move |_| {
+                    // 入力が不正なまま離れた場合は取り消す
+                    if validate_title(&value.get_untracked()).is_ok() {
+                        finish(true);
+                    } else {
+                        finish(false);
+                    }
+                }
+            />
+            {move || {
+                error
+                    .get()
+                    .map(|code| view! { <span class="field-error">{i18n.error_code(code)}</span> })
+            }}
+        </div>
+    }
+}
+
+/// 同期状態と未送信の操作の件数
+#[component]
+pub fn SyncBadge() -> impl IntoView {
+    let ctx = use_ctx();
+    let i18n = use_i18n();
+    let pending = move || ctx.queue.with(Vec::len);
+
+    view! {
+        <div class="sync-badge" role="status">
+            {move || match ctx.status.get() {
+                SyncStatus::Synced => i18n.t("sync-synced"),
+                SyncStatus::Syncing => i18n.t("sync-syncing"),
+                SyncStatus::Offline => i18n.t_args("sync-offline", [("count", pending().into())]),
+                SyncStatus::Failed => i18n.t_args("sync-failed", [("count", pending().into())]),
+            }}
+            <Show when=move || {
+                matches!(ctx.status.get(), SyncStatus::Offline | SyncStatus::Failed)
+            }>
+                <button on:click=move |_| ctx.sync()>{move || i18n.t("retry")}</button>
+            </Show>
+        </div>
+    }
+}
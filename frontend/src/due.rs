@@ -0,0 +1,82 @@
+//! 期限の入力と表示
+//!
+//! APIの期限は `YYYY-MM-DD`（終日）かUTCの RFC 3339 の日時。
+//! 画面ではブラウザのタイムゾーンでの日付と時刻として入力・表示する。
+
+use js_sys::{Date, Object, Reflect};
+use leptos::SignalGet;
+use wasm_bindgen::JsValue;
+
+use crate::i18n::I18n;
+
+/// 終日の期限（`YYYY-MM-DD`）の年・月・日
+fn date_only(due: &str) -> Option<(u32, u32, u32)> {
+    if due.len() != 10 {
+        return None;
+    }
+    let mut parts = due.splitn(3, '-').map(|part| part.parse::<u32>().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+/// ブラウザのタイムゾーンでのその日の0時（`day` は月末を超えてもよい）
+fn local_day(year: u32, month: u32, day: u32) -> Date {
+    Date::new_with_year_month_day(year, month as i32 - 1, day as i32)
+}
+
+fn valid(date: Date) -> Option<Date> {
+    (!date.get_time().is_nan()).then_some(date)
+}
+
+/// 入力欄に入れる日付と時刻（終日なら時刻は空）
+pub fn to_inputs(due: &str) -> (String, String) {
+    if date_only(due).is_some() {
+        return (due.to_string(), String::new());
+    }
+    match valid(Date::new(&JsValue::from_str(due))) {
+        Some(at) => (
+            format!(
+                "{:04}-{:02}-{:02}",
+                at.get_full_year(),
+                at.get_month() + 1,
+                at.get_date()
+            ),
+            format!("{:02}:{:02}", at.get_hours(), at.get_minutes()),
+        ),
+        None => (String::new(), String::new()),
+    }
+}
+
+/// 入力欄の日付と時刻からAPIに送る期限（日付が空なら期限なしを表す空文字列）
+pub fn from_inputs(date: &str, time: &str) -> String {
+    if date.is_empty() || time.is_empty() {
+        return date.to_string();
+    }
+    // オフセットのない日時はブラウザのタイムゾーンとして解釈される
+    match valid(Date::new(&JsValue::from_str(&format!("{}T{}", date, time)))) {
+        Some(at) => at.to_iso_string().into(),
+        None => date.to_string(),
+    }
+}
+
+/// 期限を過ぎたか（終日の期限はその日の終わりまで）
+pub fn is_overdue(due: &str) -> bool {
+    let end = match date_only(due) {
+        Some((year, month, day)) => Some(local_day(year, month, day + 1)),
+        None => valid(Date::new(&JsValue::from_str(due))),
+    };
+    end.is_some_and(|end| end.get_time() <= Date::now())
+}
+
+/// 表示中の言語の書式での期限
+pub fn label(i18n: I18n, due: &str) -> Option<String> {
+    let Some((year, month, day)) = date_only(due) else {
+        return i18n.date_time(due);
+    };
+    let options = Object::new();
+    let _ = Reflect::set(&options, &"dateStyle".into(), &"medium".into());
+    Some(
+        local_day(year, month, day)
+            .to_locale_date_string(i18n.locale.get().as_str(), &options)
+            .into(),
+    )
+}
@@ -0,0 +1,267 @@
+//! 画面の多言語対応
+//!
+//! 文言はFluentのカタログ（`frontend/locales/*.ftl`）から引く。
+//! 言語は保存した選択、なければブラウザの言語から決め、APIにも `Accept-Language` で伝える。
+
+use api_client::{ClientError, ErrorCode};
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use leptos::*;
+use unic_langid::{langid, LanguageIdentifier};
+use wasm_bindgen::JsValue;
+
+/// 選んだ言語の保存先
+const LOCALE_KEY: &str = "todo-app.v1.locale";
+
+/// 対応している言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Ja,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Ja];
+
+    /// 言語タグ（`Accept-Language` と `<html lang>` に使う）
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ja => "ja",
+        }
+    }
+
+    /// 切り替えの選択肢に表示する名前（その言語での表記）
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Ja => "日本語",
+        }
+    }
+
+    /// `ja-JP` のような言語タグから選ぶ（対応していなければ `None`）
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|l| l.as_str() == language)
+    }
+
+    fn langid(self) -> LanguageIdentifier {
+        match self {
+            Locale::En => langid!("en"),
+            Locale::Ja => langid!("ja"),
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.ftl"),
+            Locale::Ja => include_str!("../locales/ja.ftl"),
+        }
+    }
+
+    /// 保存した言語、なければブラウザの言語（どちらも対応していなければ英語）
+    fn detect() -> Locale {
+        let saved = local_storage().and_then(|storage| storage.get_item(LOCALE_KEY).ok().flatten());
+        saved
+            .or_else(|| window().navigator().language())
+            .and_then(|tag| Self::from_tag(&tag))
+            .unwrap_or(Locale::En)
+    }
+
+    /// カタログのメッセージ（IDが見つからなければIDをそのまま返す）
+    pub fn message(self, id: &str, args: Option<&FluentArgs>) -> String {
+        BUNDLES.with(|bundles| {
+            let bundle = &bundles[self as usize];
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                return id.to_string();
+            };
+            let mut errors = Vec::new();
+            bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned()
+        })
+    }
+}
+
+thread_local! {
+    /// 言語ごとのカタログ（`Locale` の並びと同じ順）
+    static BUNDLES: [FluentBundle<FluentResource>; 2] = Locale::ALL.map(|locale| {
+        let resource = FluentResource::try_new(locale.source().to_string())
+            .expect("メッセージカタログの構文が不正です");
+        let mut bundle = FluentBundle::new(vec![locale.langid()]);
+        // 引数の前後に方向制御文字を入れない
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .expect("メッセージIDが重複しています");
+        bundle
+    });
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// 表示中の言語（`provide_context` で配る）
+#[derive(Clone, Copy)]
+pub struct I18n {
+    pub locale: RwSignal<Locale>,
+}
+
+impl I18n {
+    /// 言語を決めて子コンポーネントに配る（変更は保存し、`<html lang>` にも反映する）
+    pub fn provide() -> Self {
+        let i18n = Self {
+            locale: create_rw_signal(Locale::detect()),
+        };
+        provide_context(i18n);
+
+        create_effect(move |_| {
+            let locale = i18n.locale.get();
+            if let Some(storage) = local_storage() {
+                let _ = storage.set_item(LOCALE_KEY, locale.as_str());
+            }
+            if let Some(html) = document().document_element() {
+                let _ = html.set_attribute("lang", locale.as_str());
+            }
+        });
+        i18n
+    }
+
+    /// 表示中の言語の文言（言語を切り替えると再描画される）
+    pub fn t(self, id: &str) -> String {
+        self.locale.get().message(id, None)
+    }
+
+    /// 引数を埋め込んだ文言
+    pub fn t_args<'a>(
+        self,
+        id: &str,
+        args: impl IntoIterator<Item = (&'a str, FluentValue<'a>)>,
+    ) -> String {
+        let args = FluentArgs::from_iter(args);
+        self.locale.get().message(id, Some(&args))
+    }
+
+    /// エラーコードの文言
+    pub fn error_code(self, code: ErrorCode) -> String {
+        self.t(&code.message_id())
+    }
+
+    /// APIクライアントのエラーの文言（コードで選び、知らないコードならサーバーのメッセージを使う）
+    pub fn error(self, error: &ClientError) -> String {
+        match error {
+            ClientError::Api { body, .. } => match body.error {
+                ErrorCode::Unknown => body.message.clone(),
+                ErrorCode::RateLimited => self.t_args(
+                    "error-rate-limited",
+                    [("seconds", body.retry_after_secs.unwrap_or(1).into())],
+                ),
+                code => self.error_code(code),
+            },
+            ClientError::Http { status, .. } => {
+                self.t_args("error-http", [("status", (*status).into())])
+            }
+            ClientError::Network(_) => self.t("error-network"),
+            ClientError::Decode(_) => self.t("error-decode"),
+        }
+    }
+
+    /// RFC 3339 の日時を表示中の言語の書式で（解釈できなければ `None`）
+    pub fn date_time(self, rfc3339: &str) -> Option<String> {
+        let date = js_sys::Date::new(&JsValue::from_str(rfc3339));
+        if date.get_time().is_nan() {
+            return None;
+        }
+        let options = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&options, &"dateStyle".into(), &"medium".into());
+        let _ = js_sys::Reflect::set(&options, &"timeStyle".into(), &"short".into());
+        Some(
+            date.to_locale_string(self.locale.get().as_str(), &options)
+                .into(),
+        )
+    }
+}
+
+pub fn use_i18n() -> I18n {
+    expect_context::<I18n>()
+}
+
+/// 言語の切り替え
+#[component]
+pub fn LocaleSwitcher() -> impl IntoView {
+    let i18n = use_i18n();
+
+    view! {
+        <select
+            class="locale-switcher"
+            aria-label=move || i18n.t("locale-label")
+            on:change=move |ev| {
+                if let Some(locale) = Locale::from_tag(&event_target_value(&ev)) {
+                    i18n.locale.set(locale);
+                }
+            }
+        >
+            {Locale::ALL
+                .into_iter()
+                .map(|locale| {
+                    view! {
+                        <option
+                            value=locale.as_str()
+                            selected=move || i18n.locale.get() == locale
+                        >
+                            {locale.label()}
+                        </option>
+                    }
+                })
+                .collect_view()}
+        </select>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// カタログに定義されたメッセージID
+    fn ids(locale: Locale) -> BTreeSet<&'static str> {
+        locale
+            .source()
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id))
+            .collect()
+    }
+
+    #[test]
+    fn test_locale_tags_and_catalogs() {
+        assert_eq!(Locale::from_tag("ja-JP"), Some(Locale::Ja));
+        assert_eq!(Locale::from_tag("EN_us"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("fr"), None);
+
+        // どの言語にも同じIDがそろっていて、すべてのエラーコードの文言がある
+        let english = ids(Locale::En);
+        for locale in Locale::ALL {
+            assert_eq!(
+                ids(locale),
+                english,
+                "{} のIDが英語と一致しません",
+                locale.as_str()
+            );
+        }
+        for code in ErrorCode::ALL.into_iter().chain([ErrorCode::Unknown]) {
+            assert!(english.contains(code.message_id().as_str()), "{:?}", code);
+        }
+
+        let mut args = FluentArgs::new();
+        args.set("seconds", 1);
+        assert_eq!(
+            Locale::En.message("error-rate-limited", Some(&args)),
+            "Too many requests. Please retry in 1 second."
+        );
+        assert_eq!(
+            Locale::Ja.message("sync-offline", Some(&FluentArgs::from_iter([("count", 2)]))),
+            "オフライン · 未送信 2 件"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! キーボード操作
+//!
+//! 一覧では j/k で選択を移動し、x で完了の切り替え、e で編集、dd で削除、/ で検索する。
+//! Ctrl+K（macOSではCmd+K）のコマンドパレットからはすべての操作を呼び出せる。
+
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// 一覧のショートカット
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    Next,
+    Previous,
+    Toggle,
+    Edit,
+    Delete,
+    Search,
+    /// 選択を外す
+    Deselect,
+}
+
+/// 押されたキーをショートカットに変換する
+/// dd のような2打鍵のショートカットのために、直前のキーを覚えておく
+#[derive(Debug, Default)]
+pub struct KeySequence {
+    pending: Option<char>,
+}
+
+impl KeySequence {
+    pub fn feed(&mut self, key: &str) -> Option<Shortcut> {
+        let pending = self.pending.take();
+        match key {
+            "j" | "ArrowDown" => Some(Shortcut::Next),
+            "k" | "ArrowUp" => Some(Shortcut::Previous),
+            "x" => Some(Shortcut::Toggle),
+            "e" => Some(Shortcut::Edit),
+            "/" => Some(Shortcut::Search),
+            "Escape" => Some(Shortcut::Deselect),
+            "d" if pending == Some('d') => Some(Shortcut::Delete),
+            "d" => {
+                self.pending = Some('d');
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 表示中のTodo（`ids`）の中で選択を1つ進める・戻す
+/// 未選択（または選択中のTodoが表示されていない）なら先頭・末尾を選ぶ
+pub fn step(ids: &[i32], current: Option<i32>, forward: bool) -> Option<i32> {
+    let index = current.and_then(|current| ids.iter().position(|id| *id == current));
+    let next = match (index, forward) {
+        (None, true) => 0,
+        (None, false) => ids.len().checked_sub(1)?,
+        (Some(index), true) => (index + 1).min(ids.len() - 1),
+        (Some(index), false) => index.saturating_sub(1),
+    };
+    ids.get(next).copied()
+}
+
+/// 削除したTodoの次に選択するTodo（次がなければ前）
+pub fn after_removal(ids: &[i32], removed: i32) -> Option<i32> {
+    let index = ids.iter().position(|id| *id == removed)?;
+    ids.get(index + 1)
+        .or_else(|| index.checked_sub(1).and_then(|i| ids.get(i)))
+        .copied()
+}
+
+/// ショートカットとして扱うキー入力か（入力欄での入力や修飾キー付きの操作は除く）
+pub fn is_plain_key(ev: &ev::KeyboardEvent) -> bool {
+    if ev.ctrl_key() || ev.meta_key() || ev.alt_key() {
+        return false;
+    }
+    let Some(target) = ev
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+    else {
+        return true;
+    };
+    let tag = target.tag_name();
+    !(matches!(tag.as_str(), "INPUT" | "TEXTAREA" | "SELECT") || target.is_content_editable())
+}
+
+/// コマンドパレットを開くキーか
+pub fn is_palette_key(ev: &ev::KeyboardEvent) -> bool {
+    (ev.ctrl_key() || ev.meta_key()) && ev.key().eq_ignore_ascii_case("k")
+}
+
+/// セレクタに一致する要素にフォーカスする
+pub fn focus(selector: &str) {
+    if let Some(element) = document()
+        .query_selector(selector)
+        .ok()
+        .flatten()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        let _ = element.focus();
+    }
+}
+
+/// 選択中の行が見えるようにスクロールする
+pub fn scroll_to(id: i32) {
+    if let Ok(Some(element)) = document().query_selector(&format!("[data-todo-id=\"{}\"]", id)) {
+        let options = web_sys::ScrollIntoViewOptions::new();
+        options.set_block(web_sys::ScrollLogicalPosition::Nearest);
+        element.scroll_into_view_with_scroll_into_view_options(&options);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_sequence_and_selection() {
+        let mut keys = KeySequence::default();
+        assert_eq!(keys.feed("j"), Some(Shortcut::Next));
+        assert_eq!(keys.feed("d"), None);
+        assert_eq!(keys.feed("d"), Some(Shortcut::Delete));
+        // 間に別のキーを挟むと dd にならない
+        assert_eq!(keys.feed("d"), None);
+        assert_eq!(keys.feed("x"), Some(Shortcut::Toggle));
+        assert_eq!(keys.feed("d"), None);
+        assert_eq!(keys.feed("q"), None);
+        assert_eq!(keys.feed("d"), None);
+
+        let ids = [3, 1, 2];
+        assert_eq!(step(&ids, None, true), Some(3));
+        assert_eq!(step(&ids, None, false), Some(2));
+        assert_eq!(step(&ids, Some(1), true), Some(2));
+        assert_eq!(step(&ids, Some(2), true), Some(2));
+        assert_eq!(step(&ids, Some(3), false), Some(3));
+        assert_eq!(step(&[], None, true), None);
+
+        assert_eq!(after_removal(&ids, 1), Some(2));
+        assert_eq!(after_removal(&ids, 2), Some(1));
+        assert_eq!(after_removal(&[5], 5), None);
+    }
+}
@@ -1,133 +1,118 @@
-use gloo_net::http::Request;
-use leptos::*;
-use serde::{Deserialize, Serialize};
+mod calendar;
+mod components;
+mod due;
+mod i18n;
+mod keyboard;
+mod offline;
+mod pages;
+mod palette;
+mod state;
+mod transfer;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct Todo {
-    pub id: i32,
-    pub title: String,
-    pub completed: bool,
-}
+use leptos::*;
+use leptos_router::{
+    escape, use_location, use_navigate, use_query_map, NavigateOptions, Route, Router, Routes,
+};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct User {
-    pub id: i32,
-    pub username: String,
-    pub email: String,
-    pub is_active: bool,
-}
+use calendar::CalendarFeedPanel;
+use components::{ErrorBanner, SyncBadge};
+use i18n::{use_i18n, I18n, LocaleSwitcher};
+use pages::{Filter, NotFoundPage, TodoDetailPage, TodoListPage};
+use palette::CommandPalette;
+use state::{add_todo, validate_title, Ctx};
+use transfer::TransferPanel;
 
 #[component]
 pub fn App() -> impl IntoView {
-    let (todos, set_todos) = create_signal(Vec::<Todo>::new());
-    let (users, set_users) = create_signal(Vec::<User>::new());
-
-    // Fetch todos on load
-    create_effect(move |_| {
-        spawn_local(async move {
-            let fetched_todos: Vec<Todo> = Request::get("/api/todos")
-                .send()
-                .await
-                .unwrap()
-                .json()
-                .await
-                .unwrap();
-            set_todos.set(fetched_todos);
-        });
+    let i18n = I18n::provide();
+    let ctx = Ctx::provide(i18n);
+    let palette_open = create_rw_signal(false);
+    window_event_listener(ev::keydown, move |ev| {
+        if keyboard::is_palette_key(&ev) {
+            ev.prevent_default();
+            palette_open.update(|open| *open = !*open);
+        }
     });
 
-    let add_todo = move |title: String| {
-        spawn_local(async move {
-            let new_todo = serde_json::json!({
-                "title": title,
-                "completed": false
-            });
-            let res = Request::post("/api/todos")
-                .json(&new_todo)
-                .unwrap()
-                .send()
-                .await
-                .unwrap();
-
-            if res.ok() {
-                let todo: Todo = res.json().await.unwrap();
-                set_todos.update(|t| t.push(todo));
-            }
-        });
-    };
-
-    let toggle_todo = move |id: i32, completed: bool| {
-        spawn_local(async move {
-            let update = serde_json::json!({ "completed": !completed });
-            let res = Request::put(&format!("/api/todos/{}", id))
-                .json(&update)
-                .unwrap()
-                .send()
-                .await
-                .unwrap();
-
-            if res.ok() {
-                set_todos.update(|t| {
-                    if let Some(todo) = t.iter_mut().find(|t| t.id == id) {
-                        todo.completed = !completed;
-                    }
-                });
-            }
-        });
-    };
-
-    let delete_todo = move |id: i32| {
-        spawn_local(async move {
-            let res = Request::delete(&format!("/api/todos/{}", id))
-                .send()
-                .await
-                .unwrap();
-
-            if res.ok() {
-                set_todos.update(|t| t.retain(|todo| todo.id != id));
-            }
-        });
-    };
-
     view! {
-        <div class="container">
-            <h1>"✨ Modern Todo"</h1>
-            <div class="input-group">
-                <textarea id="new-todo" placeholder="What needs to be done? ✍️"
-                    on:keydown=move |ev| {
-                        if ev.key() == "Enter" && !ev.shift_key() {
-                            ev.prevent_default();
-                            let input = event_target::<web_sys::HtmlTextAreaElement>(&ev);
-                            let value = input.value();
-                            if !value.is_empty() {
-                                add_todo(value);
-                                input.set_value("");
+        <Router>
+            <CommandPalette open=palette_open/>
+            <div class="container">
+                <header class="app-header">
+                    <h1>{move || i18n.t("app-title")}</h1>
+                    <LocaleSwitcher/>
+                </header>
+                <SyncBadge/>
+                <div class="input-group">
+                    <textarea id="new-todo" placeholder=move || i18n.t("new-todo-placeholder")
+                        on:keydown=move |ev| {
+                            if ev.key() == "Enter" && !ev.shift_key() {
+                                ev.prevent_default();
+                                let input = event_target::<web_sys::HtmlTextAreaElement>(&ev);
+                                if let Ok(title) = validate_title(&input.value()) {
+                                    add_todo(ctx, title);
+                                    input.set_value("");
+                                }
                             }
                         }
-                    }
-                />
+                    />
+                </div>
+                <SearchBox/>
+                <TransferPanel/>
+                <CalendarFeedPanel/>
+                {move || ctx.failure.get().map(|error| view! {
+                    <ErrorBanner
+                        message=i18n.t_args("save-failed", [("error", i18n.error(&error).into())])
+                        on_dismiss=Callback::new(move |_| ctx.failure.set(None))
+                    />
+                })}
+                <Routes>
+                    <Route path="/" view=|| view! { <TodoListPage filter=Filter::All/> }/>
+                    <Route path="/active" view=|| view! { <TodoListPage filter=Filter::Active/> }/>
+                    <Route path="/completed" view=|| view! { <TodoListPage filter=Filter::Completed/> }/>
+                    <Route path="/search" view=|| view! { <TodoListPage filter=Filter::Search/> }/>
+                    <Route path="/todos/:id" view=TodoDetailPage/>
+                    <Route path="/*any" view=NotFoundPage/>
+                </Routes>
             </div>
-            <ul class="todo-list">
-                <For
-                    each=move || todos.get()
-                    key=|todo| todo.id
-                    children=move |todo| {
-                        let title = todo.title.clone();
-                        view! {
-                            <li class={if todo.completed { "completed" } else { "" }}>
-                                <div class="todo-checkbox" on:click=move |_| toggle_todo(todo.id, todo.completed)>
-                                    <span class="checkmark">"✓"</span>
-                                </div>
-                                <div class="todo-text" on:click=move |_| toggle_todo(todo.id, todo.completed)>
-                                    {title}
-                                </div>
-                                <span class="todo-id">"#"{todo.id}</span>
-                                <button on:click=move |_| delete_todo(todo.id)>"Delete"</button>
-                            </li>
-                        }
-                    }
-                />
-            </ul>
-        </div>
+        </Router>
+    }
+}
+
+/// タイトル検索
+/// 入力内容を `/search?q=` に反映する（入力中は履歴を積まない）
+#[component]
+fn SearchBox() -> impl IntoView {
+    let navigate = use_navigate();
+    let location = use_location();
+    let query_map = use_query_map();
+    let i18n = use_i18n();
+    let on_search = move || location.pathname.with(|p| p == "/search");
+
+    view! {
+        <input
+            class="search"
+            type="search"
+            placeholder=move || i18n.t("search-placeholder")
+            prop:value=move || {
+                if on_search() {
+                    query_map.with(|q| q.get("q").cloned().unwrap_or_default())
+                } else {
+                    String::new()
+                }
+            }
+            on:input=move |ev| {
+                let value = event_target_value(&ev);
+                let options = NavigateOptions {
+                    replace: on_search(),
+                    ..Default::default()
+                };
+                if value.is_empty() {
+                    navigate("/", options);
+                } else {
+                    navigate(&format!("/search?q={}", escape(&value)), options);
+                }
+            }
+        />
     }
 }
@@ -0,0 +1,377 @@
+//! オフライン対応
+//!
+//! 一覧と未送信の操作をlocalStorageに保存し、オンラインになったら順に送信する。
+//! オフラインで作成したTodoには負の仮IDを振り、作成が反映されたらサーバーのIDに置き換える。
+//! サーバーの一覧は同期トークンとともに保存し、次回からは差分だけを取得する。
+
+use api_client::{MoveTodoDto, SyncResponseDto, TodoResponseDto as Todo, UpdateTodoDto};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+const TODOS_KEY: &str = "todo-app.v1.todos";
+const SNAPSHOT_KEY: &str = "todo-app.v1.snapshot";
+const QUEUE_KEY: &str = "todo-app.v1.queue";
+const NEXT_TEMP_ID_KEY: &str = "todo-app.v1.next-temp-id";
+
+/// サーバーに未送信の操作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PendingOp {
+    /// `temp_id` は作成が反映されるまでの仮ID（負の数）
+    Create {
+        temp_id: i32,
+        title: String,
+        completed: bool,
+    },
+    /// 変更した項目だけを送る（サーバー側の他の項目の変更は上書きしない）
+    Update {
+        id: i32,
+        changes: UpdateTodoDto,
+    },
+    /// `target` のTodoの直前・直後に移動する
+    Move {
+        id: i32,
+        target: MoveTodoDto,
+    },
+    Delete {
+        id: i32,
+    },
+}
+
+impl PendingOp {
+    pub fn target_id(&self) -> i32 {
+        match self {
+            PendingOp::Create { temp_id, .. } => *temp_id,
+            PendingOp::Update { id, .. }
+            | PendingOp::Move { id, .. }
+            | PendingOp::Delete { id } => *id,
+        }
+    }
+
+    /// 操作を一覧に反映
+    pub fn apply(&self, list: &mut Vec<Todo>) {
+        match self {
+            PendingOp::Create {
+                temp_id,
+                title,
+                completed,
+            } => list.push(Todo {
+                id: *temp_id,
+                title: title.clone(),
+                completed: *completed,
+                // サーバーでも末尾に置かれる
+                position: list.last().map_or(1.0, |last| last.position.floor() + 1.0),
+                // 作成日時はサーバーに保存されるまでわからない
+                created_at: None,
+                due: None,
+                completed_at: None,
+            }),
+            PendingOp::Update { id, changes } => {
+                if let Some(todo) = list.iter_mut().find(|t| t.id == *id) {
+                    if let Some(title) = &changes.title {
+                        todo.title = title.clone();
+                    }
+                    if let Some(completed) = changes.completed {
+                        todo.completed = completed;
+                    }
+                    if let Some(due) = &changes.due {
+                        todo.due = (!due.is_empty()).then(|| due.clone());
+                    }
+                }
+            }
+            PendingOp::Move { id, target } => {
+                let Some(from) = list.iter().position(|t| t.id == *id) else {
+                    return;
+                };
+                let todo = list.remove(from);
+                let anchor = target.before.or(target.after);
+                let to = match list.iter().position(|t| Some(t.id) == anchor) {
+                    Some(index) if target.after.is_some() => index + 1,
+                    Some(index) => index,
+                    // 移動先が見つからなければ元の位置に戻す
+                    None => from,
+                };
+                list.insert(to, todo);
+            }
+            PendingOp::Delete { id } => list.retain(|t| t.id != *id),
+        }
+    }
+}
+
+/// 未送信の操作を積む
+/// 未送信のTodoを削除する場合は、作成ごと取り消してサーバーには送らない
+pub fn enqueue(queue: &mut Vec<PendingOp>, op: PendingOp) {
+    if let PendingOp::Delete { id } = op {
+        let unsent = queue
+            .iter()
+            .any(|queued| matches!(queued, PendingOp::Create { temp_id, .. } if *temp_id == id));
+        if unsent {
+            queue.retain(|queued| queued.target_id() != id);
+            return;
+        }
+    }
+    queue.push(op);
+}
+
+/// 作成が反映されたら、仮IDをサーバーのIDに置き換える
+pub fn assign_server_id(list: &mut [Todo], queue: &mut [PendingOp], temp_id: i32, id: i32) {
+    for todo in list.iter_mut().filter(|t| t.id == temp_id) {
+        todo.id = id;
+    }
+    for op in queue.iter_mut() {
+        match op {
+            PendingOp::Update { id: target, .. } | PendingOp::Delete { id: target }
+                if *target == temp_id =>
+            {
+                *target = id
+            }
+            PendingOp::Move { id: moved, target } => {
+                let anchors = [&mut target.before, &mut target.after];
+                for anchor in std::iter::once(moved).chain(anchors.into_iter().flatten()) {
+                    if *anchor == temp_id {
+                        *anchor = id;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 最後に同期した時点のサーバーの一覧
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// 次回の同期で渡すトークン（一度も同期していなければ `None`）
+    pub token: Option<String>,
+    pub todos: Vec<Todo>,
+}
+
+impl Snapshot {
+    /// 同期で取得した変更を反映する（差分の場合は並び順で並べ直す）
+    pub fn apply(&mut self, changes: SyncResponseDto) {
+        if changes.full {
+            self.todos = changes.todos;
+        } else {
+            self.todos.retain(|t| !changes.deleted.contains(&t.id));
+            for todo in changes.todos {
+                match self.todos.iter_mut().find(|t| t.id == todo.id) {
+                    Some(existing) => *existing = todo,
+                    None => self.todos.push(todo),
+                }
+            }
+            self.todos.sort_by(|a, b| a.position.total_cmp(&b.position));
+        }
+        self.token = Some(changes.token);
+    }
+}
+
+/// サーバーの一覧に未送信の操作を重ねる（サーバーの状態を正とし、その上に手元の変更を適用する）
+pub fn reconcile(server: Vec<Todo>, queue: &[PendingOp]) -> Vec<Todo> {
+    let mut list = server;
+    for op in queue {
+        op.apply(&mut list);
+    }
+    list
+}
+
+/// localStorageへの保存
+/// 使えない環境（プライベートモードなど）では何もしない
+pub mod storage {
+    use super::*;
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+        let raw = local_storage()?.get_item(key).ok()??;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn save<T: Serialize>(key: &str, value: &T) {
+        if let (Some(storage), Ok(raw)) = (local_storage(), serde_json::to_string(value)) {
+            let _ = storage.set_item(key, &raw);
+        }
+    }
+
+    /// 前回保存した一覧（一度も読み込んでいなければ `None`）
+    pub fn load_todos() -> Option<Vec<Todo>> {
+        load(TODOS_KEY)
+    }
+
+    pub fn save_todos(todos: &[Todo]) {
+        save(TODOS_KEY, &todos);
+    }
+
+    pub fn load_snapshot() -> Snapshot {
+        load(SNAPSHOT_KEY).unwrap_or_default()
+    }
+
+    pub fn save_snapshot(snapshot: &Snapshot) {
+        save(SNAPSHOT_KEY, snapshot);
+    }
+
+    pub fn load_queue() -> Vec<PendingOp> {
+        load(QUEUE_KEY).unwrap_or_default()
+    }
+
+    pub fn save_queue(queue: &[PendingOp]) {
+        save(QUEUE_KEY, &queue);
+    }
+
+    /// 新しい仮ID（-1, -2, ... とリロードをまたいで重複しないように振る）
+    pub fn next_temp_id() -> i32 {
+        let id = load::<i32>(NEXT_TEMP_ID_KEY).unwrap_or(-1);
+        save(NEXT_TEMP_ID_KEY, &(id - 1));
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: i32, title: &str, completed: bool) -> Todo {
+        Todo {
+            id,
+            title: title.to_string(),
+            completed,
+            position: id as f64,
+            created_at: None,
+            due: None,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_offline_changes_replay_over_server_state() {
+        let mut queue = Vec::new();
+        enqueue(
+            &mut queue,
+            PendingOp::Create {
+                temp_id: -1,
+                title: "牛乳を買う".to_string(),
+                completed: false,
+            },
+        );
+        enqueue(
+            &mut queue,
+            PendingOp::Update {
+                id: -1,
+                changes: UpdateTodoDto {
+                    completed: Some(true),
+                    ..Default::default()
+                },
+            },
+        );
+        enqueue(
+            &mut queue,
+            PendingOp::Update {
+                id: 1,
+                changes: UpdateTodoDto {
+                    title: Some("手元で変更".to_string()),
+                    ..Default::default()
+                },
+            },
+        );
+        enqueue(
+            &mut queue,
+            PendingOp::Move {
+                id: 1,
+                target: MoveTodoDto {
+                    after: Some(-1),
+                    ..Default::default()
+                },
+            },
+        );
+
+        // サーバー側で完了にされたTodoでも、手元で変えたタイトルだけが上書きされる
+        let server = vec![todo(1, "元のタイトル", true)];
+        let mut list = reconcile(server, &queue);
+        assert_eq!(
+            list,
+            vec![
+                Todo {
+                    position: 2.0,
+                    ..todo(-1, "牛乳を買う", true)
+                },
+                todo(1, "手元で変更", true),
+            ]
+        );
+
+        // 作成が反映されたら後続の操作も新しいIDを指す
+        queue.remove(0);
+        assign_server_id(&mut list, &mut queue, -1, 7);
+        assert_eq!(list[0].id, 7);
+        assert_eq!(queue[0].target_id(), 7);
+        assert_eq!(
+            queue[2],
+            PendingOp::Move {
+                id: 1,
+                target: MoveTodoDto {
+                    after: Some(7),
+                    ..Default::default()
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_deleting_unsent_todo_cancels_its_creation() {
+        let mut queue = vec![
+            PendingOp::Create {
+                temp_id: -2,
+                title: "下書き".to_string(),
+                completed: false,
+            },
+            PendingOp::Update {
+                id: -2,
+                changes: UpdateTodoDto {
+                    completed: Some(true),
+                    ..Default::default()
+                },
+            },
+        ];
+        enqueue(&mut queue, PendingOp::Delete { id: -2 });
+        assert!(queue.is_empty());
+
+        enqueue(&mut queue, PendingOp::Delete { id: 3 });
+        assert_eq!(queue, vec![PendingOp::Delete { id: 3 }]);
+    }
+
+    #[test]
+    fn test_snapshot_applies_delta_and_full_sync() {
+        let mut snapshot = Snapshot {
+            token: Some("3".to_string()),
+            todos: vec![todo(1, "牛乳を買う", false), todo(2, "掃除", false)],
+        };
+
+        // 並べ替えられたTodoは新しい位置に並ぶ
+        let moved = Todo {
+            position: 5.0,
+            ..todo(1, "牛乳を買う", true)
+        };
+        snapshot.apply(SyncResponseDto {
+            todos: vec![moved.clone(), todo(4, "洗濯", false)],
+            deleted: vec![2],
+            token: "5".to_string(),
+            full: false,
+        });
+        assert_eq!(
+            snapshot,
+            Snapshot {
+                token: Some("5".to_string()),
+                todos: vec![todo(4, "洗濯", false), moved],
+            }
+        );
+
+        // 全件の場合は手元の一覧を置き換える
+        snapshot.apply(SyncResponseDto {
+            todos: vec![todo(4, "洗濯", false)],
+            deleted: vec![],
+            token: "1".to_string(),
+            full: true,
+        });
+        assert_eq!(snapshot.todos, vec![todo(4, "洗濯", false)]);
+        assert_eq!(snapshot.token.as_deref(), Some("1"));
+    }
+}
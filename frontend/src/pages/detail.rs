@@ -0,0 +1,137 @@
+use api_client::{ErrorCode, UpdateTodoDto};
+use leptos::*;
+use leptos_router::{use_navigate, use_params_map, A};
+
+use crate::components::{ErrorBanner, Spinner};
+use crate::due;
+use crate::i18n::use_i18n;
+use crate::state::{delete_todo, update_todo, use_ctx, validate_title, ListState};
+
+/// Todoの詳細・編集ページ
+#[component]
+pub fn TodoDetailPage() -> impl IntoView {
+    let ctx = use_ctx();
+    let i18n = use_i18n();
+    let params = use_params_map();
+    let id = move || params.with(|p| p.get("id").and_then(|id| id.parse::<i32>().ok()));
+    let todo = move || id().and_then(|id| ctx.filtered(|t| t.id == id).into_iter().next());
+    let state = ctx.list_state(move |t| Some(t.id) == id());
+    let navigate = use_navigate();
+
+    view! {
+        <A class="back-link" href="/">"← "{move || i18n.t("back-to-list")}</A>
+        {move || match state.get() {
+            ListState::Loading => view! { <Spinner/> }.into_view(),
+            ListState::Failed(e) => {
+                view! {
+                    <ErrorBanner
+                        message=i18n.t_args("load-todo-failed", [("error", i18n.error(&e).into())])
+                        on_retry=Callback::new(move |_| ctx.sync())
+                    />
+                }
+                    .into_view()
+            }
+            ListState::Empty => {
+                view! { <p class="empty-state">{move || i18n.t("todo-missing")}</p> }
+                    .into_view()
+            }
+            ListState::Ready => {
+                let Some(todo) = todo() else {
+                    return ().into_view();
+                };
+                let id = todo.id;
+                let (title, set_title) = create_signal(todo.title.clone());
+                let (completed, set_completed) = create_signal(todo.completed);
+                let (error, set_error) = create_signal(None::<ErrorCode>);
+                let (due_date, due_time) = todo.due.as_deref().map(due::to_inputs).unwrap_or_default();
+                let (due_date, set_due_date) = create_signal(due_date);
+                let (due_time, set_due_time) = create_signal(due_time);
+                let created_at = todo.created_at.clone();
+                let navigate = navigate.clone();
+                let save = move |ev: ev::SubmitEvent| {
+                    ev.prevent_default();
+                    match validate_title(&title.get()) {
+                        Ok(title) => update_todo(
+                            ctx,
+                            id,
+                            UpdateTodoDto {
+                                title: Some(title),
+                                completed: Some(completed.get()),
+                                due: Some(due::from_inputs(&due_date.get(), &due_time.get())),
+                            },
+                        ),
+                        Err(code) => set_error.set(Some(code)),
+                    }
+                };
+                view! {
+                    <form class="todo-detail" on:submit=save>
+                        <label for="todo-title">{move || i18n.t("title-label")}</label>
+                        <textarea
+                            id="todo-title"
+                            aria-invalid=move || error.get().is_some().to_string()
+                            prop:value=title
+                            on:input=move |ev| {
+                                set_title.set(event_target_value(&ev));
+                                set_error.set(None);
+                            }
+                        ></textarea>
+                        {move || {
+                            error
+                                .get()
+                                .map(|code| view! { <span class="field-error">{i18n.error_code(code)}</span> })
+                        }}
+                        <label class="checkbox">
+                            <input
+                                type="checkbox"
+                                prop:checked=completed
+                                on:change=move |ev| set_completed.set(event_target_checked(&ev))
+                            />
+                            {move || i18n.t("completed-label")}
+                        </label>
+                        <label for="todo-due">{move || i18n.t("due-label")}</label>
+                        <div class="due-inputs">
+                            <input
+                                id="todo-due"
+                                type="date"
+                                prop:value=due_date
+                                on:input=move |ev| set_due_date.set(event_target_value(&ev))
+                            />
+                            <input
+                                type="time"
+                                aria-label=move || i18n.t("due-time-label")
+                                title=move || i18n.t("due-time-label")
+                                disabled=move || due_date.get().is_empty()
+                                prop:value=due_time
+                                on:input=move |ev| set_due_time.set(event_target_value(&ev))
+                            />
+                        </div>
+                        <div class="actions">
+                            <button type="submit" class="primary">{move || i18n.t("save")}</button>
+                            <button
+                                type="button"
+                                on:click=move |_| {
+                                    delete_todo(ctx, id);
+                                    navigate("/", Default::default());
+                                }
+                            >
+                                {move || i18n.t("delete")}
+                            </button>
+                        </div>
+                        <span class="todo-id">"#"{id}</span>
+                        {created_at.map(|created_at| {
+                            view! {
+                                <span class="created-at">
+                                    {move || {
+                                        i18n.date_time(&created_at)
+                                            .map(|date| i18n.t_args("created-at", [("date", date.into())]))
+                                    }}
+                                </span>
+                            }
+                        })}
+                    </form>
+                }
+                    .into_view()
+            }
+        }}
+    }
+}
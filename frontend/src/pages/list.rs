@@ -0,0 +1,262 @@
+use api_client::{MoveTodoDto, TodoResponseDto as Todo};
+use leptos::*;
+use leptos_router::{use_query_map, A};
+
+use crate::components::{ErrorBanner, Spinner, TitleEditor};
+use crate::due;
+use crate::i18n::{use_i18n, I18n};
+use crate::keyboard::{self, KeySequence, Shortcut};
+use crate::state::{
+    delete_todo, move_todo, rename_todo, set_completed, toggle_todo, use_ctx, Ctx, ListState,
+};
+
+/// 一覧の絞り込み（URLごとに固定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    All,
+    Active,
+    Completed,
+    /// `?q=` のタイトル検索
+    Search,
+}
+
+impl Filter {
+    fn matches(self, todo: &Todo, query: &str) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Active => !todo.completed,
+            Filter::Completed => todo.completed,
+            Filter::Search => todo
+                .title
+                .to_lowercase()
+                .contains(&query.trim().to_lowercase()),
+        }
+    }
+
+    fn empty_message(self, i18n: I18n, query: &str) -> String {
+        match self {
+            Filter::All => i18n.t("empty-all"),
+            Filter::Active => i18n.t("empty-active"),
+            Filter::Completed => i18n.t("empty-completed"),
+            Filter::Search => i18n.t_args("empty-search", [("query", query.to_string().into())]),
+        }
+    }
+}
+
+#[component]
+pub fn TodoListPage(filter: Filter) -> impl IntoView {
+    let ctx = use_ctx();
+    let i18n = use_i18n();
+    let query_map = use_query_map();
+    let query = move || query_map.with(|q| q.get("q").cloned().unwrap_or_default());
+    let state = ctx.list_state(move |todo| filter.matches(todo, &query()));
+    // ドラッグ中のTodoと、その時点でカーソルが乗っているTodo
+    let dragging = create_rw_signal(None::<i32>);
+    let drop_target = create_rw_signal(None::<i32>);
+
+    let keys = store_value(KeySequence::default());
+    let shortcuts = window_event_listener(ev::keydown, move |ev| {
+        if !keyboard::is_plain_key(&ev) {
+            return;
+        }
+        let Some(shortcut) = keys.try_update_value(|keys| keys.feed(&ev.key())).flatten() else {
+            return;
+        };
+        ev.prevent_default();
+
+        let query = query_map.with_untracked(|q| q.get("q").cloned().unwrap_or_default());
+        let visible: Vec<i32> = ctx.todos.with_untracked(|todos| {
+            todos
+                .iter()
+                .flatten()
+                .filter(|todo| filter.matches(todo, &query))
+                .map(|todo| todo.id)
+                .collect()
+        });
+        let selected = ctx
+            .selected
+            .get_untracked()
+            .filter(|id| visible.contains(id));
+
+        match (shortcut, selected) {
+            (Shortcut::Next | Shortcut::Previous, _) => {
+                let next = keyboard::step(&visible, selected, shortcut == Shortcut::Next);
+                ctx.selected.set(next);
+                if let Some(id) = next {
+                    keyboard::scroll_to(id);
+                }
+            }
+            (Shortcut::Toggle, Some(id)) => toggle_todo(ctx, id),
+            (Shortcut::Edit, Some(id)) => ctx.editing.set(Some(id)),
+            (Shortcut::Delete, Some(id)) => {
+                ctx.selected.set(keyboard::after_removal(&visible, id));
+                delete_todo(ctx, id);
+            }
+            (Shortcut::Search, _) => keyboard::focus(".search"),
+            (Shortcut::Deselect, _) => ctx.selected.set(None),
+            _ => {}
+        }
+    });
+    on_cleanup(move || shortcuts.remove());
+
+    view! {
+        <nav class="filters">
+            <A href="/" exact=true>{move || i18n.t("filter-all")}</A>
+            <A href="/active">{move || i18n.t("filter-active")}</A>
+            <A href="/completed">{move || i18n.t("filter-completed")}</A>
+        </nav>
+        {move || match state.get() {
+            ListState::Loading => view! { <Spinner/> }.into_view(),
+            ListState::Failed(e) => {
+                view! {
+                    <ErrorBanner
+                        message=i18n.t_args("load-todos-failed", [("error", i18n.error(&e).into())])
+                        on_retry=Callback::new(move |_| ctx.sync())
+                    />
+                }
+                    .into_view()
+            }
+            ListState::Empty => {
+                view! { <p class="empty-state">{move || filter.empty_message(i18n, &query())}</p> }
+                    .into_view()
+            }
+            ListState::Ready => {
+                view! {
+                    <ul class="todo-list">
+                        <For
+                            each=move || ctx.filtered(|todo| filter.matches(todo, &query()))
+                            key=|todo| (todo.id, todo.completed, todo.title.clone(), todo.due.clone())
+                            children=move |todo| {
+                                let id = todo.id;
+                                let title = todo.title.clone();
+                                view! {
+                                    <li
+                                        class:completed=todo.completed
+                                        class:selected=move || ctx.selected.get() == Some(id)
+                                        data-todo-id=id
+                                        class:dragging=move || dragging.get() == Some(id)
+                                        class:drop-target=move || drop_target.get() == Some(id)
+                                        draggable="true"
+                                        on:dragstart=move |ev| {
+                                            // Firefoxはデータを設定しないとドラッグが始まらない
+                                            if let Some(data) = ev.data_transfer() {
+                                                let _ = data.set_data("text/plain", &id.to_string());
+                                            }
+                                            dragging.set(Some(id));
+                                        }
+                                        on:dragover=move |ev| {
+                                            if dragging.get_untracked().is_some_and(|dragged| dragged != id) {
+                                                ev.prevent_default();
+                                                drop_target.set(Some(id));
+                                            }
+                                        }
+                                        on:dragleave=move |_| {
+                                            if drop_target.get_untracked() == Some(id) {
+                                                drop_target.set(None);
+                                            }
+                                        }
+                                        on:drop=move |ev| {
+                                            ev.prevent_default();
+                                            if let Some(dragged) = dragging.get_untracked() {
+                                                drop_onto(ctx, dragged, id);
+                                            }
+                                            drop_target.set(None);
+                                        }
+                                        on:dragend=move |_| {
+                                            dragging.set(None);
+                                            drop_target.set(None);
+                                        }
+                                    >
+                                        <div
+                                            class="todo-checkbox"
+                                            on:click=move |_| set_completed(ctx, id, !todo.completed)
+                                        >
+                                            <span class="checkmark">"✓"</span>
+                                        </div>
+                                        <Show
+                                            when=move || ctx.editing.get() == Some(id)
+                                            fallback={
+                                                let title = title.clone();
+                                                move || {
+                                                    view! {
+                                                        <div
+                                                            class="todo-text"
+                                                            title=move || i18n.t("edit-hint")
+                                                            on:dblclick=move |_| ctx.editing.set(Some(id))
+                                                        >
+                                                            {title.clone()}
+                                                        </div>
+                                                    }
+                                                }
+                                            }
+                                        >
+                                            <TitleEditor
+                                                initial=title.clone()
+                                                on_save=Callback::new(move |title| {
+                                                    ctx.editing.set(None);
+                                                    rename_todo(ctx, id, title);
+                                                })
+                                                on_cancel=Callback::new(move |_| ctx.editing.set(None))
+                                            />
+                                        </Show>
+                                        {todo.due.clone().map(|due| {
+                                            let overdue = !todo.completed && due::is_overdue(&due);
+                                            view! {
+                                                <span class="due" class:overdue=overdue>
+                                                    {move || {
+                                                        due::label(i18n, &due)
+                                                            .map(|date| i18n.t_args("due-on", [("date", date.into())]))
+                                                    }}
+                                                </span>
+                                            }
+                                        })}
+                                        <A class="todo-id" href=format!("/todos/{}", id)>
+                                            "#"
+                                            {id}
+                                        </A>
+                                        <button on:click=move |_| delete_todo(ctx, id)>
+                                            {move || i18n.t("delete")}
+                                        </button>
+                                    </li>
+                                }
+                            }
+                        />
+                    </ul>
+                    <p class="shortcut-hint">
+                        <kbd>"j"</kbd>"/"<kbd>"k"</kbd>" "{move || i18n.t("shortcut-move")}" · "
+                        <kbd>"x"</kbd>" "{move || i18n.t("shortcut-toggle")}" · "
+                        <kbd>"e"</kbd>" "{move || i18n.t("shortcut-edit")}" · "
+                        <kbd>"dd"</kbd>" "{move || i18n.t("shortcut-delete")}" · "
+                        <kbd>"/"</kbd>" "{move || i18n.t("shortcut-search")}" · "
+                        <kbd>"Ctrl+K"</kbd>" "{move || i18n.t("shortcut-commands")}
+                    </p>
+                }
+                    .into_view()
+            }
+        }}
+    }
+}
+
+/// ドロップ先のTodoの位置に移動する
+/// 下に向かって動かした場合はドロップ先の直後に、上に向かって動かした場合は直前に置く
+fn drop_onto(ctx: Ctx, dragged: i32, target: i32) {
+    let index = |id| {
+        ctx.todos
+            .with_untracked(|todos| todos.iter().flatten().position(|t| t.id == id))
+    };
+    let (Some(from), Some(to)) = (index(dragged), index(target)) else {
+        return;
+    };
+    let target = if from < to {
+        MoveTodoDto {
+            after: Some(target),
+            ..Default::default()
+        }
+    } else {
+        MoveTodoDto {
+            before: Some(target),
+            ..Default::default()
+        }
+    };
+    move_todo(ctx, dragged, target);
+}
@@ -0,0 +1,20 @@
+mod detail;
+mod list;
+
+pub use detail::TodoDetailPage;
+pub use list::{Filter, TodoListPage};
+
+use leptos::*;
+use leptos_router::A;
+
+use crate::i18n::use_i18n;
+
+#[component]
+pub fn NotFoundPage() -> impl IntoView {
+    let i18n = use_i18n();
+    view! {
+        <p class="empty-state">
+            {move || i18n.t("page-missing")}" "<A href="/">{move || i18n.t("back-to-list")}</A>
+        </p>
+    }
+}
@@ -0,0 +1,206 @@
+//! Ctrl+K のコマンドパレット
+//!
+//! 入力した語をすべて含むコマンドに絞り込み、↑↓で選んでEnterで実行する。
+
+use std::rc::Rc;
+
+use leptos::*;
+use leptos_router::{use_navigate, NavigateOptions};
+
+use crate::i18n::{use_i18n, I18n};
+use crate::keyboard;
+use crate::state::{clear_completed, delete_todo, set_all_completed, toggle_todo, use_ctx, Ctx};
+
+/// パレットから実行できる操作
+struct Command {
+    /// 表示名のメッセージID
+    label: &'static str,
+    /// 同じ操作のショートカット
+    shortcut: Option<&'static str>,
+    run: Rc<dyn Fn()>,
+}
+
+impl Command {
+    fn new(label: &'static str, shortcut: Option<&'static str>, run: impl Fn() + 'static) -> Self {
+        Self {
+            label,
+            shortcut,
+            run: Rc::new(run),
+        }
+    }
+
+    /// 表示中の言語の表示名が入力した語をすべて含むか（大文字・小文字は区別しない）
+    fn matches(&self, i18n: I18n, query: &str) -> bool {
+        let label = i18n.t(self.label).to_lowercase();
+        query
+            .to_lowercase()
+            .split_whitespace()
+            .all(|word| label.contains(word))
+    }
+}
+
+fn commands(ctx: Ctx) -> Vec<Command> {
+    let navigate = Rc::new(use_navigate());
+    let go = move |path: &'static str| {
+        let navigate = navigate.clone();
+        move || navigate(path, NavigateOptions::default())
+    };
+    let with_selected = move |action: fn(Ctx, i32)| {
+        move || {
+            if let Some(id) = ctx.selected.get_untracked() {
+                action(ctx, id);
+            }
+        }
+    };
+
+    vec![
+        Command::new("command-show-all", None, go("/")),
+        Command::new("command-show-active", None, go("/active")),
+        Command::new("command-show-completed", None, go("/completed")),
+        Command::new("command-search", Some("/"), || keyboard::focus(".search")),
+        Command::new("command-new", None, || keyboard::focus("#new-todo")),
+        Command::new("command-toggle", Some("x"), with_selected(toggle_todo)),
+        Command::new(
+            "command-edit",
+            Some("e"),
+            with_selected(|ctx, id| ctx.editing.set(Some(id))),
+        ),
+        Command::new(
+            "command-delete",
+            Some("dd"),
+            with_selected(|ctx, id| {
+                ctx.selected.set(None);
+                delete_todo(ctx, id);
+            }),
+        ),
+        Command::new("command-complete-all", None, move || {
+            set_all_completed(ctx, true)
+        }),
+        Command::new("command-activate-all", None, move || {
+            set_all_completed(ctx, false)
+        }),
+        Command::new("command-clear-completed", None, move || {
+            clear_completed(ctx)
+        }),
+        Command::new("command-sync", None, move || ctx.sync()),
+    ]
+}
+
+#[component]
+pub fn CommandPalette(open: RwSignal<bool>) -> impl IntoView {
+    let ctx = use_ctx();
+    let i18n = use_i18n();
+    let commands = store_value(commands(ctx));
+    let query = create_rw_signal(String::new());
+    // 絞り込んだ一覧の中で選択中の位置
+    let active = create_rw_signal(0usize);
+    let input_ref = create_node_ref::<html::Input>();
+
+    // 開くたびに入力を消す
+    create_effect(move |_| {
+        if open.get() {
+            query.set(String::new());
+            active.set(0);
+        }
+    });
+    create_effect(move |_| {
+        if let Some(input) = input_ref.get() {
+            let _ = input.focus();
+        }
+    });
+
+    let matching = move || {
+        query.with(|query| {
+            commands.with_value(|commands| {
+                commands
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, command)| command.matches(i18n, query))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>()
+            })
+        })
+    };
+    let run = move |index: usize| {
+        open.set(false);
+        let run = commands.with_value(|commands| commands[index].run.clone());
+        run();
+    };
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="palette-backdrop" on:click=move |_| open.set(false)>
+                <div
+                    class="palette"
+                    role="dialog"
+                    aria-label=move || i18n.t("palette-label")
+                    on:click=|ev| ev.stop_propagation()
+                >
+                    <input
+                        node_ref=input_ref
+                        class="palette-input"
+                        placeholder=move || i18n.t("palette-placeholder")
+                        aria-label=move || i18n.t("palette-input")
+                        prop:value=query
+                        on:input=move |ev| {
+                            query.set(event_target_value(&ev));
+                            active.set(0);
+                        }
+                        on:keydown=move |ev| {
+                            let count = matching().len();
+                            match ev.key().as_str() {
+                                "ArrowDown" if count > 0 => {
+                                    ev.prevent_default();
+                                    active.update(|i| *i = (*i + 1) % count);
+                                }
+                                "ArrowUp" if count > 0 => {
+                                    ev.prevent_default();
+                                    active.update(|i| *i = (*i + count - 1) % count);
+                                }
+                                "Enter" => {
+                                    ev.prevent_default();
+                                    if let Some(index) = matching().get(active.get_untracked()) {
+                                        run(*index);
+                                    }
+                                }
+                                "Escape" => open.set(false),
+                                _ => {}
+                            }
+                        }
+                    />
+                    <ul class="palette-list" role="listbox">
+                        {move || {
+                            let matching = matching();
+                            if matching.is_empty() {
+                                return view! { <li class="palette-empty">{i18n.t("palette-empty")}</li> }
+                                    .into_view();
+                            }
+                            matching
+                                .into_iter()
+                                .enumerate()
+                                .map(|(position, index)| {
+                                    let (label, shortcut) = commands
+                                        .with_value(|commands| {
+                                            (commands[index].label, commands[index].shortcut)
+                                        });
+                                    view! {
+                                        <li
+                                            role="option"
+                                            class:active=move || active.get() == position
+                                            aria-selected=move || (active.get() == position).to_string()
+                                            on:mouseenter=move |_| active.set(position)
+                                            on:click=move |_| run(index)
+                                        >
+                                            <span>{i18n.t(label)}</span>
+                                            {shortcut.map(|keys| view! { <kbd>{keys}</kbd> })}
+                                        </li>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </ul>
+                </div>
+            </div>
+        </Show>
+    }
+}
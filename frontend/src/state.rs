@@ -0,0 +1,353 @@
+use std::time::Duration;
+
+use api_client::{
+    ApiClient, ClientError, CreateTodoDto, ErrorCode, MoveTodoDto, TodoResponseDto as Todo,
+    UpdateTodoDto,
+};
+use leptos::*;
+
+use crate::i18n::I18n;
+use crate::offline::{self, storage, PendingOp, Snapshot};
+
+/// オフライン・同期失敗時に再送を試みる間隔
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 一覧の表示状態（件数が変わっただけでは一覧を作り直さないよう、状態だけを比較する）
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListState {
+    Loading,
+    Failed(ClientError),
+    Empty,
+    Ready,
+}
+
+/// サーバーとの同期状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStatus {
+    Synced,
+    Syncing,
+    /// 通信できない（未送信の操作は保存してあり、オンラインになったら送る）
+    Offline,
+    /// サーバーがエラーを返した（しばらくしてから再送する）
+    Failed,
+}
+
+/// 全ページで共有する状態（`provide_context` で配る）
+#[derive(Clone, Copy)]
+pub struct Ctx {
+    client: StoredValue<ApiClient>,
+    /// 表示する一覧（サーバーの状態に未送信の操作を重ねたもの）。未読み込みなら `None`
+    pub todos: RwSignal<Option<Vec<Todo>>>,
+    /// 一覧がない状態で読み込みに失敗した理由
+    pub load_error: RwSignal<Option<ClientError>>,
+    pub queue: RwSignal<Vec<PendingOp>>,
+    pub status: RwSignal<SyncStatus>,
+    /// 送信を諦めた操作の通知
+    pub failure: RwSignal<Option<ClientError>>,
+    /// キーボード操作で選択中のTodo
+    pub selected: RwSignal<Option<i32>>,
+    /// タイトルを編集中のTodo
+    pub editing: RwSignal<Option<i32>>,
+    /// 最後に同期した時点のサーバーの一覧（差分同期の起点）
+    snapshot: StoredValue<Snapshot>,
+    syncing: StoredValue<bool>,
+}
+
+impl Ctx {
+    /// 保存済みの一覧を表示してからサーバーと同期し、子コンポーネントに配る
+    /// エラーメッセージは表示中の言語で返してもらう
+    pub fn provide(i18n: I18n) -> Self {
+        let ctx = Self {
+            client: store_value(
+                ApiClient::new("").with_language(i18n.locale.get_untracked().as_str()),
+            ),
+            todos: create_rw_signal(storage::load_todos()),
+            load_error: create_rw_signal(None),
+            queue: create_rw_signal(storage::load_queue()),
+            status: create_rw_signal(SyncStatus::Syncing),
+            failure: create_rw_signal(None),
+            selected: create_rw_signal(None),
+            editing: create_rw_signal(None),
+            snapshot: store_value(storage::load_snapshot()),
+            syncing: store_value(false),
+        };
+        provide_context(ctx);
+
+        // 変更のたびに保存する
+        create_effect(move |_| {
+            ctx.todos.with(|todos| {
+                if let Some(todos) = todos {
+                    storage::save_todos(todos);
+                }
+            })
+        });
+        create_effect(move |_| ctx.queue.with(|queue| storage::save_queue(queue)));
+        create_effect(move |_| {
+            let locale = i18n.locale.get();
+            ctx.client
+                .update_value(|client| client.set_language(locale.as_str()));
+        });
+
+        window_event_listener(ev::online, move |_| ctx.sync());
+        window_event_listener(ev::offline, move |_| ctx.status.set(SyncStatus::Offline));
+        let _ = set_interval_with_handle(
+            move || {
+                if ctx.status.get_untracked() != SyncStatus::Synced {
+                    ctx.sync();
+                }
+            },
+            RETRY_INTERVAL,
+        );
+
+        ctx.sync();
+        ctx
+    }
+
+    /// 表示中の言語を設定したAPIクライアント
+    pub fn client(self) -> ApiClient {
+        self.client.get_value()
+    }
+
+    /// 一覧の表示状態（`filter` に一致するTodoがなければ `Empty`）
+    pub fn list_state(self, filter: impl Fn(&Todo) -> bool + 'static) -> Memo<ListState> {
+        create_memo(move |_| {
+            self.todos.with(|todos| match todos {
+                None => match self.load_error.get() {
+                    Some(e) => ListState::Failed(e),
+                    None => ListState::Loading,
+                },
+                Some(list) if !list.iter().any(&filter) => ListState::Empty,
+                Some(_) => ListState::Ready,
+            })
+        })
+    }
+
+    /// 表示中の一覧から `filter` に一致するTodo
+    pub fn filtered(self, filter: impl Fn(&Todo) -> bool) -> Vec<Todo> {
+        self.todos.with(|todos| {
+            todos
+                .iter()
+                .flatten()
+                .filter(|t| filter(t))
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// 操作をすぐに一覧へ反映し、送信待ちに積む
+    fn submit(self, op: PendingOp) {
+        self.todos
+            .update(|todos| op.apply(todos.get_or_insert_with(Vec::new)));
+        self.queue.update(|queue| offline::enqueue(queue, op));
+        self.sync();
+    }
+
+    /// 未送信の操作を送り、前回の同期以降のサーバーの変更を取り込む
+    pub fn sync(self) {
+        if self.syncing.get_value() {
+            return;
+        }
+        if !is_online() {
+            self.status.set(SyncStatus::Offline);
+            return;
+        }
+        self.syncing.set_value(true);
+        self.status.set(SyncStatus::Syncing);
+        spawn_local(async move {
+            let status = self.flush().await;
+            self.syncing.set_value(false);
+            self.status.set(status);
+        });
+    }
+
+    async fn flush(self) -> SyncStatus {
+        let client = self.client.get_value();
+
+        while let Some(op) = self.queue.with_untracked(|queue| queue.first().cloned()) {
+            let result = match &op {
+                PendingOp::Create {
+                    title, completed, ..
+                } => client
+                    .create_todo(&CreateTodoDto {
+                        title: title.clone(),
+                        completed: *completed,
+                        due: None,
+                    })
+                    .await
+                    .map(Some),
+                PendingOp::Update { id, changes } => {
+                    client.update_todo(*id, changes).await.map(|_| None)
+                }
+                PendingOp::Move { id, target } => client.move_todo(*id, target).await.map(|_| None),
+                PendingOp::Delete { id } => client.delete_todo(*id).await.map(|_| None),
+            };
+
+            match result {
+                Err(e) if is_transient(&e) => return status_for(&e),
+                // サーバー側で削除済みなら、サーバーの状態を優先して操作を捨てる
+                Err(e) if e.is_not_found() => self.complete(&op, None),
+                Err(e) => {
+                    self.failure.set(Some(e));
+                    self.complete(&op, None);
+                }
+                Ok(created) => self.complete(&op, created),
+            }
+        }
+
+        let token = self.snapshot.with_value(|snapshot| snapshot.token.clone());
+        match client.sync(token.as_deref()).await {
+            Ok(changes) => {
+                self.snapshot.update_value(|snapshot| {
+                    snapshot.apply(changes);
+                    storage::save_snapshot(snapshot);
+                });
+                let server = self.snapshot.with_value(|snapshot| snapshot.todos.clone());
+                let list = self
+                    .queue
+                    .with_untracked(|queue| offline::reconcile(server, queue));
+                self.todos.set(Some(list));
+                self.load_error.set(None);
+                SyncStatus::Synced
+            }
+            Err(e) => {
+                if self.todos.with_untracked(Option::is_none) {
+                    self.load_error.set(Some(e.clone()));
+                }
+                status_for(&e)
+            }
+        }
+    }
+
+    /// 送信が終わった操作を送信待ちから外す
+    fn complete(self, op: &PendingOp, created: Option<Todo>) {
+        // 送信中に作成ごと取り消された場合は、作成されたTodoを削除しに行く
+        if self.queue.with_untracked(|queue| queue.first() != Some(op)) {
+            if let Some(created) = created {
+                self.queue
+                    .update(|queue| queue.push(PendingOp::Delete { id: created.id }));
+            }
+            return;
+        }
+
+        self.queue.update(|queue| {
+            queue.remove(0);
+        });
+        if let (PendingOp::Create { temp_id, .. }, Some(created)) = (op, created) {
+            self.todos.update(|todos| {
+                self.queue.update(|queue| {
+                    offline::assign_server_id(
+                        todos.get_or_insert_with(Vec::new),
+                        queue,
+                        *temp_id,
+                        created.id,
+                    )
+                })
+            });
+        }
+    }
+}
+
+fn is_online() -> bool {
+    window().navigator().on_line()
+}
+
+/// 時間をおけば成功しうるエラーか（送信待ちに残して再送する）
+fn is_transient(e: &ClientError) -> bool {
+    match e.status() {
+        None => matches!(e, ClientError::Network(_)),
+        Some(status) => status == 429 || status >= 500,
+    }
+}
+
+fn status_for(e: &ClientError) -> SyncStatus {
+    match e {
+        ClientError::Network(_) => SyncStatus::Offline,
+        _ => SyncStatus::Failed,
+    }
+}
+
+/// タイトルの検証（ドメインと同じく空白のみは不可）
+pub fn validate_title(title: &str) -> Result<String, ErrorCode> {
+    let title = title.trim();
+    if title.is_empty() {
+        Err(ErrorCode::TitleEmpty)
+    } else {
+        Ok(title.to_string())
+    }
+}
+
+pub fn use_ctx() -> Ctx {
+    expect_context::<Ctx>()
+}
+
+pub fn add_todo(ctx: Ctx, title: String) {
+    ctx.submit(PendingOp::Create {
+        temp_id: storage::next_temp_id(),
+        title,
+        completed: false,
+    });
+}
+
+pub fn update_todo(ctx: Ctx, id: i32, changes: UpdateTodoDto) {
+    ctx.submit(PendingOp::Update { id, changes });
+}
+
+pub fn set_completed(ctx: Ctx, id: i32, completed: bool) {
+    update_todo(
+        ctx,
+        id,
+        UpdateTodoDto {
+            completed: Some(completed),
+            ..Default::default()
+        },
+    );
+}
+
+/// 完了・未完了を切り替える
+pub fn toggle_todo(ctx: Ctx, id: i32) {
+    let completed = ctx.todos.with_untracked(|todos| {
+        todos
+            .iter()
+            .flatten()
+            .find(|t| t.id == id)
+            .map(|t| t.completed)
+    });
+    if let Some(completed) = completed {
+        set_completed(ctx, id, !completed);
+    }
+}
+
+/// すべてのTodoを完了（`completed` がfalseなら未完了）にする
+pub fn set_all_completed(ctx: Ctx, completed: bool) {
+    for todo in ctx.filtered(|t| t.completed != completed) {
+        set_completed(ctx, todo.id, completed);
+    }
+}
+
+/// 完了したTodoをすべて削除する
+pub fn clear_completed(ctx: Ctx) {
+    for todo in ctx.filtered(|t| t.completed) {
+        delete_todo(ctx, todo.id);
+    }
+}
+
+/// タイトルを変更する（保存に失敗した場合は同期時にサーバーのタイトルに戻る）
+pub fn rename_todo(ctx: Ctx, id: i32, title: String) {
+    update_todo(
+        ctx,
+        id,
+        UpdateTodoDto {
+            title: Some(title),
+            ..Default::default()
+        },
+    );
+}
+
+/// `target` のTodoの直前・直後に移動する
+pub fn move_todo(ctx: Ctx, id: i32, target: MoveTodoDto) {
+    ctx.submit(PendingOp::Move { id, target });
+}
+
+pub fn delete_todo(ctx: Ctx, id: i32) {
+    ctx.submit(PendingOp::Delete { id });
+}
@@ -0,0 +1,259 @@
+//! インポート・エクスポート
+//!
+//! エクスポートはサーバーが書き出したファイルをそのままダウンロードする。
+//! インポートは先にドライランで行ごとの結果を表示し、確認してから作成する。
+
+use api_client::{ClientError, ImportReportDto, ImportRowStatus, TransferFormat};
+use leptos::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::i18n::{use_i18n, I18n};
+use crate::state::use_ctx;
+
+/// 選んだファイル
+#[derive(Clone)]
+struct Upload {
+    file_name: String,
+    content: String,
+}
+
+/// インポートできなかった理由
+#[derive(Clone)]
+enum Failure {
+    /// ファイルをテキストとして読めない
+    Read,
+    Api(ClientError),
+}
+
+/// 形式の表示名（どの言語でも同じ）
+fn format_label(format: TransferFormat) -> &'static str {
+    match format {
+        TransferFormat::Csv => "CSV",
+        TransferFormat::Json => "JSON",
+        TransferFormat::Markdown => "Markdown",
+        TransferFormat::TodoTxt => "todo.txt",
+    }
+}
+
+fn status_label(i18n: I18n, status: ImportRowStatus) -> String {
+    i18n.t(match status {
+        ImportRowStatus::Imported => "import-status-imported",
+        ImportRowStatus::Duplicate => "import-status-duplicate",
+        ImportRowStatus::Invalid => "import-status-invalid",
+        ImportRowStatus::Failed => "import-status-failed",
+    })
+}
+
+/// ファイルの内容をテキストとして読む
+async fn read_file(file: web_sys::File) -> Option<Upload> {
+    let content = JsFuture::from(file.text()).await.ok()?.as_string()?;
+    Some(Upload {
+        file_name: file.name(),
+        content,
+    })
+}
+
+#[component]
+pub fn TransferPanel() -> impl IntoView {
+    let ctx = use_ctx();
+    let i18n = use_i18n();
+    let upload = create_rw_signal(None::<Upload>);
+    let report = create_rw_signal(None::<ImportReportDto>);
+    let failure = create_rw_signal(None::<Failure>);
+    let busy = create_rw_signal(false);
+
+    // ドライランなら結果を表示し、そうでなければ作成したTodoを同期で取り込む
+    // 作成に失敗した行があれば結果を表示したままにする（もう一度インポートするとその行だけを作成する）
+    let run = move |dry_run: bool| {
+        let Some(file) = upload.get_untracked() else {
+            return;
+        };
+        busy.set(true);
+        spawn_local(async move {
+            let result = ctx
+                .client()
+                .import(&file.file_name, &file.content, None, dry_run)
+                .await;
+            busy.set(false);
+            match result {
+                Ok(result) if dry_run => report.set(Some(result)),
+                Ok(result) if result.failed > 0 => {
+                    report.set(Some(result));
+                    ctx.sync();
+                }
+                Ok(_) => {
+                    upload.set(None);
+                    report.set(None);
+                    ctx.sync();
+                }
+                Err(e) => failure.set(Some(Failure::Api(e))),
+            }
+        });
+    };
+    let close = move || {
+        upload.set(None);
+        report.set(None);
+    };
+
+    let on_file = move |ev: ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        // 同じファイルを選び直しても change が発生するように空にしておく
+        input.set_value("");
+        failure.set(None);
+        spawn_local(async move {
+            match read_file(file).await {
+                Some(file) => {
+                    upload.set(Some(file));
+                    run(true);
+                }
+                None => failure.set(Some(Failure::Read)),
+            }
+        });
+    };
+
+    view! {
+        <details class="transfer">
+            <summary>{move || i18n.t("transfer-label")}</summary>
+            <div class="transfer-body">
+                <span>{move || i18n.t("export-label")}</span>
+                {TransferFormat::ALL
+                    .into_iter()
+                    .map(|format| {
+                        view! {
+                            <a
+                                class="transfer-link"
+                                href=format!("/api/v1/export?format={}", format.as_str())
+                                download=format.file_name()
+                            >
+                                {format_label(format)}
+                            </a>
+                        }
+                    })
+                    .collect_view()}
+                <label class="transfer-link">
+                    {move || i18n.t("import-label")}
+                    <input
+                        type="file"
+                        accept=".csv,.json,.md,.markdown,.txt"
+                        disabled=move || busy.get()
+                        on:change=on_file
+                    />
+                </label>
+            </div>
+            {move || {
+                failure
+                    .get()
+                    .map(|failure| {
+                        let error = match failure {
+                            Failure::Read => i18n.t("import-read-failed"),
+                            Failure::Api(e) => i18n.error(&e),
+                        };
+                        view! {
+                            <p class="field-error" role="alert">
+                                {i18n.t_args("import-failed", [("error", error.into())])}
+                            </p>
+                        }
+                    })
+            }}
+        </details>
+        {move || {
+            report
+                .get()
+                .map(|report| {
+                    let file_name = upload.with(|file| {
+                        file.as_ref().map(|file| file.file_name.clone()).unwrap_or_default()
+                    });
+                    let imported = report.imported;
+                    view! {
+                        <div class="palette-backdrop" on:click=move |_| close()>
+                            <div
+                                class="import-dialog"
+                                role="dialog"
+                                aria-modal="true"
+                                on:click=|ev| ev.stop_propagation()
+                            >
+                                <h2>
+                                    {move || {
+                                        i18n.t_args("import-preview-title", [("file", file_name.clone().into())])
+                                    }}
+                                </h2>
+                                <p>
+                                    {move || {
+                                        i18n.t_args(
+                                            "import-summary",
+                                            [
+                                                ("imported", report.imported.into()),
+                                                ("duplicates", report.duplicates.into()),
+                                                ("invalid", report.invalid.into()),
+                                                ("failed", report.failed.into()),
+                                            ],
+                                        )
+                                    }}
+                                </p>
+                                <table class="import-rows">
+                                    <thead>
+                                        <tr>
+                                            <th>{move || i18n.t("import-line")}</th>
+                                            <th>{move || i18n.t("title-label")}</th>
+                                            <th>{move || i18n.t("import-status")}</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {report
+                                            .rows
+                                            .into_iter()
+                                            .map(|row| {
+                                                let status = row.status;
+                                                let reason = move || {
+                                                    let mut reason = row
+                                                        .error
+                                                        .map(|code| i18n.error_code(code))
+                                                        .unwrap_or_default();
+                                                    if let Some(detail) = &row.detail {
+                                                        reason = format!("{} ({})", reason, detail);
+                                                    }
+                                                    reason
+                                                };
+                                                view! {
+                                                    <tr class=format!("import-{}", status_class(status))>
+                                                        <td>{row.line}</td>
+                                                        <td>{row.title}</td>
+                                                        <td>
+                                                            {move || status_label(i18n, status)}
+                                                            <span class="field-error">{reason}</span>
+                                                        </td>
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </tbody>
+                                </table>
+                                <div class="dialog-actions">
+                                    <button on:click=move |_| close()>{move || i18n.t("cancel")}</button>
+                                    <button
+                                        disabled=move || busy.get() || imported == 0
+                                        on:click=move |_| run(false)
+                                    >
+                                        {move || i18n.t_args("import-confirm", [("count", imported.into())])}
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
+
+/// 行のスタイル（`import-imported` など）
+fn status_class(status: ImportRowStatus) -> &'static str {
+    match status {
+        ImportRowStatus::Imported => "imported",
+        ImportRowStatus::Duplicate => "duplicate",
+        ImportRowStatus::Invalid => "invalid",
+        ImportRowStatus::Failed => "failed",
+    }
+}